@@ -0,0 +1,175 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::{ErrorDef, RamlError, get_error};
+use std::collections::HashMap;
+use std::ptr;
+use yaml::*;
+use example::{get_example_value, ExampleValue};
+use types::Types;
+use library::{find_declaration, Libraries, Library};
+
+/// Applied annotations, by the name of their annotation type.
+pub type Annotations = HashMap<String, AnnotationValue>;
+
+/// The value given to an annotation applied with a key such as `(deprecated):`.
+#[derive(Debug)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AnnotationValue {
+    value: ExampleValue,
+    #[cfg_attr(feature = "serde", serde(skip, default = "::serialization::default_marker"))]
+    marker: Marker,
+}
+
+// Where the annotation was applied is not part of its value.
+impl PartialEq for AnnotationValue {
+    fn eq(&self, other: &AnnotationValue) -> bool {
+        self.value == other.value
+    }
+}
+
+impl AnnotationValue {
+    pub fn value(&self) -> &ExampleValue {
+        &self.value
+    }
+}
+
+/// An annotation applied to a node of the document, as listed by `Raml::find_annotations`.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct AnnotatedNode<'a> {
+    path: String,
+    kind: &'static str,
+    value: &'a AnnotationValue,
+}
+
+impl<'a> AnnotatedNode<'a> {
+    pub fn new(path: String, kind: &'static str, value: &'a AnnotationValue) -> AnnotatedNode<'a> {
+        AnnotatedNode { path, kind, value }
+    }
+
+    /// Where the node is: empty for the document root, the title of a documentation item, the
+    /// full uri of a resource, e.g. `/users/{userId}`, the name of a method followed by the uri
+    /// of its resource, e.g. `get /users/{userId}`, or the name of a type, e.g. `User`, with the
+    /// name of a property, e.g. `User.email`.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// The annotation target the node is, e.g. `Resource` or `Method`.
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    pub fn value(&self) -> &'a AnnotationValue {
+        self.value
+    }
+}
+
+/// The annotation type applied by a key, if the key is an annotation, e.g. `(deprecated)`.
+pub fn get_annotation_name(key: &str) -> Option<&str> {
+    if key.len() > 2 && key.starts_with('(') && key.ends_with(')') {
+        Some(&key[1..key.len() - 1])
+    } else {
+        None
+    }
+}
+
+pub fn get_annotation(cursor: &mut ForwardCursor,
+                      annotations: &mut Option<Annotations>,
+                      key: MappingKey)
+                      -> Result<(), RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    let value = get_example_value(cursor)?;
+    let name = get_annotation_name(&key.value).unwrap_or_default().to_string();
+    annotations.get_or_insert_with(HashMap::new).insert(name,
+                                                        AnnotationValue {
+                                                            value,
+                                                            marker: key.marker,
+                                                        });
+    Ok(())
+}
+
+/// Reads an annotation like `get_annotation`, also keeping its value as written in `extensions`.
+pub fn get_annotation_and_node(cursor: &mut ForwardCursor,
+                               annotations: &mut Option<Annotations>,
+                               extensions: &mut Option<Extensions>,
+                               key: MappingKey)
+                               -> Result<(), RamlError> {
+    let tokens = get_raw_value(cursor)?;
+    let node = get_node(&mut cursor.replay(tokens.clone()))?;
+    extensions.get_or_insert_with(HashMap::new).insert(key.value.clone(), node);
+    // The value is read again from the cursor, followed by the tokens of the enclosing node.
+    for token in tokens.into_iter().rev() {
+        cursor.put_back(token);
+    }
+    get_annotation(cursor, annotations, key)
+}
+
+/// The value of the annotation `name` in `annotations`, like `HashMap::get`, except that a name
+/// also matches an annotation applied with another name for the same annotation type, e.g.
+/// through a library that is used by a library.
+pub fn find_annotation<'a>(annotations: Option<&'a Annotations>,
+                           name: &str,
+                           annotation_types: Option<&Types>,
+                           libraries: Option<&Libraries>)
+                           -> Option<&'a AnnotationValue> {
+    let annotations = annotations?;
+    if let Some(value) = annotations.get(name) {
+        return Some(value);
+    }
+    let find = |name: &str| {
+        find_declaration(name,
+                         annotation_types,
+                         libraries,
+                         Library::annotation_types,
+                         None)
+            .ok()
+            .and_then(|annotation_type| annotation_type)
+    };
+    let annotation_type = find(name)?;
+    let mut names: Vec<&String> = annotations.keys().collect();
+    names.sort();
+    names.into_iter()
+        .find(|applied| find(applied).is_some_and(|other| ptr::eq(annotation_type, other)))
+        .map(|applied| &annotations[applied])
+}
+
+/// Checks each applied annotation has a declared annotation type that allows `target`, e.g.
+/// `Method`.
+pub fn check_annotations(annotations: Option<&Annotations>,
+                         annotation_types: Option<&Types>,
+                         libraries: Option<&Libraries>,
+                         target: &str)
+                         -> Result<(), RamlError> {
+    let annotations = match annotations {
+        Some(annotations) => annotations,
+        None => return Ok(()),
+    };
+    let mut names: Vec<&String> = annotations.keys().collect();
+    names.sort();
+    for name in names {
+        let marker = annotations[name].marker;
+        let annotation_type = match find_declaration(name,
+                                                     annotation_types,
+                                                     libraries,
+                                                     Library::annotation_types,
+                                                     Some(marker))? {
+            Some(annotation_type) => annotation_type,
+            None => {
+                return Err(get_error(ErrorDef::UndeclaredAnnotationType { name: name.clone() },
+                                     Some(marker)))
+            }
+        };
+        if let Some(allowed_targets) = annotation_type.allowed_targets() {
+            if !allowed_targets.iter().any(|allowed| allowed == target) {
+                return Err(get_error(ErrorDef::InvalidAnnotationTarget {
+                                         name: name.clone(),
+                                         target: target.to_string(),
+                                     },
+                                     Some(marker)));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use yaml::*;
+use yaml_rust::scanner::Marker;
+use type_expression::{TypeExpression, parse_type_expression};
+use example::{Example, Examples, get_example, get_examples};
+use parser::MediaTypes;
+use types::{get_properties, BuiltInType, Properties};
+
+pub type Bodies = Vec<Body>;
+
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Body {
+    // None until a body declared without media types is expanded using the root mediaType.
+    media_type: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    body_type: Option<TypeExpression>,
+    #[cfg_attr(feature = "serde", serde(rename = "properties"))]
+    form_properties: Option<Properties>,
+    example: Option<Example>,
+    examples: Option<Examples>,
+    // The `body` key of a body declared without media types, where an error expanding it is
+    // reported.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    marker: Option<Marker>,
+}
+
+// Where the body was declared is not part of its value.
+impl PartialEq for Body {
+    fn eq(&self, other: &Body) -> bool {
+        self.media_type == other.media_type &&
+        self.body_type == other.body_type &&
+        self.form_properties == other.form_properties &&
+        self.example == other.example &&
+        self.examples == other.examples
+    }
+}
+
+impl Body {
+    pub fn media_type(&self) -> &str {
+        self.media_type.as_deref().unwrap_or_default()
+    }
+
+    pub fn body_type(&self) -> Option<&TypeExpression> {
+        self.body_type.as_ref()
+    }
+
+    /// The properties declared directly under the body. These are the fields of a form body,
+    /// e.g. one of type `multipart/form-data`.
+    pub fn form_properties(&self) -> Option<&Properties> {
+        self.form_properties.as_ref()
+    }
+
+    pub fn example(&self) -> Option<&Example> {
+        self.example.as_ref()
+    }
+
+    pub fn examples(&self) -> Option<&Examples> {
+        self.examples.as_ref()
+    }
+}
+
+/// Reads the bodies of the node with the `body` key at `marker`.
+pub fn get_bodies(cursor: &mut ForwardCursor, marker: Marker) -> Result<Bodies, RamlError> {
+    let mut bodies: Bodies = vec![];
+    let mut default_body: Option<Body> = None;
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        if key.value.contains('/') && default_body.is_none() {
+            let mut body = get_body(cursor)?;
+            body.media_type = Some(key.value);
+            bodies.push(body);
+        } else if bodies.is_empty() {
+            let body = default_body.get_or_insert_with(|| {
+                Body {
+                    marker: Some(marker),
+                    ..Body::default()
+                }
+            });
+            get_body_facet(cursor, body, key)?;
+        } else {
+            // Media type keys and type facets cannot be mixed in the same body.
+            return Err(get_error(ErrorDef::UnexpectedKeyRoot {
+                                     field: key.value,
+                                     level: HierarchyLevel::Body,
+                                 },
+                                 Some(key.marker)));
+        }
+    }
+    if let Some(body) = default_body {
+        bodies.push(body);
+    }
+    Ok(bodies)
+}
+
+fn get_body(cursor: &mut ForwardCursor) -> Result<Body, RamlError> {
+    let mut body = Body::default();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        None => {
+            // Either the shorthand `application/json: Person` or an empty declaration.
+            if let Some(expression) = get_optional_scalar(cursor)? {
+                body.body_type = Some(parse_type_expression(&expression.value,
+                                                            expression.marker)?);
+            }
+            return Ok(body);
+        }
+    };
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        get_body_facet(cursor, &mut body, key)?;
+    }
+    Ok(body)
+}
+
+fn get_body_facet(cursor: &mut ForwardCursor,
+                  body: &mut Body,
+                  key: MappingKey)
+                  -> Result<(), RamlError> {
+    match key.value.as_str() {
+        "type" => {
+            let expression = get_single_scalar(cursor)?;
+            let body_type = parse_type_expression(&expression.value, expression.marker)?;
+            if body.form_properties.is_some() {
+                check_object_type(&body_type, key.marker)?;
+            }
+            body.body_type = Some(body_type);
+        }
+        "properties" => {
+            if let Some(ref body_type) = body.body_type {
+                check_object_type(body_type, key.marker)?;
+            }
+            body.form_properties = Some(get_properties(cursor)?);
+        }
+        "example" => body.example = Some(get_example(cursor)?),
+        "examples" => body.examples = Some(get_examples(cursor)?),
+        _ => skip_unknown_key(cursor, key, HierarchyLevel::Body)?,
+    }
+    Ok(())
+}
+
+// Only an object can have properties. A declared type is assumed to be one.
+fn check_object_type(body_type: &TypeExpression, marker: Marker) -> Result<(), RamlError> {
+    let is_object = match *body_type {
+        TypeExpression::Named(ref name) => {
+            BuiltInType::from_name(name).is_none_or(|built_in| built_in == BuiltInType::Object)
+        }
+        _ => false,
+    };
+    if is_object {
+        return Ok(());
+    }
+    Err(get_error(ErrorDef::PropertiesOnNonObjectBody { body_type: body_type.to_string() },
+                  Some(marker)))
+}
+
+/// Expands a body declared without media types into one body per root media type.
+pub fn expand_default_media_types(bodies: &mut Bodies,
+                                  media_types: Option<&MediaTypes>)
+                                  -> Result<(), RamlError> {
+    let position = match bodies.iter().position(|b| b.media_type.is_none()) {
+        Some(position) => position,
+        None => return Ok(()),
+    };
+    let media_types = match media_types {
+        Some(media_types) => media_types,
+        None => {
+            return Err(get_error(ErrorDef::MissingDefaultMediaType, bodies[position].marker))
+        }
+    };
+    let default_body = bodies.remove(position);
+    for media_type in media_types {
+        bodies.push(Body {
+            media_type: Some(media_type.to_string()),
+            body_type: default_body.body_type.clone(),
+            form_properties: default_body.form_properties.clone(),
+            example: default_body.example.clone(),
+            examples: default_body.examples.clone(),
+            marker: None,
+        });
+    }
+    Ok(())
+}
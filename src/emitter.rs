@@ -0,0 +1,682 @@
+use std::collections::HashMap;
+use parser::{Protocol, Raml, SecuredBy, SecuredByEntries, SecurityScheme};
+use security_settings::SecuritySchemeSettings;
+use method::{Method, METHOD_NAMES};
+use resource::Resource;
+use resource_type::{ResourceType, ResourceTypeReference};
+use traits::{TemplateParameters, Trait, TraitReference, TraitReferences};
+use parameter::{NamedParameter, NamedParameters};
+use body::Bodies;
+use response::{Response, Responses};
+use types::{Properties, RamlType, Types, XmlFacets};
+use example::{Example, ExampleValue, Examples};
+use annotation::{get_annotation_name, Annotations};
+use yaml::{Extensions, YamlNode};
+
+/// A node of the document being written.
+enum Node {
+    /// A string, quoted when YAML would not read it back as written.
+    String(String),
+    /// A string that is also quoted when it would be read back as a number, boolean or null, as
+    /// examples and annotations keep the difference.
+    TypedString(String),
+    /// A number, boolean or null.
+    Plain(String),
+    Sequence(Vec<Node>),
+    Mapping(Vec<(String, Node)>),
+}
+
+type Entries = Vec<(String, Node)>;
+
+/// Writes a document as RAML 1.0. Declarations are written in order of their names, and methods
+/// in the order of `METHOD_NAMES`. Unknown keys kept by a parse that allows them are written back.
+pub fn emit_raml(raml: &Raml) -> String {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "title", Some(text(raml.title())));
+    add(&mut entries, "description", raml.description().map(text));
+    add(&mut entries, "version", raml.version().map(text));
+    add(&mut entries, "baseUri", raml.base_uri().map(text));
+    add(&mut entries,
+        "baseUriParameters",
+        raml.base_uri_parameters().map(named_parameters_node));
+    if !raml.protocols_inferred() {
+        add(&mut entries,
+            "protocols",
+            raml.protocols().map(protocols_node));
+    }
+    add(&mut entries,
+        "mediaType",
+        raml.media_types().map(|media_types| {
+            Node::Sequence(media_types.iter().map(|media_type| text(media_type.as_str())).collect())
+        }));
+    add(&mut entries,
+        "documentation",
+        raml.documentation().map(|documentation| {
+            Node::Sequence(documentation.iter()
+                .map(|entry| {
+                    let mut entries = vec![("title".to_string(), text(entry.title())),
+                                           ("content".to_string(), text(entry.content()))];
+                    add_annotations(&mut entries, entry.annotations());
+                    Node::Mapping(entries)
+                })
+                .collect())
+        }));
+    add(&mut entries,
+        "securitySchemes",
+        raml.security_schemes().map(|schemes| mapping(schemes, security_scheme_node)));
+    add(&mut entries, "securedBy", raml.secured_by().map(secured_by_node));
+    add(&mut entries, "types", raml.types().map(types_node));
+    add(&mut entries, "annotationTypes", raml.annotation_types().map(types_node));
+    add(&mut entries, "traits", raml.traits().map(|traits| mapping(traits, trait_node)));
+    add(&mut entries,
+        "resourceTypes",
+        raml.resource_types().map(|resource_types| mapping(resource_types, resource_type_node)));
+    add_annotations(&mut entries, raml.annotations());
+    add_unknown_keys(&mut entries, raml.extra());
+    for resource in raml.resources() {
+        entries.push((resource.relative_uri().to_string(), resource_node(resource)));
+    }
+
+    let mut out = "#%RAML 1.0\n".to_string();
+    write_entries(&mut out, &entries, 0);
+    out
+}
+
+fn add(entries: &mut Entries, key: &str, node: Option<Node>) {
+    if let Some(node) = node {
+        entries.push((key.to_string(), node));
+    }
+}
+
+fn add_annotations(entries: &mut Entries, annotations: Option<&Annotations>) {
+    for (name, annotation) in sorted(annotations.into_iter().flatten()) {
+        entries.push((format!("({})", name), example_value_node(annotation.value())));
+    }
+}
+
+// The extensions other than annotations, which are written from their parsed values.
+fn add_unknown_keys(entries: &mut Entries, extensions: Option<&Extensions>) {
+    for (key, node) in sorted(extensions.into_iter().flatten()) {
+        if get_annotation_name(key).is_none() {
+            entries.push((key.clone(), yaml_node(node)));
+        }
+    }
+}
+
+fn yaml_node(node: &YamlNode) -> Node {
+    match *node {
+        YamlNode::Scalar(ref value, _) => text(value),
+        YamlNode::Sequence(ref values, _) => Node::Sequence(values.iter().map(yaml_node).collect()),
+        YamlNode::Mapping(ref entries, _) => {
+            Node::Mapping(entries.iter()
+                .map(|(key, value)| (key.clone(), yaml_node(value)))
+                .collect())
+        }
+    }
+}
+
+fn text(value: &str) -> Node {
+    Node::String(value.to_string())
+}
+
+fn plain<T: ToString>(value: T) -> Node {
+    Node::Plain(value.to_string())
+}
+
+fn strings_node(values: &[String]) -> Node {
+    Node::Sequence(values.iter().map(|value| text(value)).collect())
+}
+
+/// The entries of a map in order of their keys, so the same document is always written the
+/// same way.
+fn sorted<'a, K, V, I>(map: I) -> Vec<(&'a K, &'a V)>
+    where K: Ord + 'a,
+          V: 'a,
+          I: Iterator<Item = (&'a K, &'a V)>
+{
+    let mut entries: Vec<(&K, &V)> = map.collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn mapping<K: Ord + ToString, V, F: Fn(&V) -> Node>(map: &HashMap<K, V>, f: F) -> Node {
+    Node::Mapping(sorted(map.iter()).into_iter().map(|(key, value)| (key.to_string(), f(value)))
+                                             .collect())
+}
+
+fn protocols_node(protocols: &[Protocol]) -> Node {
+    Node::Sequence(protocols.iter().map(protocol_node).collect())
+}
+
+fn protocol_node(protocol: &Protocol) -> Node {
+    text(protocol.as_str())
+}
+
+fn security_scheme_node(scheme: &SecurityScheme) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "type", Some(text(scheme.security_type().as_str())));
+    add(&mut entries, "displayName", scheme.display_name().map(text));
+    add(&mut entries, "description", scheme.description().map(text));
+    if let Some(described_by) = scheme.described_by() {
+        let mut described_by_entries: Entries = vec![];
+        add(&mut described_by_entries,
+            "headers",
+            described_by.headers().map(named_parameters_node));
+        add(&mut described_by_entries,
+            "queryParameters",
+            described_by.query_parameters().map(named_parameters_node));
+        add(&mut described_by_entries,
+            "responses",
+            described_by.responses().map(responses_node));
+        entries.push(("describedBy".to_string(), Node::Mapping(described_by_entries)));
+    }
+    add(&mut entries, "settings", scheme.settings().map(settings_node));
+    Node::Mapping(entries)
+}
+
+fn settings_node(settings: &SecuritySchemeSettings) -> Node {
+    let mut entries: Entries = vec![];
+    match *settings {
+        SecuritySchemeSettings::OAuth1(ref settings) => {
+            add(&mut entries, "requestTokenUri", Some(text(settings.request_token_uri())));
+            add(&mut entries, "authorizationUri", Some(text(settings.authorization_uri())));
+            add(&mut entries,
+                "tokenCredentialsUri",
+                Some(text(settings.token_credentials_uri())));
+            add(&mut entries,
+                "signatures",
+                settings.signatures().map(|signatures| {
+                    Node::Sequence(signatures.iter().map(|s| text(s.as_str())).collect())
+                }));
+        }
+        SecuritySchemeSettings::OAuth2(ref settings) => {
+            add(&mut entries, "authorizationUri", settings.authorization_uri().map(text));
+            add(&mut entries, "accessTokenUri", settings.access_token_uri().map(text));
+            let grants = settings.authorization_grants().iter().map(|g| text(g.as_str()));
+            add(&mut entries, "authorizationGrants", Some(Node::Sequence(grants.collect())));
+            add(&mut entries, "scopes", settings.scopes().map(strings_node));
+        }
+        SecuritySchemeSettings::PassThrough(ref settings) => {
+            add(&mut entries, "queryParameterName", settings.query_parameter_name().map(text));
+            add(&mut entries, "headerName", settings.header_name().map(text));
+        }
+        SecuritySchemeSettings::Other(ref value) => return example_value_node(value),
+    }
+    Node::Mapping(entries)
+}
+
+fn secured_by_node(secured_by: &SecuredByEntries) -> Node {
+    Node::Sequence(secured_by.iter()
+        .map(|entry| match *entry {
+                 SecuredBy::Null => plain("null"),
+                 SecuredBy::Scheme(ref name) => text(name),
+                 SecuredBy::Parameterized(ref name, ref parameters) => {
+                     Node::Mapping(vec![(name.clone(), mapping(parameters, example_value_node))])
+                 }
+             })
+        .collect())
+}
+
+fn reference_node(name: &str, parameters: Option<&TemplateParameters>) -> Node {
+    match parameters {
+        Some(parameters) => {
+            Node::Mapping(vec![(name.to_string(), mapping(parameters, |value| text(value)))])
+        }
+        None => text(name),
+    }
+}
+
+fn trait_references_node(references: &TraitReferences) -> Node {
+    Node::Sequence(references.iter()
+        .map(|reference: &TraitReference| reference_node(reference.name(), reference.parameters()))
+        .collect())
+}
+
+fn resource_type_reference_node(reference: &ResourceTypeReference) -> Node {
+    reference_node(reference.name(), reference.parameters())
+}
+
+fn trait_node(raml_trait: &Trait) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "usage", raml_trait.usage().map(text));
+    add(&mut entries, "displayName", raml_trait.display_name().map(text));
+    add(&mut entries, "description", raml_trait.description().map(text));
+    add(&mut entries, "securedBy", raml_trait.secured_by().map(secured_by_node));
+    add(&mut entries,
+        "queryParameters",
+        raml_trait.query_parameters().map(named_parameters_node));
+    add(&mut entries, "headers", raml_trait.headers().map(named_parameters_node));
+    add(&mut entries, "body", raml_trait.body().map(bodies_node));
+    add(&mut entries, "responses", raml_trait.responses().map(responses_node));
+    add_annotations(&mut entries, raml_trait.annotations());
+    Node::Mapping(entries)
+}
+
+fn method_node(method: &Method) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "displayName", method.display_name().map(text));
+    add(&mut entries, "description", method.description().map(text));
+    add(&mut entries, "is", method.is().map(trait_references_node));
+    add(&mut entries, "securedBy", method.secured_by().map(secured_by_node));
+    add(&mut entries, "protocols", method.protocols().map(protocols_node));
+    add(&mut entries, "uriParameters", method.uri_parameters().map(named_parameters_node));
+    add(&mut entries, "queryParameters", method.query_parameters().map(named_parameters_node));
+    add(&mut entries, "queryString", method.query_string().map(type_node));
+    add(&mut entries, "headers", method.headers().map(named_parameters_node));
+    add(&mut entries, "body", method.body().map(bodies_node));
+    add(&mut entries, "responses", method.responses().map(responses_node));
+    add_annotations(&mut entries, method.annotations());
+    add_unknown_keys(&mut entries, method.extra());
+    Node::Mapping(entries)
+}
+
+fn resource_type_node(resource_type: &ResourceType) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "usage", resource_type.usage().map(text));
+    add(&mut entries, "displayName", resource_type.display_name().map(text));
+    add(&mut entries, "description", resource_type.description().map(text));
+    add(&mut entries,
+        "type",
+        resource_type.resource_type().map(resource_type_reference_node));
+    add(&mut entries, "is", resource_type.is().map(trait_references_node));
+    add(&mut entries, "securedBy", resource_type.secured_by().map(secured_by_node));
+    add(&mut entries,
+        "uriParameters",
+        resource_type.uri_parameters().map(named_parameters_node));
+    add_annotations(&mut entries, resource_type.annotations());
+    for name in METHOD_NAMES.iter() {
+        if let Some(method) = resource_type.methods().get(*name) {
+            let key = if resource_type.is_optional_method(name) {
+                format!("{}?", name)
+            } else {
+                name.to_string()
+            };
+            entries.push((key, method_node(method)));
+        }
+    }
+    Node::Mapping(entries)
+}
+
+fn resource_node(resource: &Resource) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "displayName", resource.display_name().map(text));
+    add(&mut entries, "description", resource.description().map(text));
+    add(&mut entries, "type", resource.resource_type().map(resource_type_reference_node));
+    add(&mut entries, "is", resource.is().map(trait_references_node));
+    add(&mut entries, "securedBy", resource.secured_by().map(secured_by_node));
+    add(&mut entries, "uriParameters", resource.uri_parameters().map(named_parameters_node));
+    add_annotations(&mut entries, resource.annotations());
+    add_unknown_keys(&mut entries, resource.extra());
+    for name in METHOD_NAMES.iter() {
+        if let Some(method) = resource.methods().get(*name) {
+            entries.push((name.to_string(), method_node(method)));
+        }
+    }
+    for nested in resource.resources() {
+        entries.push((nested.relative_uri().to_string(), resource_node(nested)));
+    }
+    Node::Mapping(entries)
+}
+
+fn named_parameters_node(parameters: &NamedParameters) -> Node {
+    mapping(parameters, named_parameter_node)
+}
+
+fn named_parameter_node(parameter: &NamedParameter) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "type", parameter.param_type().map(text));
+    add(&mut entries, "displayName", parameter.display_name().map(text));
+    add(&mut entries, "description", parameter.description().map(text));
+    // Parameters are required unless declared otherwise.
+    if !parameter.required() {
+        entries.push(("required".to_string(), plain(false)));
+    }
+    add(&mut entries, "default", parameter.default_value().map(text));
+    add(&mut entries, "enum", parameter.enum_values().map(|values| strings_node(values)));
+    add(&mut entries, "minimum", parameter.minimum().map(plain));
+    add(&mut entries, "maximum", parameter.maximum().map(plain));
+    add(&mut entries, "multipleOf", parameter.multiple_of().map(plain));
+    add(&mut entries, "minLength", parameter.min_length().map(plain));
+    add(&mut entries, "maxLength", parameter.max_length().map(plain));
+    add(&mut entries, "example", parameter.example().map(example_node));
+    add(&mut entries, "examples", parameter.examples().map(examples_node));
+    Node::Mapping(entries)
+}
+
+fn responses_node(responses: &Responses) -> Node {
+    mapping(responses, response_node)
+}
+
+fn response_node(response: &Response) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "description", response.description().map(text));
+    add(&mut entries, "headers", response.headers().map(named_parameters_node));
+    add(&mut entries, "body", response.body().map(bodies_node));
+    Node::Mapping(entries)
+}
+
+fn bodies_node(bodies: &Bodies) -> Node {
+    let mut entries: Entries = vec![];
+    for body in bodies {
+        let mut facets: Entries = vec![];
+        add(&mut facets, "type", body.body_type().map(|t| text(&t.to_string())));
+        add(&mut facets, "properties", body.form_properties().map(properties_node));
+        add(&mut facets, "example", body.example().map(example_node));
+        add(&mut facets, "examples", body.examples().map(examples_node));
+        // A body declared without a media type is only kept by traits and resource types,
+        // which are not expanded with the root media types.
+        if body.media_type().is_empty() {
+            return Node::Mapping(facets);
+        }
+        entries.push((body.media_type().to_string(), Node::Mapping(facets)));
+    }
+    Node::Mapping(entries)
+}
+
+fn types_node(types: &Types) -> Node {
+    mapping(types, type_node)
+}
+
+fn type_node(raml_type: &RamlType) -> Node {
+    let mut entries: Entries = vec![];
+    match raml_type.parent_types() {
+        [] => {}
+        [parent] => add(&mut entries, "type", Some(text(&parent.to_string()))),
+        parents => {
+            add(&mut entries,
+                "type",
+                Some(Node::Sequence(parents.iter().map(|p| text(&p.to_string())).collect())))
+        }
+    }
+    add(&mut entries, "type", raml_type.schema().map(|schema| text(schema.raw())));
+    add(&mut entries, "displayName", raml_type.display_name().map(text));
+    add(&mut entries, "description", raml_type.description().map(text));
+    add(&mut entries, "default", raml_type.default_value().map(text));
+    add(&mut entries, "pattern", raml_type.pattern().map(text));
+    add(&mut entries, "minLength", raml_type.min_length().map(plain));
+    add(&mut entries, "maxLength", raml_type.max_length().map(plain));
+    add(&mut entries, "minimum", raml_type.minimum().map(plain));
+    add(&mut entries, "maximum", raml_type.maximum().map(plain));
+    add(&mut entries, "multipleOf", raml_type.multiple_of().map(plain));
+    add(&mut entries, "enum", raml_type.enum_values().map(|values| strings_node(values)));
+    add(&mut entries, "properties", object_properties_node(raml_type));
+    if !raml_type.additional_properties() {
+        entries.push(("additionalProperties".to_string(), plain(false)));
+    }
+    add(&mut entries, "minProperties", raml_type.min_properties().map(plain));
+    add(&mut entries, "maxProperties", raml_type.max_properties().map(plain));
+    add(&mut entries, "items", raml_type.items().map(type_node));
+    add(&mut entries, "minItems", raml_type.min_items().map(plain));
+    add(&mut entries, "maxItems", raml_type.max_items().map(plain));
+    add(&mut entries, "uniqueItems", raml_type.unique_items().map(plain));
+    add(&mut entries, "example", raml_type.example().map(example_node));
+    add(&mut entries, "examples", raml_type.examples().map(examples_node));
+    add(&mut entries, "facets", raml_type.facets().map(properties_node));
+    for (facet, value) in sorted(raml_type.facet_values().into_iter().flatten()) {
+        entries.push((facet.clone(), example_value_node(value.value())));
+    }
+    add(&mut entries, "xml", raml_type.xml().map(xml_node));
+    add(&mut entries, "discriminator", raml_type.discriminator().map(text));
+    add(&mut entries, "discriminatorValue", raml_type.discriminator_value().map(text));
+    add(&mut entries, "format", raml_type.format().map(|format| plain(format.as_str())));
+    add(&mut entries, "fileTypes", raml_type.file_types().map(strings_node));
+    add(&mut entries,
+        "allowedTargets",
+        raml_type.allowed_targets().map(|targets| strings_node(targets)));
+    add_annotations(&mut entries, raml_type.annotations());
+    // A type that only names the type it extends is written in the shorthand form, e.g.
+    // `Email: string`.
+    if entries.len() == 1 && entries[0].0 == "type" {
+        if let Node::String(_) = entries[0].1 {
+            return entries.remove(0).1;
+        }
+    }
+    Node::Mapping(entries)
+}
+
+fn xml_node(xml: &XmlFacets) -> Node {
+    let mut entries: Entries = vec![];
+    add(&mut entries, "attribute", xml.attribute().map(plain));
+    add(&mut entries, "wrapped", xml.wrapped().map(plain));
+    add(&mut entries, "name", xml.name().map(text));
+    add(&mut entries, "namespace", xml.namespace().map(text));
+    add(&mut entries, "prefix", xml.prefix().map(text));
+    Node::Mapping(entries)
+}
+
+fn object_properties_node(raml_type: &RamlType) -> Option<Node> {
+    let patterns = raml_type.pattern_properties();
+    let mut entries = match (raml_type.properties().map(properties_node), patterns) {
+        (Some(Node::Mapping(entries)), _) => entries,
+        (_, Some(_)) => vec![],
+        (node, None) => return node,
+    };
+    for (pattern, property) in sorted(patterns.into_iter().flatten()) {
+        entries.push((format!("/{}/", pattern), type_node(property.property_type())));
+    }
+    Some(Node::Mapping(entries))
+}
+
+fn properties_node(properties: &Properties) -> Node {
+    let mut entries: Entries = vec![];
+    for (name, property) in sorted(properties.iter()) {
+        let node = type_node(property.property_type());
+        if name.ends_with('?') {
+            // The question mark is part of the name, so whether the property is required is
+            // given explicitly.
+            let mut facets = match node {
+                Node::Mapping(facets) => facets,
+                node => vec![("type".to_string(), node)],
+            };
+            facets.insert(0, ("required".to_string(), plain(property.required())));
+            entries.push((name.clone(), Node::Mapping(facets)));
+        } else if property.required() {
+            entries.push((name.clone(), node));
+        } else {
+            entries.push((format!("{}?", name), node));
+        }
+    }
+    Node::Mapping(entries)
+}
+
+fn examples_node(examples: &Examples) -> Node {
+    mapping(examples, example_node)
+}
+
+fn example_node(example: &Example) -> Node {
+    // A mapping with a `value` key would be read back as the declaration of an example.
+    let has_value_key = match *example.value() {
+        ExampleValue::Mapping(ref entries) => entries.iter().any(|(key, _)| key == "value"),
+        _ => false,
+    };
+    if example.display_name().is_none() && example.description().is_none() &&
+       example.strict().is_none() && !has_value_key {
+        return example_value_node(example.value());
+    }
+    let mut entries: Entries = vec![];
+    add(&mut entries, "displayName", example.display_name().map(text));
+    add(&mut entries, "description", example.description().map(text));
+    add(&mut entries, "strict", example.strict().map(plain));
+    add(&mut entries, "value", Some(example_value_node(example.value())));
+    Node::Mapping(entries)
+}
+
+fn example_value_node(value: &ExampleValue) -> Node {
+    match *value {
+        ExampleValue::String(ref value) => Node::TypedString(value.clone()),
+        ExampleValue::Number(number) => plain(number),
+        ExampleValue::Boolean(boolean) => plain(boolean),
+        ExampleValue::Null => plain("null"),
+        ExampleValue::Sequence(ref values) => {
+            Node::Sequence(values.iter().map(example_value_node).collect())
+        }
+        ExampleValue::Mapping(ref entries) => {
+            Node::Mapping(entries.iter()
+                .map(|(key, value)| (key.clone(), example_value_node(value)))
+                .collect())
+        }
+    }
+}
+
+fn write_entries(out: &mut String, entries: &[(String, Node)], indent: usize) {
+    for (key, node) in entries {
+        out.push_str(&" ".repeat(indent));
+        out.push_str(&scalar(key, false, false));
+        out.push(':');
+        write_value(out, node, indent);
+    }
+}
+
+/// Writes the value of a key or sequence entry, starting on the line of the key. Nested blocks
+/// are indented past `indent`, the indentation of the key.
+fn write_value(out: &mut String, node: &Node, indent: usize) {
+    match *node {
+        Node::Mapping(ref entries) if !entries.is_empty() => {
+            out.push('\n');
+            write_entries(out, entries, indent + 2);
+        }
+        Node::Sequence(ref items) if !is_flow_sequence(items) => {
+            out.push('\n');
+            for item in items {
+                write_sequence_entry(out, item, indent + 2);
+            }
+        }
+        Node::String(ref value) |
+        Node::TypedString(ref value) if is_literal_block(value) => {
+            write_literal_block(out, value, indent + 2);
+        }
+        _ => {
+            // A scalar written after its key is not inside a flow collection.
+            let value = match *node {
+                Node::String(ref value) => scalar(value, false, false),
+                Node::TypedString(ref value) => scalar(value, false, true),
+                _ => flow(node),
+            };
+            out.push(' ');
+            out.push_str(&value);
+            out.push('\n');
+        }
+    }
+}
+
+fn write_sequence_entry(out: &mut String, node: &Node, indent: usize) {
+    match *node {
+        Node::Mapping(ref entries) if !entries.is_empty() => {
+            // The entries are written as though indented past the dash, and the first of them
+            // starts on the line of the dash.
+            let mut mapping = String::new();
+            write_entries(&mut mapping, entries, indent + 2);
+            out.push_str(&" ".repeat(indent));
+            out.push_str("- ");
+            out.push_str(&mapping[indent + 2..]);
+        }
+        _ => {
+            out.push_str(&" ".repeat(indent));
+            out.push('-');
+            write_value(out, node, indent);
+        }
+    }
+}
+
+/// A sequence of single line scalars, written on one line, e.g. `[HTTP, HTTPS]`.
+fn is_flow_sequence(items: &[Node]) -> bool {
+    items.iter().all(|item| match *item {
+                         Node::String(ref value) |
+                         Node::TypedString(ref value) => !value.contains('\n'),
+                         Node::Plain(_) => true,
+                         Node::Sequence(_) |
+                         Node::Mapping(_) => false,
+                     })
+}
+
+/// Whether a multi-line string is written as a literal block, e.g. `description: |`. Strings
+/// starting with whitespace would need an indentation indicator, and are quoted instead.
+fn is_literal_block(value: &str) -> bool {
+    let content = value.trim_end_matches('\n');
+    content.contains('\n') && !content.starts_with(char::is_whitespace) &&
+    !value.chars().any(|c| c.is_control() && c != '\n' && c != '\t')
+}
+
+fn write_literal_block(out: &mut String, value: &str, indent: usize) {
+    let content = value.trim_end_matches('\n');
+    // The chomping indicator keeps the number of trailing line breaks.
+    let trailing = value.len() - content.len();
+    out.push_str(match trailing {
+                     0 => " |-\n",
+                     1 => " |\n",
+                     _ => " |+\n",
+                 });
+    for line in content.split('\n') {
+        if !line.is_empty() {
+            out.push_str(&" ".repeat(indent));
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    for _ in 1..trailing {
+        out.push('\n');
+    }
+}
+
+/// A node written on a single line.
+fn flow(node: &Node) -> String {
+    match *node {
+        Node::String(ref value) => scalar(value, true, false),
+        Node::TypedString(ref value) => scalar(value, true, true),
+        Node::Plain(ref value) => value.clone(),
+        Node::Sequence(ref items) => {
+            let items: Vec<String> = items.iter().map(flow).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Node::Mapping(ref entries) => {
+            let entries: Vec<String> = entries.iter()
+                .map(|(key, value)| format!("{}: {}", scalar(key, true, false), flow(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// A string as a scalar, quoted when it would not be read back as written: in a flow
+/// collection when `in_flow` is set, and as a string rather than another value when `typed` is
+/// set.
+fn scalar(value: &str, in_flow: bool, typed: bool) -> String {
+    let has_control = value.chars().any(char::is_control);
+    let needs_quotes = value.is_empty() || has_control ||
+                       value.starts_with(|c: char| {
+                                             c.is_whitespace() ||
+                                             "-?:,[]{}#&*!|>'\"%@`".contains(c)
+                                         }) ||
+                       value.ends_with(|c: char| c.is_whitespace() || c == ':') ||
+                       value.contains(": ") || value.contains(" #") ||
+                       (in_flow && value.contains(|c: char| ",[]{}:".contains(c))) ||
+                       (typed && is_typed_scalar(value));
+    if !needs_quotes {
+        value.to_string()
+    } else if !has_control {
+        format!("'{}'", value.replace('\'', "''"))
+    } else {
+        let mut quoted = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\t' => quoted.push_str("\\t"),
+                '\r' => quoted.push_str("\\r"),
+                c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+}
+
+/// Whether a plain scalar would be read as a number, boolean or null.
+fn is_typed_scalar(value: &str) -> bool {
+    match value.to_lowercase().as_str() {
+        "null" | "~" | "true" | "false" | "yes" | "no" | "on" | "off" => true,
+        _ => value.parse::<f64>().is_ok(),
+    }
+}
@@ -3,10 +3,22 @@ use yaml_rust::scanner::{Marker, ScanError};
 use std::fmt;
 
 #[derive(Debug)]
+#[derive(Clone)]
 pub enum HierarchyLevel {
     DocumentRoot,
     Documentation,
     SecurityScheme,
+    UriParameter,
+    Resource,
+    Method,
+    QueryParameter,
+    Header,
+    Response,
+    Body,
+    Trait,
+    ResourceType,
+    Library,
+    Type,
 }
 
 impl fmt::Display for HierarchyLevel {
@@ -15,6 +27,17 @@ impl fmt::Display for HierarchyLevel {
             HierarchyLevel::DocumentRoot => "document root",
             HierarchyLevel::Documentation => "documentation",
             HierarchyLevel::SecurityScheme => "security scheme",
+            HierarchyLevel::UriParameter => "uri parameter",
+            HierarchyLevel::Resource => "resource",
+            HierarchyLevel::Method => "method",
+            HierarchyLevel::QueryParameter => "query parameter",
+            HierarchyLevel::Header => "header",
+            HierarchyLevel::Response => "response",
+            HierarchyLevel::Body => "body",
+            HierarchyLevel::Trait => "trait",
+            HierarchyLevel::ResourceType => "resource type",
+            HierarchyLevel::Library => "library",
+            HierarchyLevel::Type => "type",
         };
         write!(f, "{}", printable)
     }
@@ -35,40 +58,458 @@ pub enum ErrorDef {
         found: TokenTypeDef,
     },
     MissingRamlVersion,
+    UnsupportedRamlVersion { version: String },
     MissingField {
         field: String,
         level: HierarchyLevel,
     },
+    UnexpectedProtocol { level: HierarchyLevel },
+    InvalidProtocol { protocol: String },
+    QueryStringWithQueryParameters { level: HierarchyLevel },
+    PropertiesOnNonObjectBody { body_type: String },
+    MissingProtocols { level: HierarchyLevel },
+    InvalidSecuritySchemeType,
+    InvalidBoolean { facet: String, value: String },
+    InvalidInteger { facet: String, value: String },
+    InvalidNumber { value: String },
+    InvalidUnsignedInteger { value: String },
+    InvalidBaseUri { uri: String },
+    MissingBaseUriVersion,
+    ReservedBaseUriParameter { name: String },
+    UnusedBaseUriParameter { name: String },
+    UndeclaredSecurityScheme {
+        name: String,
+        level: HierarchyLevel,
+    },
+    InvalidStatusCode { code: String },
+    MissingDefaultMediaType,
+    UndeclaredUriParameter { name: String },
+    UnusedUriParameter { name: String },
+    UnexpectedTypeFacet { facet: String, type_name: String },
+    ItemsOnNonArrayType { type_name: String },
+    InvalidTypeExpression { expression: String },
+    UndeclaredType { name: String, type_name: String },
+    SchemaTypeReference { name: String, type_name: String },
+    CircularTypeInheritance { cycle: Vec<String> },
+    CircularTypeDependency { types: Vec<String> },
+    WidenedTypeFacet { facet: String, type_name: String },
+    MissingTypeFacet { facet: String, type_name: String },
+    InvalidDiscriminator { discriminator: String, type_name: String },
+    InvalidDatetimeFormat { format: String },
+    InvalidDatetimeExample { value: String, type_name: String },
+    #[cfg_attr(not(feature = "regex"), allow(dead_code))]
+    InvalidPropertyPattern { pattern: String, type_name: String },
+    #[cfg_attr(feature = "regex", allow(dead_code))]
+    UnsupportedPropertyPattern { pattern: String, type_name: String },
+    ExamplePropertyCount { count: u64, type_name: String },
+    UndeclaredExampleProperty { property: String, type_name: String },
+    EmptyEnum,
+    ValueNotInEnum { facet: String, value: String },
+    InvalidExampleFacet { facet: String },
+    UndeclaredResourceType { name: String },
+    CircularResourceType { cycle: Vec<String> },
+    UndeclaredTrait {
+        name: String,
+        level: HierarchyLevel,
+    },
+    UndeclaredTemplateParameter {
+        name: String,
+        level: HierarchyLevel,
+        template: String,
+    },
+    UnknownTemplateFunction { name: String },
+    UndeclaredAnnotationType { name: String },
+    UndeclaredLibrary { namespace: String },
+    UnresolvedInclude { path: String },
+    MissingIncludeResolver { path: String },
+    CircularInclude { cycle: Vec<String> },
+    UnreadableFile {
+        path: String,
+        message: String,
+    },
+    InvalidYaml { info: String },
+    LimitExceeded { limit: String, max: usize },
+    MissingLibraryHeader,
+    MissingFragmentHeader,
+    UnknownFragment { kind: String },
+    MissingOverlayHeader,
+    UnexpectedSetting {
+        key: String,
+        scheme: Option<String>,
+    },
+    DuplicateKey {
+        key: String,
+        level: HierarchyLevel,
+    },
+    InvalidAuthorizationGrant { grant: String },
+    InvalidOAuth1Signature { signature: String },
+    RestrictedOverlayNode { node: String },
+    FragmentNotApiDocument { kind: String },
+    InvalidAnnotationTarget {
+        name: String,
+        target: String,
+    },
+    InvalidFacetRange {
+        min_facet: String,
+        max_facet: String,
+    },
+    UndeclaredBaseUriParameter { name: String },
+    InvalidMediaType { media_type: String },
+    EmptyField {
+        field: String,
+        level: HierarchyLevel,
+    },
+    UnreadableSource { message: String },
+    InvalidUtf8 { offset: usize },
+}
+
+/// The kind of a `RamlError`, for handling errors without matching on their messages.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum ErrorKind {
+    UnexpectedKeyRoot,
+    UnexpectedEntry,
+    UnexpectedEntryMulti,
+    MissingRamlVersion,
+    UnsupportedRamlVersion,
+    MissingField,
     UnexpectedProtocol,
+    InvalidProtocol,
+    QueryStringWithQueryParameters,
+    PropertiesOnNonObjectBody,
     MissingProtocols,
     InvalidSecuritySchemeType,
+    InvalidBoolean,
+    InvalidInteger,
+    InvalidNumber,
+    InvalidUnsignedInteger,
+    InvalidBaseUri,
+    MissingBaseUriVersion,
+    ReservedBaseUriParameter,
+    UnusedBaseUriParameter,
+    UndeclaredSecurityScheme,
+    InvalidStatusCode,
+    MissingDefaultMediaType,
+    UndeclaredUriParameter,
+    UnusedUriParameter,
+    UnexpectedTypeFacet,
+    ItemsOnNonArrayType,
+    InvalidTypeExpression,
+    UndeclaredType,
+    SchemaTypeReference,
+    CircularTypeInheritance,
+    CircularTypeDependency,
+    WidenedTypeFacet,
+    MissingTypeFacet,
+    InvalidDiscriminator,
+    InvalidDatetimeFormat,
+    InvalidDatetimeExample,
+    InvalidPropertyPattern,
+    UnsupportedPropertyPattern,
+    ExamplePropertyCount,
+    UndeclaredExampleProperty,
+    EmptyEnum,
+    ValueNotInEnum,
+    InvalidExampleFacet,
+    UndeclaredResourceType,
+    CircularResourceType,
+    UndeclaredTrait,
+    UndeclaredTemplateParameter,
+    UnknownTemplateFunction,
+    UndeclaredAnnotationType,
+    UndeclaredLibrary,
+    UnresolvedInclude,
+    MissingIncludeResolver,
+    CircularInclude,
+    UnreadableFile,
+    InvalidYaml,
+    LimitExceeded,
+    MissingLibraryHeader,
+    MissingFragmentHeader,
+    UnknownFragment,
+    MissingOverlayHeader,
+    UnexpectedSetting,
+    DuplicateKey,
+    InvalidAuthorizationGrant,
+    InvalidOAuth1Signature,
+    RestrictedOverlayNode,
+    FragmentNotApiDocument,
+    InvalidAnnotationTarget,
+    InvalidFacetRange,
+    UndeclaredBaseUriParameter,
+    InvalidMediaType,
+    EmptyField,
+    UnreadableSource,
+    InvalidUtf8,
+}
+
+impl ErrorDef {
+    fn kind(&self) -> ErrorKind {
+        match *self {
+            ErrorDef::UnexpectedKeyRoot { .. } => ErrorKind::UnexpectedKeyRoot,
+            ErrorDef::UnexpectedEntry { .. } => ErrorKind::UnexpectedEntry,
+            ErrorDef::UnexpectedEntryMulti { .. } => ErrorKind::UnexpectedEntryMulti,
+            ErrorDef::MissingRamlVersion => ErrorKind::MissingRamlVersion,
+            ErrorDef::UnsupportedRamlVersion { .. } => ErrorKind::UnsupportedRamlVersion,
+            ErrorDef::MissingField { .. } => ErrorKind::MissingField,
+            ErrorDef::UnexpectedProtocol { .. } => ErrorKind::UnexpectedProtocol,
+            ErrorDef::InvalidProtocol { .. } => ErrorKind::InvalidProtocol,
+            ErrorDef::QueryStringWithQueryParameters { .. } => {
+                ErrorKind::QueryStringWithQueryParameters
+            }
+            ErrorDef::PropertiesOnNonObjectBody { .. } => ErrorKind::PropertiesOnNonObjectBody,
+            ErrorDef::MissingProtocols { .. } => ErrorKind::MissingProtocols,
+            ErrorDef::InvalidSecuritySchemeType => ErrorKind::InvalidSecuritySchemeType,
+            ErrorDef::InvalidBoolean { .. } => ErrorKind::InvalidBoolean,
+            ErrorDef::InvalidInteger { .. } => ErrorKind::InvalidInteger,
+            ErrorDef::InvalidNumber { .. } => ErrorKind::InvalidNumber,
+            ErrorDef::InvalidUnsignedInteger { .. } => ErrorKind::InvalidUnsignedInteger,
+            ErrorDef::InvalidBaseUri { .. } => ErrorKind::InvalidBaseUri,
+            ErrorDef::MissingBaseUriVersion => ErrorKind::MissingBaseUriVersion,
+            ErrorDef::ReservedBaseUriParameter { .. } => ErrorKind::ReservedBaseUriParameter,
+            ErrorDef::UnusedBaseUriParameter { .. } => ErrorKind::UnusedBaseUriParameter,
+            ErrorDef::UndeclaredSecurityScheme { .. } => ErrorKind::UndeclaredSecurityScheme,
+            ErrorDef::InvalidStatusCode { .. } => ErrorKind::InvalidStatusCode,
+            ErrorDef::MissingDefaultMediaType => ErrorKind::MissingDefaultMediaType,
+            ErrorDef::UndeclaredUriParameter { .. } => ErrorKind::UndeclaredUriParameter,
+            ErrorDef::UnusedUriParameter { .. } => ErrorKind::UnusedUriParameter,
+            ErrorDef::UnexpectedTypeFacet { .. } => ErrorKind::UnexpectedTypeFacet,
+            ErrorDef::ItemsOnNonArrayType { .. } => ErrorKind::ItemsOnNonArrayType,
+            ErrorDef::InvalidTypeExpression { .. } => ErrorKind::InvalidTypeExpression,
+            ErrorDef::UndeclaredType { .. } => ErrorKind::UndeclaredType,
+            ErrorDef::SchemaTypeReference { .. } => ErrorKind::SchemaTypeReference,
+            ErrorDef::CircularTypeInheritance { .. } => ErrorKind::CircularTypeInheritance,
+            ErrorDef::CircularTypeDependency { .. } => ErrorKind::CircularTypeDependency,
+            ErrorDef::WidenedTypeFacet { .. } => ErrorKind::WidenedTypeFacet,
+            ErrorDef::MissingTypeFacet { .. } => ErrorKind::MissingTypeFacet,
+            ErrorDef::InvalidDiscriminator { .. } => ErrorKind::InvalidDiscriminator,
+            ErrorDef::InvalidDatetimeFormat { .. } => ErrorKind::InvalidDatetimeFormat,
+            ErrorDef::InvalidDatetimeExample { .. } => ErrorKind::InvalidDatetimeExample,
+            ErrorDef::InvalidPropertyPattern { .. } => ErrorKind::InvalidPropertyPattern,
+            ErrorDef::UnsupportedPropertyPattern { .. } => ErrorKind::UnsupportedPropertyPattern,
+            ErrorDef::ExamplePropertyCount { .. } => ErrorKind::ExamplePropertyCount,
+            ErrorDef::UndeclaredExampleProperty { .. } => ErrorKind::UndeclaredExampleProperty,
+            ErrorDef::EmptyEnum => ErrorKind::EmptyEnum,
+            ErrorDef::ValueNotInEnum { .. } => ErrorKind::ValueNotInEnum,
+            ErrorDef::InvalidExampleFacet { .. } => ErrorKind::InvalidExampleFacet,
+            ErrorDef::UndeclaredResourceType { .. } => ErrorKind::UndeclaredResourceType,
+            ErrorDef::CircularResourceType { .. } => ErrorKind::CircularResourceType,
+            ErrorDef::UndeclaredTrait { .. } => ErrorKind::UndeclaredTrait,
+            ErrorDef::UndeclaredTemplateParameter { .. } => ErrorKind::UndeclaredTemplateParameter,
+            ErrorDef::UnknownTemplateFunction { .. } => ErrorKind::UnknownTemplateFunction,
+            ErrorDef::UndeclaredAnnotationType { .. } => ErrorKind::UndeclaredAnnotationType,
+            ErrorDef::UndeclaredLibrary { .. } => ErrorKind::UndeclaredLibrary,
+            ErrorDef::UnresolvedInclude { .. } => ErrorKind::UnresolvedInclude,
+            ErrorDef::MissingIncludeResolver { .. } => ErrorKind::MissingIncludeResolver,
+            ErrorDef::CircularInclude { .. } => ErrorKind::CircularInclude,
+            ErrorDef::UnreadableFile { .. } => ErrorKind::UnreadableFile,
+            ErrorDef::InvalidYaml { .. } => ErrorKind::InvalidYaml,
+            ErrorDef::LimitExceeded { .. } => ErrorKind::LimitExceeded,
+            ErrorDef::MissingLibraryHeader => ErrorKind::MissingLibraryHeader,
+            ErrorDef::MissingFragmentHeader => ErrorKind::MissingFragmentHeader,
+            ErrorDef::UnknownFragment { .. } => ErrorKind::UnknownFragment,
+            ErrorDef::MissingOverlayHeader => ErrorKind::MissingOverlayHeader,
+            ErrorDef::UnexpectedSetting { .. } => ErrorKind::UnexpectedSetting,
+            ErrorDef::DuplicateKey { .. } => ErrorKind::DuplicateKey,
+            ErrorDef::InvalidAuthorizationGrant { .. } => ErrorKind::InvalidAuthorizationGrant,
+            ErrorDef::InvalidOAuth1Signature { .. } => ErrorKind::InvalidOAuth1Signature,
+            ErrorDef::RestrictedOverlayNode { .. } => ErrorKind::RestrictedOverlayNode,
+            ErrorDef::FragmentNotApiDocument { .. } => ErrorKind::FragmentNotApiDocument,
+            ErrorDef::InvalidAnnotationTarget { .. } => ErrorKind::InvalidAnnotationTarget,
+            ErrorDef::InvalidFacetRange { .. } => ErrorKind::InvalidFacetRange,
+            ErrorDef::UndeclaredBaseUriParameter { .. } => ErrorKind::UndeclaredBaseUriParameter,
+            ErrorDef::InvalidMediaType { .. } => ErrorKind::InvalidMediaType,
+            ErrorDef::EmptyField { .. } => ErrorKind::EmptyField,
+            ErrorDef::UnreadableSource { .. } => ErrorKind::UnreadableSource,
+            ErrorDef::InvalidUtf8 { .. } => ErrorKind::InvalidUtf8,
+        }
+    }
+
+    fn subject(&self) -> Option<Subject> {
+        match *self {
+            ErrorDef::UnexpectedKeyRoot { ref field, .. } |
+            ErrorDef::MissingField { ref field, .. } |
+            ErrorDef::EmptyField { ref field, .. } => Some(Subject::Field(field.clone())),
+            ErrorDef::UnexpectedSetting { ref key, .. } |
+            ErrorDef::DuplicateKey { ref key, .. } => Some(Subject::Field(key.clone())),
+            ErrorDef::UnexpectedTypeFacet { ref facet, .. } |
+            ErrorDef::WidenedTypeFacet { ref facet, .. } |
+            ErrorDef::MissingTypeFacet { ref facet, .. } |
+            ErrorDef::InvalidDiscriminator { discriminator: ref facet, .. } |
+            ErrorDef::ValueNotInEnum { ref facet, .. } |
+            ErrorDef::InvalidExampleFacet { ref facet } => Some(Subject::Field(facet.clone())),
+            ErrorDef::UndeclaredExampleProperty { ref property, .. } => {
+                Some(Subject::Field(property.clone()))
+            }
+            ErrorDef::ReservedBaseUriParameter { ref name } |
+            ErrorDef::UnusedBaseUriParameter { ref name } |
+            ErrorDef::UndeclaredBaseUriParameter { ref name } |
+            ErrorDef::UndeclaredSecurityScheme { ref name, .. } |
+            ErrorDef::UndeclaredUriParameter { ref name } |
+            ErrorDef::UnusedUriParameter { ref name } |
+            ErrorDef::UndeclaredType { ref name, .. } |
+            ErrorDef::SchemaTypeReference { ref name, .. } |
+            ErrorDef::UndeclaredResourceType { ref name } |
+            ErrorDef::UndeclaredTrait { ref name, .. } |
+            ErrorDef::UndeclaredTemplateParameter { ref name, .. } |
+            ErrorDef::UnknownTemplateFunction { ref name } |
+            ErrorDef::UndeclaredAnnotationType { ref name } |
+            ErrorDef::InvalidAnnotationTarget { ref name, .. } => Some(Subject::Name(name.clone())),
+            ErrorDef::UndeclaredLibrary { ref namespace } => Some(Subject::Name(namespace.clone())),
+            ErrorDef::UnresolvedInclude { ref path } |
+            ErrorDef::MissingIncludeResolver { ref path } |
+            ErrorDef::UnreadableFile { ref path, .. } => Some(Subject::Path(path.clone())),
+            ErrorDef::UnsupportedRamlVersion { ref version } => {
+                Some(Subject::Version(version.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+// What an error is about, returned by the `field`, `name` and `path` accessors of `RamlError`.
+#[derive(Debug)]
+enum Subject {
+    Field(String),
+    Name(String),
+    Path(String),
+    Version(String),
 }
 
-#[derive(Default)]
 #[derive(Debug)]
 pub struct RamlError {
+    // The message followed by its position and key path, when it has them.
     error: String,
+    message_len: usize,
+    marker: Option<Marker>,
+    kind: ErrorKind,
+    subject: Option<Subject>,
+    key_path: Box<[String]>,
 }
 
 impl RamlError {
-    fn new(error: &str) -> RamlError {
-        RamlError { error: error.to_string() }
+    fn new(error: &ErrorDef, message: String, marker: Option<Marker>) -> RamlError {
+        let message_len = message.len();
+        let formatted = match marker {
+            // The marker properties are private, so work around this by constructing a
+            // ScanError and use the display format.
+            Some(marker) => format!("{}", ScanError::new(marker, &message)),
+            None => message,
+        };
+        RamlError {
+            error: formatted,
+            message_len,
+            marker,
+            kind: error.kind(),
+            subject: error.subject(),
+            key_path: Box::new([]),
+        }
+    }
+
+    /// The error found in the node with the given key path, which is appended to its text.
+    pub fn with_key_path(mut self, key_path: Vec<String>) -> RamlError {
+        if !key_path.is_empty() {
+            self.error = format!("{} (in {})", self.error, key_path.join(" > "));
+        }
+        self.key_path = key_path.into_boxed_slice();
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The field or key the error is about, e.g. the missing field of a `MissingField` error or
+    /// the unexpected key of an `UnexpectedKeyRoot` error.
+    pub fn field(&self) -> Option<&str> {
+        match self.subject {
+            Some(Subject::Field(ref field)) => Some(field),
+            _ => None,
+        }
+    }
+
+    /// The name of the declaration or parameter the error refers to, e.g. the undeclared trait
+    /// of an `UndeclaredTrait` error.
+    pub fn name(&self) -> Option<&str> {
+        match self.subject {
+            Some(Subject::Name(ref name)) => Some(name),
+            _ => None,
+        }
     }
 
-    fn with_marker(error: &str, marker: Marker) -> RamlError {
-        // The marker properties are private, so work around this by constructing a ScanError
-        // and use the display format.
-        let error = format!("{}", ScanError::new(marker, error));
-        RamlError { error: error }
+    /// The path of the included document the error is about.
+    pub fn path(&self) -> Option<&str> {
+        match self.subject {
+            Some(Subject::Path(ref path)) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The keys of the nodes enclosing the error, outermost first, e.g.
+    /// `["securitySchemes", "oauth_2_0", "describedBy"]`. Empty for an error in the document
+    /// root, or about the document as a whole.
+    pub fn key_path(&self) -> &[String] {
+        &self.key_path
     }
 
+    /// The RAML version a document declares, for an `UnsupportedRamlVersion` error.
+    pub fn version(&self) -> Option<&str> {
+        match self.subject {
+            Some(Subject::Version(ref version)) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// The message, followed by the line and column of the error when it has a position and the
+    /// key path of the node it is in, e.g. `... at line 9 column 7 (in traits > paged)`.
     pub fn error(&self) -> &str {
         self.error.as_str()
     }
+
+    /// The message, without the position of the error.
+    pub fn message(&self) -> &str {
+        &self.error[..self.message_len]
+    }
+
+    pub fn marker(&self) -> Option<Marker> {
+        self.marker
+    }
+
+    /// The line of the error, starting from 1.
+    pub fn line(&self) -> Option<usize> {
+        self.marker.map(|marker| marker_position(marker).1)
+    }
+
+    /// The column of the error, starting from 1.
+    pub fn column(&self) -> Option<usize> {
+        self.marker.map(|marker| marker_position(marker).2 + 1)
+    }
+
+    /// The offset of the error from the start of the source, in characters.
+    pub fn index(&self) -> Option<usize> {
+        self.marker.map(|marker| marker_position(marker).0)
+    }
+}
+
+// Reads the index, line and column of a marker from its debug format,
+// `Marker { index: 0, line: 1, col: 0 }`, as the marker doesn't expose them.
+pub fn marker_position(marker: Marker) -> (usize, usize, usize) {
+    let debug = format!("{:?}", marker);
+    let mut fields = debug.split(|c: char| !c.is_ascii_digit())
+        .filter(|field| !field.is_empty())
+        .map(|field| field.parse().unwrap_or(0));
+    let index = fields.next().unwrap_or(0);
+    let line = fields.next().unwrap_or(0);
+    let column = fields.next().unwrap_or(0);
+    (index, line, column)
 }
 
 pub fn get_error(error: ErrorDef, marker: Option<Marker>) -> RamlError {
-    let message = match error {
+    let message = match &error {
         ErrorDef::UnexpectedKeyRoot { field, level } => {
             format!("Unexpected field found at the {}: {}", level, field)
         }
@@ -90,21 +531,253 @@ pub fn get_error(error: ErrorDef, marker: Option<Marker>) -> RamlError {
         ErrorDef::MissingRamlVersion => {
             "Document must start with the following RAML comment line: #%RAML 1.0".to_string()
         }
+        ErrorDef::UnsupportedRamlVersion { version } => {
+            format!("RAML version {} is not supported; only 1.0", version)
+        }
         ErrorDef::MissingField { field, level } => {
             format!("Error parsing {}. Missing field: {}", level, field)
         }
-        ErrorDef::UnexpectedProtocol => {
-            "Error parsing document root. Unexpected protocol".to_string()
+        ErrorDef::EmptyField { field, level } => {
+            format!("Error parsing {}. Field must not be empty: {}", level, field)
         }
-        ErrorDef::MissingProtocols => {
-            "Error parsing document root. Protocols must not be empty".to_string()
+        ErrorDef::PropertiesOnNonObjectBody { body_type } => {
+            format!("Error parsing body. Properties can't be declared for a body of type {}",
+                    body_type)
+        }
+        ErrorDef::QueryStringWithQueryParameters { level } => {
+            format!("Error parsing {}. queryString and queryParameters can't both be declared",
+                    level)
+        }
+        ErrorDef::UnexpectedProtocol { level } => {
+            format!("Error parsing {}. Unexpected protocol", level)
+        }
+        ErrorDef::InvalidProtocol { protocol } => {
+            format!("Invalid protocol: {}. Expected HTTP or HTTPS", protocol)
+        }
+        ErrorDef::MissingProtocols { level } => {
+            format!("Error parsing {}. Protocols must not be empty", level)
         }
         ErrorDef::InvalidSecuritySchemeType => {
             "Error parsing security scheme. Unexpected type".to_string()
         }
+        ErrorDef::InvalidBoolean { facet, value } => {
+            format!("Error parsing {}. Expected true or false, Found {}", facet, value)
+        }
+        ErrorDef::InvalidInteger { facet, value } => {
+            format!("Error parsing {}. Expected an integer, Found {}", facet, value)
+        }
+        ErrorDef::InvalidNumber { value } => {
+            format!("Unexpected entry found. Expected a number, Found {}", value)
+        }
+        ErrorDef::InvalidUnsignedInteger { value } => {
+            format!("Unexpected entry found. Expected a non-negative integer, Found {}",
+                    value)
+        }
+        ErrorDef::InvalidBaseUri { uri } => {
+            format!("Error parsing document root. The baseUri must be an absolute URI: {}", uri)
+        }
+        ErrorDef::MissingBaseUriVersion => {
+            "Error parsing document root. The baseUri refers to {version}, but no version is \
+             declared"
+                .to_string()
+        }
+        ErrorDef::ReservedBaseUriParameter { name } => {
+            format!("Error parsing document root. Reserved base uri parameter: {}", name)
+        }
+        ErrorDef::UnusedBaseUriParameter { name } => {
+            format!("Error parsing document root. Base uri parameter not found in baseUri: {}",
+                    name)
+        }
+        ErrorDef::UndeclaredBaseUriParameter { name } => {
+            format!("Error parsing document root. Base uri parameter not declared: {}", name)
+        }
+        ErrorDef::UndeclaredSecurityScheme { name, level } => {
+            format!("Error parsing {}. Security scheme not declared: {}", level, name)
+        }
+        ErrorDef::InvalidStatusCode { code } => {
+            format!("Error parsing method. Invalid status code: {}", code)
+        }
+        ErrorDef::InvalidMediaType { media_type } => {
+            format!("Invalid media type, expected type/subtype: {}", media_type)
+        }
+        ErrorDef::MissingDefaultMediaType => {
+            "Error parsing body. A media type must be given when there is no mediaType at the \
+             document root"
+                .to_string()
+        }
+        ErrorDef::UndeclaredUriParameter { name } => {
+            format!("Error parsing resource. Uri parameter not declared: {}", name)
+        }
+        ErrorDef::UnusedUriParameter { name } => {
+            format!("Error parsing resource. Uri parameter not found in the resource uri: {}",
+                    name)
+        }
+        ErrorDef::UnexpectedTypeFacet { facet, type_name } => {
+            format!("Error parsing type {}. Unexpected facet: {}", type_name, facet)
+        }
+        ErrorDef::ItemsOnNonArrayType { type_name } => {
+            format!("Error parsing type {}. The items facet is only valid for array types",
+                    type_name)
+        }
+        ErrorDef::InvalidTypeExpression { expression } => {
+            format!("Invalid type expression: {}", expression)
+        }
+        ErrorDef::UndeclaredType { name, type_name } => {
+            format!("Error resolving type {}. Type not declared: {}", type_name, name)
+        }
+        ErrorDef::SchemaTypeReference { name, type_name } => {
+            format!("Error resolving type {}. Type {} is declared with a schema, so it can only \
+                     be the type of a body",
+                    type_name,
+                    name)
+        }
+        ErrorDef::CircularTypeInheritance { cycle } => {
+            format!("Error resolving type {}. Circular inheritance: {}",
+                    cycle[0],
+                    cycle.join(" -> "))
+        }
+        ErrorDef::CircularTypeDependency { types } => {
+            format!("Error ordering types. These types inherit from each other: {}",
+                    types.join(", "))
+        }
+        ErrorDef::WidenedTypeFacet { facet, type_name } => {
+            format!("Error resolving type {}. The {} facet widens the inherited value",
+                    type_name,
+                    facet)
+        }
+        ErrorDef::InvalidDiscriminator { discriminator, type_name } => {
+            format!("Error resolving type {}. The discriminator {} is not a required string \
+                     property",
+                    type_name,
+                    discriminator)
+        }
+        ErrorDef::InvalidDatetimeFormat { format } => {
+            format!("Error parsing type. Invalid datetime format, expected rfc3339 or rfc2616: \
+                     {}",
+                    format)
+        }
+        ErrorDef::InvalidDatetimeExample { value, type_name } => {
+            format!("Error parsing type {}. The example is not an RFC 3339 datetime: {}",
+                    type_name,
+                    value)
+        }
+        ErrorDef::InvalidPropertyPattern { pattern, type_name } => {
+            format!("Error parsing type {}. Invalid regular expression for a property: {}",
+                    type_name,
+                    pattern)
+        }
+        ErrorDef::UnsupportedPropertyPattern { pattern, type_name } => {
+            format!("Error parsing type {}. Pattern properties are unsupported without the regex \
+                     feature: {}",
+                    type_name,
+                    pattern)
+        }
+        ErrorDef::ExamplePropertyCount { count, type_name } => {
+            format!("Error parsing type {}. An example has {} properties, outside of \
+                     minProperties and maxProperties",
+                    type_name,
+                    count)
+        }
+        ErrorDef::UndeclaredExampleProperty { property, type_name } => {
+            format!("Error parsing type {}. An example has a property that is not declared: {}",
+                    type_name,
+                    property)
+        }
+        ErrorDef::MissingTypeFacet { facet, type_name } => {
+            format!("Error parsing type {}. Missing value for the inherited facet: {}",
+                    type_name,
+                    facet)
+        }
+        ErrorDef::EmptyEnum => "The enum facet must have at least one value".to_string(),
+        ErrorDef::ValueNotInEnum { facet, value } => {
+            format!("The {} value is not one of the enum values: {}", facet, value)
+        }
+        ErrorDef::InvalidFacetRange { min_facet, max_facet } => {
+            format!("The {} facet must not be greater than the {} facet",
+                    min_facet,
+                    max_facet)
+        }
+        ErrorDef::InvalidExampleFacet { facet } => {
+            format!("Error parsing example. Invalid value for facet: {}", facet)
+        }
+        ErrorDef::UndeclaredTrait { name, level } => {
+            format!("Error parsing {}. Trait not declared: {}", level, name)
+        }
+        ErrorDef::UndeclaredTemplateParameter { name, level, template } => {
+            format!("Error parsing {} {}. Parameter not declared: {}", level, template, name)
+        }
+        ErrorDef::UnknownTemplateFunction { name } => {
+            format!("Unknown template parameter function: {}", name)
+        }
+        ErrorDef::UndeclaredLibrary { namespace } => {
+            format!("Error parsing reference. Library not declared: {}", namespace)
+        }
+        ErrorDef::UnresolvedInclude { path } => format!("Unable to resolve include: {}", path),
+        ErrorDef::MissingIncludeResolver { path } => {
+            format!("Unable to resolve include: {}. No include resolver was given", path)
+        }
+        ErrorDef::CircularInclude { cycle } => {
+            format!("Circular include: {}", cycle.join(" -> "))
+        }
+        ErrorDef::UnreadableFile { path, message } => {
+            format!("Unable to read file {}: {}", path, message)
+        }
+        ErrorDef::UnreadableSource { message } => format!("Unable to read source: {}", message),
+        ErrorDef::InvalidUtf8 { offset } => {
+            format!("Invalid UTF-8 in source at byte offset {}", offset)
+        }
+        ErrorDef::InvalidYaml { info } => format!("Invalid YAML: {}", info),
+        ErrorDef::LimitExceeded { limit, max } => {
+            format!("Error parsing document. Exceeded the limit on {} of {}", limit, max)
+        }
+        ErrorDef::MissingFragmentHeader => {
+            "Fragment must start with a RAML comment line such as: #%RAML 1.0 DataType"
+                .to_string()
+        }
+        ErrorDef::UnknownFragment { kind } => format!("Unknown fragment type: {}", kind),
+        ErrorDef::MissingOverlayHeader => {
+            "Overlay must start with one of the following RAML comment lines: #%RAML 1.0 \
+             Overlay, #%RAML 1.0 Extension"
+                .to_string()
+        }
+        ErrorDef::InvalidAuthorizationGrant { grant } => {
+            format!("Error parsing security scheme. Invalid authorization grant: {}", grant)
+        }
+        ErrorDef::InvalidOAuth1Signature { signature } => {
+            format!("Error parsing security scheme. Invalid signature method: {}", signature)
+        }
+        ErrorDef::DuplicateKey { key, level } => {
+            format!("Error parsing {}. Duplicate key: {}", level, key)
+        }
+        ErrorDef::UnexpectedSetting { key, scheme: Some(scheme) } => {
+            format!("Error parsing security scheme {}. Unexpected setting: {}", scheme, key)
+        }
+        ErrorDef::UnexpectedSetting { key, scheme: None } => {
+            format!("Error parsing security scheme. Unexpected setting: {}", key)
+        }
+        ErrorDef::RestrictedOverlayNode { node } => {
+            format!("Error applying overlay. Only an extension may change: {}", node)
+        }
+        ErrorDef::FragmentNotApiDocument { kind } => {
+            format!("Document is a {} fragment, not an API document", kind)
+        }
+        ErrorDef::MissingLibraryHeader => {
+            "Library must start with the following RAML comment line: #%RAML 1.0 Library"
+                .to_string()
+        }
+        ErrorDef::UndeclaredAnnotationType { name } => {
+            format!("Error parsing annotation. Annotation type not declared: {}", name)
+        }
+        ErrorDef::InvalidAnnotationTarget { name, target } => {
+            format!("Error parsing annotation {}. Not allowed on target: {}", name, target)
+        }
+        ErrorDef::UndeclaredResourceType { name } => {
+            format!("Error parsing resource. Resource type not declared: {}", name)
+        }
+        ErrorDef::CircularResourceType { cycle } => {
+            format!("Error parsing resource. Circular resource type: {}",
+                    cycle.join(" -> "))
+        }
     };
-    match marker {
-        Some(m) => RamlError::with_marker(message.as_str(), m),
-        None => RamlError::new(message.as_str()),
-    }
+    RamlError::new(&error, message, marker)
 }
\ No newline at end of file
@@ -0,0 +1,204 @@
+use error_definitions::{ErrorDef, RamlError, get_error};
+use std::collections::HashMap;
+use yaml::*;
+use yaml_rust::scanner::{TScalarStyle, TokenType};
+
+pub type Examples = HashMap<String, Example>;
+
+const EXAMPLE_FACETS: [&str; 4] = ["value", "displayName", "description", "strict"];
+
+/// An example value, which may be any YAML structure.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum ExampleValue {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Null,
+    Sequence(Vec<ExampleValue>),
+    // Entries are kept in document order.
+    Mapping(Vec<(String, ExampleValue)>),
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Example {
+    value: ExampleValue,
+    display_name: Option<String>,
+    description: Option<String>,
+    strict: Option<bool>,
+}
+
+impl Example {
+    pub fn value(&self) -> &ExampleValue {
+        &self.value
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn strict(&self) -> Option<bool> {
+        self.strict
+    }
+}
+
+pub fn get_example(cursor: &mut ForwardCursor) -> Result<Example, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    let value = get_example_value(cursor)?;
+    to_example(value)
+}
+
+pub fn get_examples(cursor: &mut ForwardCursor) -> Result<Examples, RamlError> {
+    let mut examples: Examples = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        let example = get_example(cursor)?;
+        examples.insert(key.value, example);
+    }
+    Ok(examples)
+}
+
+/// The text of a scalar example, used to compare it with enum values.
+pub fn scalar_text(value: &ExampleValue) -> Option<String> {
+    match *value {
+        ExampleValue::String(ref s) => Some(s.clone()),
+        ExampleValue::Number(n) => Some(n.to_string()),
+        ExampleValue::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+pub fn get_example_value(cursor: &mut ForwardCursor) -> Result<ExampleValue, RamlError> {
+    match cursor.peek_def()? {
+        TokenTypeDef::BlockEntry => {
+            // A sequence at the same indentation as its key has no start or end token.
+            return Ok(ExampleValue::Sequence(get_block_sequence_examples(cursor)?));
+        }
+        TokenTypeDef::BlockMappingStart |
+        TokenTypeDef::FlowMappingStart => {
+            let mut entries = vec![];
+            let style = get_mapping_start(cursor)?;
+            while let Some(key) = get_mapping_key(cursor, &style)? {
+                cursor.expect(TokenTypeDef::Value)?;
+                entries.push((key.value, get_example_value(cursor)?));
+            }
+            return Ok(ExampleValue::Mapping(entries));
+        }
+        TokenTypeDef::Key |
+        TokenTypeDef::BlockEnd |
+        TokenTypeDef::FlowEntry |
+        TokenTypeDef::FlowMappingEnd |
+        TokenTypeDef::FlowSequenceEnd => {
+            // The example has no value.
+            return Ok(ExampleValue::Null);
+        }
+        _ => {}
+    }
+    let token = cursor.next_token()?;
+    match token.1 {
+        TokenType::Scalar(style, value) => Ok(get_scalar_example(style, value)),
+        TokenType::FlowSequenceStart => {
+            let mut values = vec![];
+            while !cursor.next_is(TokenTypeDef::FlowSequenceEnd)? {
+                values.push(get_example_value(cursor)?);
+                cursor.next_is(TokenTypeDef::FlowEntry)?;
+            }
+            Ok(ExampleValue::Sequence(values))
+        }
+        TokenType::BlockSequenceStart => {
+            let values = get_block_sequence_examples(cursor)?;
+            cursor.expect(TokenTypeDef::BlockEnd)?;
+            Ok(ExampleValue::Sequence(values))
+        }
+        _ => {
+            Err(get_error(ErrorDef::UnexpectedEntry {
+                              expected: TokenTypeDef::Scalar,
+                              found: get_token_def(&token.1),
+                          },
+                          Some(token.0)))
+        }
+    }
+}
+
+fn get_block_sequence_examples(cursor: &mut ForwardCursor)
+                               -> Result<Vec<ExampleValue>, RamlError> {
+    let mut values = vec![];
+    while cursor.next_is(TokenTypeDef::BlockEntry)? {
+        values.push(get_example_value(cursor)?);
+    }
+    Ok(values)
+}
+
+fn get_scalar_example(style: TScalarStyle, value: String) -> ExampleValue {
+    // Only plain scalars are interpreted; quoted and block scalars are always strings.
+    if style != TScalarStyle::Plain {
+        return ExampleValue::String(value);
+    }
+    match value.as_str() {
+        "null" | "~" | "" => return ExampleValue::Null,
+        "true" => return ExampleValue::Boolean(true),
+        "false" => return ExampleValue::Boolean(false),
+        _ => {}
+    }
+    let is_numeric = value.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+    match value.parse::<f64>() {
+        Ok(number) if is_numeric => ExampleValue::Number(number),
+        _ => ExampleValue::String(value),
+    }
+}
+
+/// An example is either the value itself or a declaration with a `value` facet.
+fn to_example(value: ExampleValue) -> Result<Example, RamlError> {
+    let mut example = Example {
+        value: ExampleValue::Null,
+        display_name: None,
+        description: None,
+        strict: None,
+    };
+    let entries = match value {
+        ExampleValue::Mapping(entries) if is_example_declaration(&entries) => entries,
+        value => {
+            example.value = value;
+            return Ok(example);
+        }
+    };
+    for (facet, value) in entries {
+        match facet.as_str() {
+            "value" => example.value = value,
+            "strict" => {
+                example.strict = match value {
+                    ExampleValue::Boolean(strict) => Some(strict),
+                    ExampleValue::String(ref text) => Some(parse_bool_scalar(&facet, text, None)?),
+                    _ => return Err(get_error(ErrorDef::InvalidExampleFacet { facet }, None)),
+                }
+            }
+            _ => {
+                let text = match scalar_text(&value) {
+                    Some(text) => text,
+                    None => return Err(get_error(ErrorDef::InvalidExampleFacet { facet }, None)),
+                };
+                if facet == "displayName" {
+                    example.display_name = Some(text);
+                } else {
+                    example.description = Some(text);
+                }
+            }
+        }
+    }
+    Ok(example)
+}
+
+fn is_example_declaration(entries: &[(String, ExampleValue)]) -> bool {
+    entries.iter().any(|(key, _)| key == "value") &&
+    entries.iter().all(|(key, _)| EXAMPLE_FACETS.contains(&key.as_str()))
+}
@@ -0,0 +1,94 @@
+use yaml_rust::scanner::{Token, TokenType};
+use error_definitions::{ErrorDef, RamlError, get_error};
+use yaml::*;
+use options::ParseOptions;
+use example::{get_examples, Examples};
+use library::{parse_library, Library};
+use overlay::{parse_overlay, RamlOverlay};
+use parser::{get_documentation_item, get_security_scheme, RamlDocumentation, SecurityScheme};
+use resource_type::{get_resource_type, ResourceType};
+use traits::{get_trait, Trait};
+use types::{get_annotation_type, get_type, RamlType};
+
+const FRAGMENT_KINDS: [&str; 10] = ["DataType",
+                                    "Trait",
+                                    "ResourceType",
+                                    "DocumentationItem",
+                                    "SecurityScheme",
+                                    "NamedExample",
+                                    "AnnotationTypeDeclaration",
+                                    "Library",
+                                    "Overlay",
+                                    "Extension"];
+
+/// A standalone document declaring a single part of an API, identified by its first line, e.g.
+/// `#%RAML 1.0 DataType`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum RamlFragment {
+    DataType(RamlType),
+    Trait(Trait),
+    ResourceType(ResourceType),
+    DocumentationItem(RamlDocumentation),
+    SecurityScheme(SecurityScheme),
+    NamedExample(Examples),
+    AnnotationTypeDeclaration(RamlType),
+    Library(Library),
+    /// An overlay or extension.
+    Overlay(RamlOverlay),
+}
+
+/// The first line of a document, which is its RAML comment line when it has one, without a byte
+/// order mark or surrounding whitespace.
+pub fn get_header(source: &str) -> &str {
+    strip_bom(source).lines().next().unwrap_or_default().trim()
+}
+
+/// The kind of fragment a document's first line declares, if it is a known fragment.
+pub fn get_fragment_kind(source: &str) -> Option<&str> {
+    let kind = get_header(source).strip_prefix("#%RAML 1.0 ")?.trim();
+    FRAGMENT_KINDS.iter().find(|k| **k == kind).cloned()
+}
+
+pub fn parse_fragment(source: &str, options: &ParseOptions) -> Result<RamlFragment, RamlError> {
+    let kind = match get_header(source).strip_prefix("#%RAML 1.0 ") {
+        Some(kind) => kind.trim(),
+        None => return Err(get_error(ErrorDef::MissingFragmentHeader, None)),
+    };
+    if kind == "Library" {
+        return Ok(RamlFragment::Library(parse_library(source, options, None, None)?));
+    }
+    if kind == "Overlay" || kind == "Extension" {
+        return Ok(RamlFragment::Overlay(parse_overlay(source, options, None, None)?));
+    }
+    let mut cursor = ForwardCursor::with_options(source, options.clone());
+    let start = cursor.next_token()?;
+    if get_token_def(&start.1) != TokenTypeDef::StreamStart {
+        return Err(get_error(ErrorDef::UnexpectedEntry {
+                                 expected: TokenTypeDef::StreamStart,
+                                 found: get_token_def(&start.1),
+                             },
+                             Some(start.0)));
+    }
+    // The document is parsed as the value of the node it declares.
+    cursor.put_back(Token(start.0, TokenType::Value));
+    let fragment = match kind {
+        "DataType" => RamlFragment::DataType(get_type(&mut cursor, kind)?),
+        "Trait" => RamlFragment::Trait(get_trait(&mut cursor)?),
+        "ResourceType" => RamlFragment::ResourceType(get_resource_type(&mut cursor)?),
+        "DocumentationItem" => {
+            RamlFragment::DocumentationItem(get_documentation_item(&mut cursor)?)
+        }
+        "SecurityScheme" => {
+            RamlFragment::SecurityScheme(get_security_scheme(&mut cursor, None, start.0)?)
+        }
+        "NamedExample" => RamlFragment::NamedExample(get_examples(&mut cursor)?),
+        "AnnotationTypeDeclaration" => {
+            RamlFragment::AnnotationTypeDeclaration(get_annotation_type(&mut cursor, kind)?)
+        }
+        _ => return Err(get_error(ErrorDef::UnknownFragment { kind: kind.to_string() }, None)),
+    };
+    cursor.expect(TokenTypeDef::StreamEnd)?;
+    Ok(fragment)
+}
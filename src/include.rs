@@ -0,0 +1,280 @@
+use yaml_rust::scanner::{Marker, Scanner, TScalarStyle, Token, TokenType};
+use error_definitions::{ErrorDef, ErrorKind, RamlError, get_error};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use yaml::{get_scan_error, get_token_def, strip_bom, TokenTypeDef};
+use fragment::get_header;
+use location::SourceFile;
+use options::ParseOptions;
+
+// The handle of the tokens that mark where the tokens of an included file start and end. It has
+// a space, which the tag of a node can't have.
+const SOURCE_HANDLE: &str = "! source";
+
+/// Loads the documents a RAML document refers to, such as the libraries it uses and the files
+/// it includes with `!include`.
+pub trait IncludeResolver {
+    fn resolve(&self, path: &str) -> Result<String, RamlError>;
+
+    /// Loads the document at `path`, which may be at most `max_bytes` long. A resolver that
+    /// reads its documents should fail with a `LimitExceeded` error without reading the rest of
+    /// a longer one. By default the document is loaded with `resolve`, and its size checked
+    /// afterwards.
+    fn resolve_with_limit(&self, path: &str, max_bytes: usize) -> Result<String, RamlError> {
+        let source = self.resolve(path)?;
+        check_limit("max_source_bytes", source.len(), max_bytes, None)?;
+        Ok(source)
+    }
+}
+
+/// Resolves paths from documents held in memory, keyed by path.
+impl IncludeResolver for HashMap<String, String> {
+    fn resolve(&self, path: &str) -> Result<String, RamlError> {
+        match self.get(path) {
+            Some(source) => Ok(source.clone()),
+            None => Err(get_error(ErrorDef::UnresolvedInclude { path: path.to_string() }, None)),
+        }
+    }
+}
+
+/// Resolves paths by reading files. Relative paths are relative to the process working directory,
+/// so included paths are first made relative to the including file with `get_include_path`.
+pub struct FileResolver;
+
+impl IncludeResolver for FileResolver {
+    fn resolve(&self, path: &str) -> Result<String, RamlError> {
+        fs::read_to_string(path).map_err(|e| get_unreadable_file_error(path, &e))
+    }
+
+    fn resolve_with_limit(&self, path: &str, max_bytes: usize) -> Result<String, RamlError> {
+        let file = fs::File::open(path).map_err(|e| get_unreadable_file_error(path, &e))?;
+        let bytes = read_bytes(file, max_bytes).map_err(|e| get_unreadable_file_error(path, &e))?;
+        check_limit("max_source_bytes", bytes.len(), max_bytes, None)?;
+        String::from_utf8(bytes).map_err(|_| {
+            let error = io::Error::new(io::ErrorKind::InvalidData,
+                                       "stream did not contain valid UTF-8");
+            get_unreadable_file_error(path, &error)
+        })
+    }
+}
+
+fn get_unreadable_file_error(path: &str, error: &io::Error) -> RamlError {
+    get_error(ErrorDef::UnreadableFile {
+                  path: path.to_string(),
+                  message: error.to_string(),
+              },
+              None)
+}
+
+/// Reads the whole of a document from `reader`, which may be at most `max_bytes` long. No more
+/// than one byte past the limit is read. Invalid UTF-8 is an error giving the byte offset it
+/// starts at.
+pub fn read_source<R: Read>(reader: R, max_bytes: usize) -> Result<String, RamlError> {
+    let bytes = read_bytes(reader, max_bytes)
+        .map_err(|e| get_error(ErrorDef::UnreadableSource { message: e.to_string() }, None))?;
+    check_limit("max_source_bytes", bytes.len(), max_bytes, None)?;
+    String::from_utf8(bytes).map_err(|e| {
+        get_error(ErrorDef::InvalidUtf8 { offset: e.utf8_error().valid_up_to() }, None)
+    })
+}
+
+// Reads up to one byte more than `max_bytes`, so that a source over the limit can be told apart
+// from one at it without reading the rest.
+fn read_bytes<R: Read>(reader: R, max_bytes: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![];
+    reader.take(max_bytes as u64 + 1).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// The path of a document referred to from another document, which a relative path is relative
+/// to. Documents loaded from a string have no path.
+pub fn get_include_path(including: Option<&str>, path: &str) -> String {
+    let including = match including {
+        Some(including) => including,
+        None => return path.to_string(),
+    };
+    let joined = Path::new(including).parent().unwrap_or_else(|| Path::new("")).join(path);
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            // `..` at the start of a relative path is kept, as there is nothing to remove.
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            _ => normalized.push(component.as_os_str()),
+        }
+    }
+    normalized.to_string_lossy().into_owned()
+}
+
+/// A token marking that the tokens after it were read from `file`, a position in the files
+/// included by the document, or from the document itself when None. The cursor reads these to
+/// locate nodes, and never returns them.
+pub fn get_source_token(marker: Marker, file: Option<usize>) -> Token {
+    let suffix = file.map(|file| file.to_string()).unwrap_or_default();
+    Token(marker, TokenType::Tag(SOURCE_HANDLE.to_string(), suffix))
+}
+
+/// The file a token from `get_source_token` marks the start of, or None when the token is any
+/// other token.
+pub fn get_source_file(token: &Token) -> Option<Option<usize>> {
+    match token.1 {
+        TokenType::Tag(ref handle, ref suffix) if handle == SOURCE_HANDLE => {
+            Some(suffix.parse().ok())
+        }
+        _ => None,
+    }
+}
+
+/// The limits of `ParseOptions` on the documents included by a document, with what has been
+/// included so far.
+pub struct IncludeLimits {
+    max_include_depth: usize,
+    max_includes: usize,
+    max_source_bytes: usize,
+    depth: usize,
+    includes: usize,
+    bytes: usize,
+}
+
+impl IncludeLimits {
+    /// The limits for including documents in `source`, which counts towards the size allowed.
+    pub fn new(options: &ParseOptions, source: &str) -> Result<IncludeLimits, RamlError> {
+        let limits = IncludeLimits {
+            max_include_depth: options.max_include_depth,
+            max_includes: options.max_includes,
+            max_source_bytes: options.max_source_bytes,
+            depth: 0,
+            includes: 0,
+            bytes: source.len(),
+        };
+        check_limit("max_source_bytes", limits.bytes, limits.max_source_bytes, None)?;
+        Ok(limits)
+    }
+}
+
+/// Fails with a `LimitExceeded` error when `value` is over the limit `max`, named after the
+/// field of `ParseOptions` that sets it.
+pub fn check_limit(limit: &str,
+                   value: usize,
+                   max: usize,
+                   marker: Option<Marker>)
+                   -> Result<(), RamlError> {
+    if value > max {
+        Err(get_error(ErrorDef::LimitExceeded {
+                          limit: limit.to_string(),
+                          max,
+                      },
+                      marker))
+    } else {
+        Ok(())
+    }
+}
+
+/// Reads the tokens of a document, replacing each `!include` with the document it names. RAML
+/// fragments are read as nodes, and any other document as a string. `chain` holds the paths of
+/// the documents being included, the last of which is the document being read, to detect
+/// circular includes and resolve relative paths. Each included document is added to `files`,
+/// and the tokens of a fragment are marked as read from it, after which the tokens are marked as
+/// read from `file`, the document being read. What is included is counted in `limits`.
+pub fn get_included_tokens(source: &str,
+                           resolver: &dyn IncludeResolver,
+                           chain: &mut Vec<String>,
+                           files: &mut Vec<SourceFile>,
+                           file: Option<usize>,
+                           limits: &mut IncludeLimits)
+                           -> Result<Vec<Token>, RamlError> {
+    let mut scanner = Scanner::new(strip_bom(source).chars());
+    let mut tokens = vec![];
+    loop {
+        let token = match scanner.next() {
+            Some(token) => token,
+            None => return Err(get_scan_error(&scanner)),
+        };
+        match token.1 {
+            TokenType::Tag(ref handle, ref suffix) if handle == "!" && suffix == "include" => {}
+            TokenType::StreamEnd => {
+                tokens.push(token);
+                return Ok(tokens);
+            }
+            _ => {
+                tokens.push(token);
+                continue;
+            }
+        }
+        let path = match scanner.next() {
+            Some(Token(_, TokenType::Scalar(_, path))) => {
+                get_include_path(chain.last().map(|p| p.as_str()), &path)
+            }
+            found => {
+                let found = found.map(|t| get_token_def(&t.1)).unwrap_or(TokenTypeDef::StreamEnd);
+                return Err(get_error(ErrorDef::UnexpectedEntry {
+                                         expected: TokenTypeDef::Scalar,
+                                         found,
+                                     },
+                                     Some(token.0)));
+            }
+        };
+        if chain.contains(&path) {
+            let mut cycle = chain.clone();
+            cycle.push(path);
+            return Err(get_error(ErrorDef::CircularInclude { cycle }, Some(token.0)));
+        }
+        limits.includes += 1;
+        check_limit("max_includes", limits.includes, limits.max_includes, Some(token.0))?;
+        // The resolver is only given what is left of the limit, so its error is replaced by one
+        // giving the whole limit, at the include.
+        let remaining = limits.max_source_bytes.saturating_sub(limits.bytes);
+        let included = resolver.resolve_with_limit(&path, remaining).map_err(|error| {
+            if error.kind() == ErrorKind::LimitExceeded {
+                get_error(ErrorDef::LimitExceeded {
+                              limit: "max_source_bytes".to_string(),
+                              max: limits.max_source_bytes,
+                          },
+                          Some(token.0))
+            } else {
+                error
+            }
+        })?;
+        limits.bytes += included.len();
+        let included_file = match files.iter().position(|f| f.path() == path) {
+            Some(position) => position,
+            None => {
+                files.push(SourceFile::new(path.clone(), included.len()));
+                files.len() - 1
+            }
+        };
+        if is_fragment(&included) {
+            limits.depth += 1;
+            check_limit("max_include_depth",
+                        limits.depth,
+                        limits.max_include_depth,
+                        Some(token.0))?;
+            chain.push(path);
+            let included_tokens = get_included_tokens(&included,
+                                                      resolver,
+                                                      chain,
+                                                      files,
+                                                      Some(included_file),
+                                                      limits)?;
+            chain.pop();
+            limits.depth -= 1;
+            tokens.push(get_source_token(token.0, Some(included_file)));
+            tokens.extend(included_tokens.into_iter().filter(|t| {
+                !matches!(t.1, TokenType::StreamStart(_) | TokenType::StreamEnd)
+            }));
+            tokens.push(get_source_token(token.0, file));
+        } else {
+            tokens.push(Token(token.0, TokenType::Scalar(TScalarStyle::Literal, included)));
+        }
+    }
+}
+
+/// Whether an included document is a RAML fragment, e.g. `#%RAML 1.0 DataType`, rather than
+/// a file included as a string such as a JSON schema.
+fn is_fragment(source: &str) -> bool {
+    get_header(source).starts_with("#%RAML 1.0")
+}
@@ -1,8 +1,115 @@
 extern crate yaml_rust;
+#[cfg(feature = "regex")]
+extern crate regex;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "openapi")]
+#[macro_use]
+extern crate serde_json;
+#[cfg(feature = "markdown")]
+extern crate pulldown_cmark;
 
+mod annotation;
+mod body;
+mod emitter;
 mod error_definitions;
+mod example;
+mod fragment;
+mod include;
+mod library;
+mod location;
+#[cfg(feature = "markdown")]
+mod markdown;
+mod media_type;
+mod method;
+#[cfg(feature = "openapi")]
+mod openapi;
+mod options;
+mod overlay;
+mod parameter;
 mod parser;
+mod resource;
+mod resource_type;
+mod response;
+mod security_settings;
+#[cfg(feature = "serde")]
+mod serialization;
+mod template;
+mod traits;
+mod type_dependencies;
+mod type_expression;
+mod types;
+mod validation;
+mod visitor;
+mod warnings;
 mod yaml;
 
 pub use parser::RamlParser;
-pub use parser::{Protocol, Raml, RamlResult, RamlDocumentation, SecuritySchemeType};
+pub use parser::{Protocol, Raml, RamlBuilder, RamlResult, RamlDocumentation, SecuredBy,
+                 SecuredByParameters, SecurityScheme, SecuritySchemeBuilder,
+                 SecuritySchemeDescribedBy, SecuritySchemeType};
+pub use annotation::{AnnotatedNode, AnnotationValue};
+pub use body::Body;
+pub use error_definitions::{ErrorKind, RamlError};
+pub use example::{Example, ExampleValue};
+pub use fragment::RamlFragment;
+pub use include::IncludeResolver;
+pub use library::Library;
+pub use location::{SourceFile, SourceLocation};
+pub use media_type::MediaType;
+pub use method::Method;
+#[cfg(feature = "openapi")]
+pub use openapi::{ConversionWarning, ConversionWarningKind};
+pub use options::{ParseOptions, UnknownKeyBehaviour, ValidationOptions};
+pub use overlay::{OverlayKind, RamlOverlay};
+pub use parameter::NamedParameter;
+pub use resource::{Operation, Resource, UriParameterValues};
+pub use resource_type::{ResourceType, ResourceTypeReference};
+pub use response::Response;
+pub use security_settings::{AuthorizationGrant, OAuth1Settings, OAuth1Signature, OAuth2Settings,
+                            PassThroughSettings, SecuritySchemeSettings};
+pub use traits::{Trait, TraitReference};
+pub use type_expression::TypeExpression;
+pub use types::{BuiltInType, DatetimeFormat, ExternalSchema, FacetValue, Property, RamlType,
+                ResolvedType, SchemaKind, XmlFacets};
+pub use validation::{RamlViolation, Severity, ValidationRule};
+pub use visitor::{EndpointCounter, RamlVisitor, VisitContext};
+pub use warnings::{RamlWarning, WarningKind};
+pub use yaml::{TokenTypeDef, YamlNode};
+
+// The parsed model is plain owned data, so a document can be parsed on one thread and shared
+// with others, e.g. behind an `Arc`. This stops compiling if one of these types stops being Send
+// or Sync.
+#[allow(dead_code)]
+fn assert_model_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Raml>();
+    assert_send_sync::<RamlError>();
+    assert_send_sync::<RamlWarning>();
+    assert_send_sync::<RamlViolation>();
+    assert_send_sync::<RamlFragment>();
+    assert_send_sync::<RamlOverlay>();
+    assert_send_sync::<Library>();
+    assert_send_sync::<Resource>();
+    assert_send_sync::<Method>();
+    assert_send_sync::<Response>();
+    assert_send_sync::<Body>();
+    assert_send_sync::<NamedParameter>();
+    assert_send_sync::<RamlType>();
+    assert_send_sync::<ResolvedType>();
+    assert_send_sync::<Trait>();
+    assert_send_sync::<ResourceType>();
+    assert_send_sync::<SecurityScheme>();
+    assert_send_sync::<SecuritySchemeSettings>();
+    assert_send_sync::<RamlDocumentation>();
+    assert_send_sync::<AnnotationValue>();
+    assert_send_sync::<Example>();
+    assert_send_sync::<ExampleValue>();
+    assert_send_sync::<YamlNode>();
+    assert_send_sync::<SourceLocation>();
+    assert_send_sync::<ParseOptions>();
+}
@@ -0,0 +1,161 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use std::collections::HashMap;
+use yaml::*;
+use include::{get_include_path, IncludeResolver};
+use fragment::get_header;
+use options::ParseOptions;
+use parser::{get_security_schemes, SecuritySchemes};
+use resource_type::{get_resource_types, ResourceTypes};
+use traits::{get_traits, Traits};
+use types::{get_annotation_types, get_types, Types};
+
+/// Libraries by the namespace they are used with in a `uses` node.
+pub type Libraries = HashMap<String, Library>;
+
+/// A `#%RAML 1.0 Library` document. Its declarations are referenced from the document that uses
+/// it with names qualified by its namespace, e.g. `myLib.Person`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Library {
+    usage: Option<String>,
+    uses: Option<Libraries>,
+    types: Option<Types>,
+    traits: Option<Traits>,
+    resource_types: Option<ResourceTypes>,
+    security_schemes: Option<SecuritySchemes>,
+    annotation_types: Option<Types>,
+}
+
+impl Library {
+    pub fn usage(&self) -> Option<&str> {
+        self.usage.as_deref()
+    }
+
+    pub fn uses(&self) -> Option<&Libraries> {
+        self.uses.as_ref()
+    }
+
+    pub fn types(&self) -> Option<&Types> {
+        self.types.as_ref()
+    }
+
+    pub fn traits(&self) -> Option<&Traits> {
+        self.traits.as_ref()
+    }
+
+    pub fn resource_types(&self) -> Option<&ResourceTypes> {
+        self.resource_types.as_ref()
+    }
+
+    pub fn security_schemes(&self) -> Option<&SecuritySchemes> {
+        self.security_schemes.as_ref()
+    }
+
+    pub fn annotation_types(&self) -> Option<&Types> {
+        self.annotation_types.as_ref()
+    }
+}
+
+pub fn parse_library(source: &str,
+                     options: &ParseOptions,
+                     resolver: Option<&dyn IncludeResolver>,
+                     path: Option<&str>)
+                     -> Result<Library, RamlError> {
+    if get_header(source) != "#%RAML 1.0 Library" {
+        return Err(get_error(ErrorDef::MissingLibraryHeader, None));
+    }
+    let mut cursor = match resolver {
+        Some(resolver) => ForwardCursor::with_resolver(source, options.clone(), resolver, path)?,
+        None => ForwardCursor::with_options(source, options.clone()),
+    };
+    get_library_root(&mut cursor).map_err(|error| cursor.annotate(error))
+}
+
+fn get_library_root(cursor: &mut ForwardCursor) -> Result<Library, RamlError> {
+    let mut library = Library::default();
+    cursor.expect(TokenTypeDef::StreamStart)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        None => return Ok(library),
+    };
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "usage" => library.usage = Some(get_single_value(cursor)?),
+            "uses" => library.uses = Some(get_uses(cursor)?),
+            "types" => library.types = Some(get_types(cursor)?),
+            "traits" => library.traits = Some(get_traits(cursor)?),
+            "resourceTypes" => library.resource_types = Some(get_resource_types(cursor)?),
+            "securitySchemes" => {
+                library.security_schemes = Some(get_security_schemes(cursor)?)
+            }
+            "annotationTypes" => {
+                library.annotation_types = Some(get_annotation_types(cursor)?)
+            }
+            _ => skip_unknown_key(cursor, key, HierarchyLevel::Library)?,
+        }
+    }
+    Ok(library)
+}
+
+/// Loads the libraries of a `uses` node with the cursor's include resolver.
+pub fn get_uses(cursor: &mut ForwardCursor) -> Result<Libraries, RamlError> {
+    let mut libraries: Libraries = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        let path = get_single_scalar(cursor)?;
+        let resolver = match cursor.resolver() {
+            Some(resolver) => resolver,
+            None => {
+                return Err(get_error(ErrorDef::MissingIncludeResolver { path: path.value },
+                                     Some(path.marker)))
+            }
+        };
+        let path = get_include_path(cursor.path(), &path.value);
+        let source = resolver.resolve_with_limit(&path, cursor.options().max_source_bytes)?;
+        let library = parse_library(&source, cursor.options(), Some(resolver), Some(&path))?;
+        libraries.insert(key.value, library);
+    }
+    Ok(libraries)
+}
+
+/// Finds the library a name qualified with a namespace refers to, e.g. `myLib` for
+/// `myLib.Person`, returning it with the name within the library. Unqualified names give `None`.
+pub fn find_library<'a, 'n>(name: &'n str,
+                            libraries: Option<&'a Libraries>,
+                            marker: Option<Marker>)
+                            -> Result<Option<(&'a Library, &'n str)>, RamlError> {
+    let index = match name.find('.') {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let namespace = &name[..index];
+    match libraries.and_then(|libraries| libraries.get(namespace)) {
+        Some(library) => Ok(Some((library, &name[index + 1..]))),
+        None => {
+            Err(get_error(ErrorDef::UndeclaredLibrary { namespace: namespace.to_string() },
+                          marker))
+        }
+    }
+}
+
+/// Looks up a declaration either in `declarations` or, for a qualified name, in the library
+/// it refers to. `select` gives the declarations of a library that are searched.
+pub fn find_declaration<'a, T>(name: &str,
+                               declarations: Option<&'a HashMap<String, T>>,
+                               libraries: Option<&'a Libraries>,
+                               select: fn(&Library) -> Option<&HashMap<String, T>>,
+                               marker: Option<Marker>)
+                               -> Result<Option<&'a T>, RamlError> {
+    match find_library(name, libraries, marker)? {
+        Some((library, name)) => {
+            find_declaration(name, select(library), library.uses(), select, marker)
+        }
+        None => Ok(declarations.and_then(|declarations| declarations.get(name))),
+    }
+}
@@ -0,0 +1,59 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::marker_position;
+
+/// Where a node starts in the source it was parsed from. Nodes read from an included file are
+/// located in that file.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct SourceLocation {
+    /// The line, starting from 1.
+    pub line: usize,
+    /// The column, starting from 1.
+    pub column: usize,
+    /// The offset from the start of the source, in characters.
+    pub index: usize,
+    /// The position in `Raml::source_files` of the included file the node was read from, or
+    /// None when it was read from the document itself.
+    pub file: Option<usize>,
+}
+
+/// A file included by a document, such as a type declared in a `DataType` fragment.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct SourceFile {
+    path: String,
+    size: usize,
+}
+
+impl SourceFile {
+    pub fn new(path: String, size: usize) -> SourceFile {
+        SourceFile { path, size }
+    }
+
+    /// The path the file was resolved with. For a document loaded from a file, this joins the
+    /// path of the including file with the path it includes.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// The size of the file, in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// The location of a node starting at `marker`, without the file it was read from.
+pub fn get_location(marker: Marker) -> SourceLocation {
+    let (index, line, column) = marker_position(marker);
+    SourceLocation {
+        line,
+        column: column + 1,
+        index,
+        file: None,
+    }
+}
@@ -0,0 +1,9 @@
+use pulldown_cmark::{html, Parser};
+
+/// Renders the markdown of a description as HTML. Descriptions are stored as written and only
+/// rendered when asked for, so parsing doesn't pay for it.
+pub fn render_markdown(markdown: &str) -> String {
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new(markdown));
+    rendered
+}
@@ -0,0 +1,78 @@
+use error_definitions::{ErrorDef, RamlError, get_error};
+use std::fmt;
+use std::str::FromStr;
+
+/// A media type such as `application/json` or `application/hal+json; charset=utf-8`, as
+/// written in the document.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub struct MediaType {
+    text: String,
+}
+
+impl MediaType {
+    pub fn as_str(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// The type, e.g. `application`.
+    pub fn type_(&self) -> &str {
+        self.essence().0
+    }
+
+    /// The subtype, including any suffix, e.g. `hal+json`.
+    pub fn subtype(&self) -> &str {
+        self.essence().1
+    }
+
+    /// The structured syntax suffix of the subtype, e.g. `json` for `application/hal+json`.
+    pub fn suffix(&self) -> Option<&str> {
+        self.subtype().rsplit_once('+').map(|(_, suffix)| suffix)
+    }
+
+    /// Whether the type or the subtype is `*`, matching more than one media type.
+    pub fn is_wildcard(&self) -> bool {
+        self.type_() == "*" || self.subtype() == "*"
+    }
+
+    fn essence(&self) -> (&str, &str) {
+        let essence = self.text.split(';').next().unwrap_or_default().trim();
+        essence.split_once('/').unwrap_or((essence, ""))
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for MediaType {
+    type Err = RamlError;
+
+    fn from_str(s: &str) -> Result<MediaType, RamlError> {
+        let media_type = MediaType { text: s.to_string() };
+        let is_token = |part: &str| {
+            !part.is_empty() &&
+            part.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+        };
+        let (type_, subtype) = media_type.essence();
+        // Parameters are `name=value` pairs, e.g. `charset=utf-8`.
+        let parameters_valid = s.split(';').skip(1).all(|parameter| {
+            match parameter.split_once('=') {
+                Some((name, value)) => is_token(name.trim()) && !value.trim().is_empty(),
+                None => false,
+            }
+        });
+        let valid = s.contains('/') && is_token(type_) && is_token(subtype) &&
+                    (type_ != "*" || subtype == "*") && parameters_valid;
+        if valid {
+            Ok(media_type)
+        } else {
+            Err(get_error(ErrorDef::InvalidMediaType { media_type: s.to_string() }, None))
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::{ErrorDef, RamlError, HierarchyLevel, get_error};
+use std::collections::HashMap;
+use std::hash::Hash;
+use yaml::*;
+use parameter::{get_named_parameters, NamedParameter, NamedParameters};
+use response::{expand_response_bodies, get_responses, Response, Responses};
+use body::{expand_default_media_types, get_bodies, Bodies};
+use traits::{apply_trait, get_referenced_traits, get_trait_references, Trait, TraitReferences,
+             Traits};
+use template::TemplateContext;
+use library::Libraries;
+use annotation::{get_annotation_and_node, get_annotation_name, Annotations};
+use parser::{get_effective_secured_by, get_protocols, get_secured_by_entries, MediaTypes,
+             Protocol, Protocols, Raml, SecuredByEntries};
+use resource::{get_inherited_uri_parameters, Resource};
+use overlay::{check_overlay_nodes, override_map, override_value, OverlayKind};
+use location::SourceLocation;
+use types::{get_type, RamlType};
+
+pub const METHOD_NAMES: [&str; 8] = ["get", "post", "put", "delete", "patch", "head", "options",
+                                     "trace"];
+
+pub type Methods = HashMap<String, Method>;
+
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Method {
+    display_name: Option<String>,
+    description: Option<String>,
+    uri_parameters: Option<NamedParameters>,
+    query_parameters: Option<NamedParameters>,
+    query_string: Option<Box<RamlType>>,
+    headers: Option<NamedParameters>,
+    responses: Option<Responses>,
+    body: Option<Bodies>,
+    is: Option<TraitReferences>,
+    annotations: Option<Annotations>,
+    extensions: Option<Extensions>,
+    secured_by: Option<SecuredByEntries>,
+    protocols: Option<Protocols>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Option<SourceLocation>,
+}
+
+// The extensions are left out, as their nodes keep the positions they were read from.
+impl PartialEq for Method {
+    fn eq(&self, other: &Method) -> bool {
+        self.display_name == other.display_name &&
+        self.description == other.description &&
+        self.uri_parameters == other.uri_parameters &&
+        self.query_parameters == other.query_parameters &&
+        self.query_string == other.query_string &&
+        self.headers == other.headers &&
+        self.responses == other.responses &&
+        self.body == other.body &&
+        self.is == other.is &&
+        self.annotations == other.annotations &&
+        self.secured_by == other.secured_by &&
+        self.protocols == other.protocols
+    }
+}
+
+impl Method {
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// The display name, or else `key`, the name of the method, e.g. `get`.
+    pub fn display_name_or_key<'a>(&'a self, key: &'a str) -> &'a str {
+        self.display_name.as_deref().unwrap_or(key)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Where the method is declared, when it was parsed. A method added by a resource type is
+    /// located in the resource type.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+
+    /// The uri parameters the method declares or refines, which take the place of those of its
+    /// resource.
+    pub fn uri_parameters(&self) -> Option<&NamedParameters> {
+        self.uri_parameters.as_ref()
+    }
+
+    /// The uri parameters of the method of the last resource of `resource_chain`, a resource
+    /// and the resources it is nested in, outermost first, as given by
+    /// `Resource::resource_chain`. These are the parameters the method declares, then those of
+    /// the closest resource declaring each, and implicit parameters for the other template
+    /// variables of the resources' uris.
+    pub fn effective_uri_parameters(&self, resource_chain: &[&Resource]) -> NamedParameters {
+        let mut parameters = get_inherited_uri_parameters(resource_chain);
+        for (name, parameter) in self.uri_parameters.iter().flatten() {
+            parameters.insert(name.clone(), parameter.clone());
+        }
+        parameters
+    }
+
+    pub fn query_parameters(&self) -> Option<&NamedParameters> {
+        self.query_parameters.as_ref()
+    }
+
+    /// The type of the whole query string, declared instead of `queryParameters`.
+    pub fn query_string(&self) -> Option<&RamlType> {
+        self.query_string.as_deref()
+    }
+
+    pub fn headers(&self) -> Option<&NamedParameters> {
+        self.headers.as_ref()
+    }
+
+    pub fn responses(&self) -> Option<&Responses> {
+        self.responses.as_ref()
+    }
+
+    pub fn body(&self) -> Option<&Bodies> {
+        self.body.as_ref()
+    }
+
+    /// The headers declared by any of the method's responses, e.g. to configure caching. A
+    /// header declared by more than one response is taken from the lowest status code.
+    pub fn all_response_headers(&self) -> HashMap<&str, &NamedParameter> {
+        let mut responses: Vec<(&u16, &Response)> = self.responses.iter().flatten().collect();
+        responses.sort_by_key(|&(code, _)| *code);
+        let mut result = HashMap::new();
+        for (_, response) in responses {
+            for (name, header) in response.headers().into_iter().flatten() {
+                result.entry(name.as_str()).or_insert(header);
+            }
+        }
+        result
+    }
+
+    pub fn is(&self) -> Option<&TraitReferences> {
+        self.is.as_ref()
+    }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.annotations.as_ref()
+    }
+
+    /// The values of the method's annotations and, unless `ParseOptions::unknown_keys` rejects
+    /// them, its unknown keys, as written.
+    pub fn extra(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    pub fn secured_by(&self) -> Option<&SecuredByEntries> {
+        self.secured_by.as_ref()
+    }
+
+    /// The security schemes that apply to this method of `raml`: those the method declares, or
+    /// else those of its resource, or else those of the API.
+    pub fn effective_secured_by<'a>(&'a self, raml: &'a Raml) -> Option<&'a SecuredByEntries> {
+        get_effective_secured_by(raml, self)
+    }
+
+    pub fn protocols(&self) -> Option<&[Protocol]> {
+        self.protocols.as_deref()
+    }
+
+    /// The protocols this method of `resource` is served over: those the method declares, or
+    /// else those of the API, which may have been taken from the scheme of the baseUri.
+    /// Resources can't declare protocols, so `resource` isn't consulted yet.
+    pub fn effective_protocols<'a>(&'a self,
+                                   raml: &'a Raml,
+                                   _resource: &Resource)
+                                   -> Option<&'a [Protocol]> {
+        self.protocols().or_else(|| raml.protocols())
+    }
+}
+
+pub fn is_method_name(name: &str) -> bool {
+    METHOD_NAMES.contains(&name)
+}
+
+pub fn get_method(cursor: &mut ForwardCursor, marker: Marker) -> Result<Method, RamlError> {
+    let mut method = Method {
+        location: Some(cursor.location(marker)),
+        ..Method::default()
+    };
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        // A method declared without a body, e.g. `get:`
+        None => return Ok(method),
+    };
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "is" => method.is = Some(get_trait_references(cursor, HierarchyLevel::Method)?),
+            _ => get_method_facet(cursor, &mut method, key, HierarchyLevel::Method)?,
+        }
+    }
+    Ok(method)
+}
+
+/// Parses one of the nodes a method can have. Traits share these nodes, so the hierarchy level
+/// used for errors is given by the caller.
+pub fn get_method_facet(cursor: &mut ForwardCursor,
+                        method: &mut Method,
+                        key: MappingKey,
+                        level: HierarchyLevel)
+                        -> Result<(), RamlError> {
+    match key.value.as_str() {
+        "displayName" => method.display_name = Some(get_single_value(cursor)?),
+        "description" => method.description = get_optional_value(cursor)?,
+        "uriParameters" => {
+            method.uri_parameters =
+                Some(get_named_parameters(cursor, HierarchyLevel::UriParameter)?)
+        }
+        "queryParameters" | "queryString" if method.query_parameters.is_some() ||
+                                              method.query_string.is_some() => {
+            return Err(get_error(ErrorDef::QueryStringWithQueryParameters { level },
+                                 Some(key.marker)));
+        }
+        "queryParameters" => {
+            method.query_parameters =
+                Some(get_named_parameters(cursor, HierarchyLevel::QueryParameter)?)
+        }
+        "queryString" => {
+            method.query_string = Some(Box::new(get_type(cursor, "queryString")?))
+        }
+        "headers" => method.headers = Some(get_named_parameters(cursor, HierarchyLevel::Header)?),
+        "responses" => method.responses = Some(get_responses(cursor)?),
+        "body" => method.body = Some(get_bodies(cursor, key.marker)?),
+        "securedBy" => {
+            let entries = get_secured_by_entries(cursor, level)?;
+            method.secured_by = Some(entries.into_iter().map(|(entry, _)| entry).collect());
+        }
+        "protocols" => method.protocols = Some(get_protocols(cursor, level, key.marker)?),
+        name if get_annotation_name(name).is_some() => {
+            get_annotation_and_node(cursor, &mut method.annotations, &mut method.extensions, key)?
+        }
+        _ => keep_unknown_key(cursor, key, level, &mut method.extensions)?,
+    }
+    Ok(())
+}
+
+pub fn expand_method_bodies(method: &mut Method,
+                            media_types: Option<&MediaTypes>)
+                            -> Result<(), RamlError> {
+    if let Some(ref mut body) = method.body {
+        expand_default_media_types(body, media_types)?;
+    }
+    if let Some(ref mut responses) = method.responses {
+        expand_response_bodies(responses, media_types)?;
+    }
+    Ok(())
+}
+
+/// Checks the traits referenced by a method are declared. When `merge` is set the traits are
+/// merged into the method, after those applied to the method those applied to its resource.
+pub fn apply_method_traits(method: &mut Method,
+                           resource_traits: &[Trait],
+                           traits: Option<&Traits>,
+                           libraries: Option<&Libraries>,
+                           merge: bool,
+                           context: &TemplateContext)
+                           -> Result<(), RamlError> {
+    let method_traits = get_referenced_traits(method.is.as_ref(),
+                                              traits,
+                                              libraries,
+                                              HierarchyLevel::Method,
+                                              context)?;
+    if merge {
+        for raml_trait in method_traits.iter().chain(resource_traits) {
+            apply_trait(method, raml_trait);
+        }
+    }
+    Ok(())
+}
+
+/// Merges the nodes of a trait into a method. Nodes the method already declares are kept.
+pub fn merge_method(method: &mut Method, source: &Method) {
+    if method.display_name.is_none() {
+        method.display_name = source.display_name.clone();
+    }
+    if method.description.is_none() {
+        method.description = source.description.clone();
+    }
+    merge_map(&mut method.uri_parameters, &source.uri_parameters);
+    // A method declaring either of queryParameters and queryString doesn't take the other.
+    if method.query_string.is_none() {
+        merge_map(&mut method.query_parameters, &source.query_parameters);
+    }
+    if method.query_string.is_none() && method.query_parameters.is_none() {
+        method.query_string = source.query_string.clone();
+    }
+    merge_map(&mut method.headers, &source.headers);
+    merge_map(&mut method.responses, &source.responses);
+    merge_map(&mut method.annotations, &source.annotations);
+    merge_map(&mut method.extensions, &source.extensions);
+    if method.secured_by.is_none() {
+        method.secured_by = source.secured_by.clone();
+    }
+    if method.protocols.is_none() {
+        method.protocols = source.protocols.clone();
+    }
+    if let Some(ref source_is) = source.is {
+        let is = method.is.get_or_insert_with(Vec::new);
+        is.extend(source_is.iter().cloned());
+    }
+    if let Some(ref source_bodies) = source.body {
+        let bodies = method.body.get_or_insert_with(Vec::new);
+        for body in source_bodies {
+            if !bodies.iter().any(|b| b.media_type() == body.media_type()) {
+                bodies.push(body.clone());
+            }
+        }
+    }
+}
+
+fn merge_map<K: Eq + Hash + Clone, V: Clone>(target: &mut Option<HashMap<K, V>>,
+                                              source: &Option<HashMap<K, V>>) {
+    if let Some(ref source) = *source {
+        let target = target.get_or_insert_with(HashMap::new);
+        for (key, value) in source {
+            target.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+}
+
+/// Merges a method of an overlay or extension into the method of the API it extends. `location`
+/// names the method in errors, e.g. `get /users`.
+pub fn merge_overlay_method(method: &mut Method,
+                            source: &Method,
+                            kind: &OverlayKind,
+                            location: &str)
+                            -> Result<(), RamlError> {
+    check_overlay_nodes(kind,
+                        &[("uriParameters", source.uri_parameters.is_some()),
+                          ("queryParameters", source.query_parameters.is_some()),
+                          ("queryString", source.query_string.is_some()),
+                          ("headers", source.headers.is_some()),
+                          ("responses", source.responses.is_some()),
+                          ("body", source.body.is_some()),
+                          ("is", source.is.is_some()),
+                          ("securedBy", source.secured_by.is_some()),
+                          ("protocols", source.protocols.is_some())],
+                        Some(location))?;
+    override_value(&mut method.display_name, &source.display_name);
+    override_value(&mut method.description, &source.description);
+    override_map(&mut method.uri_parameters, &source.uri_parameters);
+    override_map(&mut method.query_parameters, &source.query_parameters);
+    override_value(&mut method.query_string, &source.query_string);
+    override_map(&mut method.headers, &source.headers);
+    override_map(&mut method.responses, &source.responses);
+    override_map(&mut method.annotations, &source.annotations);
+    override_map(&mut method.extensions, &source.extensions);
+    override_value(&mut method.secured_by, &source.secured_by);
+    override_value(&mut method.protocols, &source.protocols);
+    if let Some(ref source_is) = source.is {
+        let is = method.is.get_or_insert_with(Vec::new);
+        is.extend(source_is.iter().cloned());
+    }
+    if let Some(ref source_bodies) = source.body {
+        let bodies = method.body.get_or_insert_with(Vec::new);
+        for body in source_bodies {
+            bodies.retain(|b| b.media_type() != body.media_type());
+            bodies.push(body.clone());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,688 @@
+use serde_json::{Map, Value};
+use parser::{get_uri_template_variables, Raml, SecuredBy, SecuredByEntries, SecurityScheme,
+             SecuritySchemeType, SecuritySchemes};
+use security_settings::{AuthorizationGrant, OAuth2Settings};
+use method::{Method, METHOD_NAMES};
+use resource::Resource;
+use parameter::{NamedParameter, NamedParameters};
+use body::Bodies;
+use response::Responses;
+use traits::TraitReferences;
+use types::{RamlType, SchemaKind};
+use type_expression::TypeExpression;
+use example::{Example, ExampleValue, Examples};
+use annotation::Annotations;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum ConversionWarningKind {
+    /// A trait applied with parameters, which OpenAPI has no equivalent for. Parse with
+    /// `apply_traits` to merge its nodes into the operations it applies to.
+    ParameterizedTrait,
+    /// A resource type applied with parameters, which OpenAPI has no equivalent for. Parse with
+    /// `apply_resource_types` to merge its nodes into the paths it applies to.
+    ParameterizedResourceType,
+    /// A security scheme with no OpenAPI equivalent, such as OAuth 1.0 or an `x-` type, which
+    /// is left out.
+    UnsupportedSecurityScheme,
+    /// An OAuth 2.0 extension grant, which has no OpenAPI flow and is left out.
+    UnsupportedAuthorizationGrant,
+    /// The `examples` of a type, which are left out as a schema only has a single `example`.
+    TypeExamples,
+    /// A type declared with an XML schema, which is converted to an empty schema.
+    XmlSchemaType,
+    /// The pattern properties of a type, which OpenAPI 3.0 has no equivalent for. The type
+    /// allows additional properties instead.
+    PatternProperties,
+}
+
+/// A node of a document that could not be converted to OpenAPI as it is.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct ConversionWarning {
+    kind: ConversionWarningKind,
+    message: String,
+}
+
+impl ConversionWarning {
+    fn new(kind: ConversionWarningKind, message: String) -> ConversionWarning {
+        ConversionWarning { kind, message }
+    }
+
+    pub fn kind(&self) -> ConversionWarningKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+/// Converts a document to OpenAPI 3.0, returning it with the warnings for the nodes that could
+/// not be converted as they are.
+pub fn convert_to_openapi(raml: &Raml) -> (Value, Vec<ConversionWarning>) {
+    let mut warnings = vec![];
+    let info = object(vec![("title", Some(json!(raml.title()))),
+                           ("version", Some(json!(raml.version().unwrap_or_default()))),
+                           ("description", string(raml.description()))]);
+    let schemes = get_security_schemes(raml.security_schemes(), &mut warnings);
+    let mut paths = Map::new();
+    add_paths(&mut paths, raml.resources(), "", &[], &schemes, &mut warnings);
+    let schemas = raml.types().map(|types| {
+        Value::Object(types.iter()
+            .map(|(name, raml_type)| (name.clone(), type_schema(raml_type, name, &mut warnings)))
+            .collect())
+    });
+    let mut components = Map::new();
+    if let Some(schemas) = schemas {
+        components.insert("schemas".to_string(), schemas);
+    }
+    if !schemes.is_empty() {
+        components.insert("securitySchemes".to_string(), Value::Object(schemes.clone()));
+    }
+    let document =
+        object(vec![("openapi", Some(json!("3.0.3"))),
+                    ("info", Some(info)),
+                    ("servers", raml.base_uri().map(|base_uri| json!([server(raml, base_uri)]))),
+                    ("security",
+                     raml.secured_by().map(|secured_by| security(secured_by, &schemes))),
+                    ("paths", Some(Value::Object(paths))),
+                    ("components", non_empty(components))]);
+    (document, warnings)
+}
+
+/// An object of the entries that are given.
+fn object(entries: Vec<(&str, Option<Value>)>) -> Value {
+    Value::Object(entries.into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key.to_string(), value)))
+        .collect())
+}
+
+fn string(value: Option<&str>) -> Option<Value> {
+    value.map(|value| json!(value))
+}
+
+fn non_empty(map: Map<String, Value>) -> Option<Value> {
+    if map.is_empty() {
+        None
+    } else {
+        Some(Value::Object(map))
+    }
+}
+
+/// Adds annotations to an object as specification extensions, e.g. `x-deprecated`.
+fn add_annotations(map: &mut Map<String, Value>, annotations: Option<&Annotations>) {
+    for (name, annotation) in annotations.into_iter().flatten() {
+        map.insert(format!("x-{}", name), example_value(annotation.value()));
+    }
+}
+
+fn server(raml: &Raml, base_uri: &str) -> Value {
+    let url = match raml.version() {
+        Some(version) => base_uri.replace("{version}", version),
+        None => base_uri.to_string(),
+    };
+    let mut variables = Map::new();
+    for name in get_uri_template_variables(&url) {
+        let parameter = raml.base_uri_parameters().and_then(|parameters| parameters.get(&name));
+        variables.insert(name, server_variable(parameter));
+    }
+    object(vec![("url", Some(json!(url))), ("variables", non_empty(variables))])
+}
+
+fn server_variable(parameter: Option<&NamedParameter>) -> Value {
+    // A server variable must have a default, which is the first value of its enum when none is
+    // given.
+    let enum_values = parameter.and_then(NamedParameter::enum_values);
+    let default = parameter.and_then(NamedParameter::default_value)
+        .or_else(|| enum_values.and_then(|values| values.first()).map(String::as_str))
+        .unwrap_or_default();
+    object(vec![("default", Some(json!(default))),
+                ("enum", enum_values.map(|values| json!(values))),
+                ("description", string(parameter.and_then(NamedParameter::description)))])
+}
+
+fn get_security_schemes(schemes: Option<&SecuritySchemes>,
+                        warnings: &mut Vec<ConversionWarning>)
+                        -> Map<String, Value> {
+    // Sorted by name, so the warnings are in a stable order.
+    let mut schemes: Vec<(&String, &SecurityScheme)> = schemes.into_iter().flatten().collect();
+    schemes.sort_by(|a, b| a.0.cmp(b.0));
+    let mut result = Map::new();
+    for (name, scheme) in schemes {
+        if let Some(converted) = security_scheme(name, scheme, warnings) {
+            result.insert(name.clone(), converted);
+        }
+    }
+    result
+}
+
+fn security_scheme(name: &str,
+                   scheme: &SecurityScheme,
+                   warnings: &mut Vec<ConversionWarning>)
+                   -> Option<Value> {
+    let mut converted = match *scheme.security_type() {
+        SecuritySchemeType::OAuth2 => {
+            let flows = oauth2_flows(name, scheme.oauth2_settings(), warnings);
+            json!({ "type": "oauth2", "flows": flows })
+        }
+        SecuritySchemeType::BasicAuthentication => json!({ "type": "http", "scheme": "basic" }),
+        SecuritySchemeType::DigestAuthentication => json!({ "type": "http", "scheme": "digest" }),
+        SecuritySchemeType::PassThrough => {
+            // An API key is sent in one place, so a header is preferred to a query parameter.
+            let settings = scheme.pass_through_settings();
+            match settings.and_then(|s| s.header_name()) {
+                Some(header) => json!({ "type": "apiKey", "in": "header", "name": header }),
+                None => {
+                    let query = settings.and_then(|s| s.query_parameter_name());
+                    json!({ "type": "apiKey", "in": "query", "name": query })
+                }
+            }
+        }
+        _ => {
+            warnings.push(ConversionWarning::new(ConversionWarningKind::UnsupportedSecurityScheme,
+                                                 format!("Security scheme {} of type {} has no \
+                                                          OpenAPI equivalent",
+                                                         name,
+                                                         scheme.security_type())));
+            return None;
+        }
+    };
+    if let Some(description) = scheme.description() {
+        converted["description"] = json!(description);
+    }
+    Some(converted)
+}
+
+fn oauth2_flows(name: &str,
+                settings: Option<&OAuth2Settings>,
+                warnings: &mut Vec<ConversionWarning>)
+                -> Value {
+    let settings = match settings {
+        Some(settings) => settings,
+        None => return json!({}),
+    };
+    let scopes: Map<String, Value> = settings.scopes()
+        .into_iter()
+        .flatten()
+        .map(|scope| (scope.clone(), json!("")))
+        .collect();
+    let scopes = Some(Value::Object(scopes));
+    let authorization_url = string(settings.authorization_uri());
+    let token_url = string(settings.access_token_uri());
+    let mut flows = Map::new();
+    for grant in settings.authorization_grants() {
+        let (flow, converted) = match *grant {
+            AuthorizationGrant::AuthorizationCode => {
+                ("authorizationCode",
+                 object(vec![("authorizationUrl", authorization_url.clone()),
+                             ("tokenUrl", token_url.clone()),
+                             ("scopes", scopes.clone())]))
+            }
+            AuthorizationGrant::Implicit => {
+                ("implicit",
+                 object(vec![("authorizationUrl", authorization_url.clone()),
+                             ("scopes", scopes.clone())]))
+            }
+            AuthorizationGrant::Password => {
+                ("password",
+                 object(vec![("tokenUrl", token_url.clone()), ("scopes", scopes.clone())]))
+            }
+            AuthorizationGrant::ClientCredentials => {
+                ("clientCredentials",
+                 object(vec![("tokenUrl", token_url.clone()), ("scopes", scopes.clone())]))
+            }
+            AuthorizationGrant::Extension(ref uri) => {
+                warnings.push(ConversionWarning::new(
+                    ConversionWarningKind::UnsupportedAuthorizationGrant,
+                    format!("Grant {} of security scheme {} has no OpenAPI flow", uri, name)));
+                continue;
+            }
+        };
+        flows.insert(flow.to_string(), converted);
+    }
+    Value::Object(flows)
+}
+
+/// The security requirements of a `securedBy` node, leaving out the schemes that were not
+/// converted. `null` is the empty requirement, which makes security optional.
+fn security(secured_by: &SecuredByEntries, schemes: &Map<String, Value>) -> Value {
+    let requirements = secured_by.iter().filter_map(|entry| {
+        let scopes = match *entry {
+            SecuredBy::Null => return Some(json!({})),
+            SecuredBy::Scheme(_) => vec![],
+            SecuredBy::Parameterized(_, ref parameters) => {
+                match parameters.get("scopes") {
+                    Some(ExampleValue::Sequence(scopes)) => {
+                        scopes.iter().map(example_value).collect()
+                    }
+                    _ => vec![],
+                }
+            }
+        };
+        let name = entry.name().unwrap_or_default();
+        if !schemes.contains_key(name) {
+            return None;
+        }
+        let mut requirement = Map::new();
+        requirement.insert(name.to_string(), Value::Array(scopes));
+        Some(Value::Object(requirement))
+    });
+    Value::Array(requirements.collect())
+}
+
+fn warn_parameterized_traits(references: Option<&TraitReferences>,
+                             location: &str,
+                             warnings: &mut Vec<ConversionWarning>) {
+    for reference in references.into_iter().flatten() {
+        if reference.parameters().is_some() {
+            warnings.push(ConversionWarning::new(ConversionWarningKind::ParameterizedTrait,
+                                                 format!("Trait {} applied to {} with \
+                                                          parameters is not converted",
+                                                         reference.name(),
+                                                         location)));
+        }
+    }
+}
+
+/// Adds a path for each resource with methods. `parent_parameters` are the uri parameters
+/// declared by the enclosing resources.
+fn add_paths<'a>(paths: &mut Map<String, Value>,
+                 resources: &'a [Resource],
+                 parent_path: &str,
+                 parent_parameters: &[(&'a String, &'a NamedParameter)],
+                 schemes: &Map<String, Value>,
+                 warnings: &mut Vec<ConversionWarning>) {
+    for resource in resources {
+        let path = format!("{}{}", parent_path, resource.relative_uri());
+        let mut uri_parameters = parent_parameters.to_vec();
+        uri_parameters.extend(resource.uri_parameters().into_iter().flatten());
+        if let Some(reference) = resource.resource_type() {
+            if reference.parameters().is_some() {
+                warnings.push(ConversionWarning::new(
+                    ConversionWarningKind::ParameterizedResourceType,
+                    format!("Resource type {} applied to {} with parameters is not converted",
+                            reference.name(),
+                            path)));
+            }
+        }
+        warn_parameterized_traits(resource.is(), &path, warnings);
+        if !resource.methods().is_empty() {
+            let mut item = Map::new();
+            if let Some(name) = resource.display_name() {
+                item.insert("summary".to_string(), json!(name));
+            }
+            if let Some(description) = resource.description() {
+                item.insert("description".to_string(), json!(description));
+            }
+            // Every template variable of the path is a required path parameter, and the
+            // innermost declaration of a parameter is used.
+            let parameters: Vec<Value> = get_uri_template_variables(&path)
+                .iter()
+                .map(|name| {
+                    match uri_parameters.iter().rev().find(|(n, _)| *n == name) {
+                        Some(&(_, parameter)) => named_parameter(name, "path", parameter, true),
+                        None => {
+                            json!({ "name": name, "in": "path", "required": true,
+                                    "schema": { "type": "string" } })
+                        }
+                    }
+                })
+                .collect();
+            if !parameters.is_empty() {
+                item.insert("parameters".to_string(), Value::Array(parameters));
+            }
+            for verb in METHOD_NAMES.iter() {
+                if let Some(method) = resource.methods().get(*verb) {
+                    let location = format!("{} {}", verb, path);
+                    item.insert(verb.to_string(),
+                                operation(method, resource, &location, schemes, warnings));
+                }
+            }
+            add_annotations(&mut item, resource.annotations());
+            paths.insert(path.clone(), Value::Object(item));
+        }
+        add_paths(paths, resource.resources(), &path, &uri_parameters, schemes, warnings);
+    }
+}
+
+fn operation(method: &Method,
+             resource: &Resource,
+             location: &str,
+             schemes: &Map<String, Value>,
+             warnings: &mut Vec<ConversionWarning>)
+             -> Value {
+    warn_parameterized_traits(method.is(), location, warnings);
+    let mut parameters = named_parameters(method.query_parameters(), "query");
+    parameters.extend(named_parameters(method.headers(), "header"));
+    let parameters = if parameters.is_empty() {
+        None
+    } else {
+        Some(Value::Array(parameters))
+    };
+    let request_body = method.body()
+        .map(|bodies| json!({ "content": content(bodies), "required": true }));
+    // The security of the API applies unless the method or its resource declares its own.
+    let security = method.secured_by()
+        .or_else(|| resource.secured_by())
+        .map(|secured_by| security(secured_by, schemes));
+    let mut converted = object(vec![("summary", string(method.display_name())),
+                                    ("description", string(method.description())),
+                                    ("parameters", parameters),
+                                    ("requestBody", request_body),
+                                    ("responses", Some(responses(method.responses()))),
+                                    ("security", security)]);
+    if let Value::Object(ref mut map) = converted {
+        add_annotations(map, method.annotations());
+    }
+    converted
+}
+
+fn named_parameters(parameters: Option<&NamedParameters>, location: &str) -> Vec<Value> {
+    let mut parameters: Vec<(&String, &NamedParameter)> =
+        parameters.into_iter().flatten().collect();
+    parameters.sort_by(|a, b| a.0.cmp(b.0));
+    parameters.into_iter()
+        .map(|(name, parameter)| {
+            named_parameter(name, location, parameter, parameter.required())
+        })
+        .collect()
+}
+
+fn named_parameter(name: &str,
+                   location: &str,
+                   parameter: &NamedParameter,
+                   required: bool)
+                   -> Value {
+    let mut converted = header(parameter, required);
+    converted["name"] = json!(name);
+    converted["in"] = json!(location);
+    converted
+}
+
+/// A header object, which is a parameter object without its name and location.
+fn header(parameter: &NamedParameter, required: bool) -> Value {
+    let type_name = parameter.param_type().unwrap_or("string");
+    let mut facets = Map::new();
+    if let Some(default) = parameter.default_value() {
+        facets.insert("default".to_string(), typed_value(default, type_name));
+    }
+    if let Some(values) = parameter.enum_values() {
+        facets.insert("enum".to_string(),
+                      values.iter().map(|value| typed_value(value, type_name)).collect());
+    }
+    insert_number(&mut facets, "minimum", parameter.minimum());
+    insert_number(&mut facets, "maximum", parameter.maximum());
+    insert_number(&mut facets, "multipleOf", parameter.multiple_of());
+    insert_number(&mut facets, "minLength", parameter.min_length().map(|n| n as f64));
+    insert_number(&mut facets, "maxLength", parameter.max_length().map(|n| n as f64));
+    object(vec![("description", string(parameter.description())),
+                ("required", Some(json!(required))),
+                ("schema", Some(with_facets(named_schema(type_name), facets))),
+                ("example", parameter.example().map(|example| example_value(example.value()))),
+                ("examples", parameter.examples().map(examples))])
+}
+
+fn content(bodies: &Bodies) -> Value {
+    Value::Object(bodies.iter()
+        .map(|body| {
+            (body.media_type().to_string(),
+             object(vec![("schema", body.body_type().map(expression_schema)),
+                         ("example", body.example().map(|example| example_value(example.value()))),
+                         ("examples", body.examples().map(examples))]))
+        })
+        .collect())
+}
+
+fn responses(responses: Option<&Responses>) -> Value {
+    let responses = match responses {
+        Some(responses) if !responses.is_empty() => responses,
+        // An operation must have at least one response.
+        _ => return json!({ "default": { "description": "" } }),
+    };
+    Value::Object(responses.iter()
+        .map(|(code, response)| {
+            let headers = response.headers().map(|headers| {
+                Value::Object(headers.iter()
+                    .map(|(name, parameter)| {
+                        (name.clone(), header(parameter, parameter.required()))
+                    })
+                    .collect())
+            });
+            (code.to_string(),
+             object(vec![("description",
+                          Some(json!(response.description().unwrap_or_default()))),
+                         ("headers", headers),
+                         ("content", response.body().map(content))]))
+        })
+        .collect())
+}
+
+fn examples(examples: &Examples) -> Value {
+    Value::Object(examples.iter()
+        .map(|(name, example): (&String, &Example)| {
+            (name.clone(),
+             object(vec![("summary", string(example.display_name())),
+                         ("description", string(example.description())),
+                         ("value", Some(example_value(example.value())))]))
+        })
+        .collect())
+}
+
+fn example_value(value: &ExampleValue) -> Value {
+    match *value {
+        ExampleValue::String(ref value) => json!(value),
+        ExampleValue::Number(number) => number_value(number),
+        ExampleValue::Boolean(boolean) => json!(boolean),
+        ExampleValue::Null => Value::Null,
+        ExampleValue::Sequence(ref values) => values.iter().map(example_value).collect(),
+        ExampleValue::Mapping(ref entries) => {
+            Value::Object(entries.iter()
+                .map(|(key, value)| (key.clone(), example_value(value)))
+                .collect())
+        }
+    }
+}
+
+/// A number, written without a fraction when it is a whole number.
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        json!(number as i64)
+    } else {
+        json!(number)
+    }
+}
+
+fn insert_number(map: &mut Map<String, Value>, key: &str, number: Option<f64>) {
+    if let Some(number) = number {
+        map.insert(key.to_string(), number_value(number));
+    }
+}
+
+/// A value given as text, such as a default, as the JSON value of its type.
+fn typed_value(value: &str, type_name: &str) -> Value {
+    let typed = match type_name {
+        "integer" | "number" => value.parse::<f64>().ok().map(number_value),
+        "boolean" => value.parse::<bool>().ok().map(|boolean| json!(boolean)),
+        _ => None,
+    };
+    typed.unwrap_or_else(|| json!(value))
+}
+
+/// The schema of a built-in or declared type.
+fn named_schema(name: &str) -> Value {
+    match name {
+        "string" | "number" | "integer" | "boolean" | "object" | "array" => json!({ "type": name }),
+        "date-only" => json!({ "type": "string", "format": "date" }),
+        "datetime" => json!({ "type": "string", "format": "date-time" }),
+        "time-only" | "datetime-only" => json!({ "type": "string" }),
+        "file" => json!({ "type": "string", "format": "binary" }),
+        "nil" => json!({ "nullable": true }),
+        "any" => json!({}),
+        _ => json!({ "$ref": format!("#/components/schemas/{}", name) }),
+    }
+}
+
+fn expression_schema(expression: &TypeExpression) -> Value {
+    match *expression {
+        TypeExpression::Named(ref name) => named_schema(name),
+        TypeExpression::Array(ref items) => {
+            json!({ "type": "array", "items": expression_schema(items) })
+        }
+        TypeExpression::Union(ref members) => {
+            let is_nil = |member: &&TypeExpression| match **member {
+                TypeExpression::Named(ref name) => name == "nil",
+                _ => false,
+            };
+            let mut schemas: Vec<Value> =
+                members.iter().filter(|m| !is_nil(m)).map(expression_schema).collect();
+            let schema = if schemas.len() == 1 {
+                schemas.remove(0)
+            } else {
+                json!({ "oneOf": schemas })
+            };
+            if members.iter().any(|m| is_nil(&m)) {
+                let mut nullable = Map::new();
+                nullable.insert("nullable".to_string(), json!(true));
+                with_facets(schema, nullable)
+            } else {
+                schema
+            }
+        }
+    }
+}
+
+/// Adds facets to a schema. A reference can't have other keys, so it is wrapped in `allOf`.
+fn with_facets(schema: Value, facets: Map<String, Value>) -> Value {
+    if facets.is_empty() {
+        return schema;
+    }
+    let mut map = match schema {
+        Value::Object(ref map) if map.contains_key("$ref") => {
+            let mut map = Map::new();
+            map.insert("allOf".to_string(), json!([schema]));
+            map
+        }
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    map.extend(facets);
+    Value::Object(map)
+}
+
+fn type_schema(raml_type: &RamlType,
+               name: &str,
+               warnings: &mut Vec<ConversionWarning>)
+               -> Value {
+    if let Some(schema) = raml_type.schema() {
+        if schema.kind() == SchemaKind::Json {
+            return serde_json::from_str(schema.raw()).unwrap_or_else(|_| json!({}));
+        }
+        warnings.push(ConversionWarning::new(ConversionWarningKind::XmlSchemaType,
+                                             format!("The XML schema of type {} is left out",
+                                                     name)));
+        return json!({});
+    }
+    let parents = raml_type.parent_types();
+    let schema = match parents {
+        [] => json!({}),
+        [parent] => expression_schema(parent),
+        _ => json!({ "allOf": parents.iter().map(expression_schema).collect::<Vec<_>>() }),
+    };
+    let type_name = match parents {
+        [TypeExpression::Named(ref parent)] => parent.as_str(),
+        _ => "string",
+    };
+    let mut facets = Map::new();
+    if parents.is_empty() && raml_type.properties().is_some() {
+        facets.insert("type".to_string(), json!("object"));
+    }
+    if let Some(display_name) = raml_type.display_name() {
+        facets.insert("title".to_string(), json!(display_name));
+    }
+    if let Some(description) = raml_type.description() {
+        facets.insert("description".to_string(), json!(description));
+    }
+    if let Some(default) = raml_type.default_value() {
+        facets.insert("default".to_string(), typed_value(default, type_name));
+    }
+    if let Some(pattern) = raml_type.pattern() {
+        facets.insert("pattern".to_string(), json!(pattern));
+    }
+    insert_number(&mut facets, "minLength", raml_type.min_length().map(|n| n as f64));
+    insert_number(&mut facets, "maxLength", raml_type.max_length().map(|n| n as f64));
+    insert_number(&mut facets, "minimum", raml_type.minimum());
+    insert_number(&mut facets, "maximum", raml_type.maximum());
+    insert_number(&mut facets, "multipleOf", raml_type.multiple_of());
+    if let Some(values) = raml_type.enum_values() {
+        facets.insert("enum".to_string(),
+                      values.iter().map(|value| typed_value(value, type_name)).collect());
+    }
+    if let Some(properties) = raml_type.properties() {
+        let mut required: Vec<&String> =
+            properties.iter().filter(|(_, p)| p.required()).map(|(name, _)| name).collect();
+        required.sort();
+        let properties: Map<String, Value> = properties.iter()
+            .map(|(property_name, property)| {
+                (property_name.clone(), type_schema(property.property_type(), name, warnings))
+            })
+            .collect();
+        facets.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            facets.insert("required".to_string(), json!(required));
+        }
+    }
+    if raml_type.pattern_properties().is_some() {
+        warnings.push(ConversionWarning::new(ConversionWarningKind::PatternProperties,
+                                             format!("The pattern properties of type {} are left \
+                                                      out",
+                                                     name)));
+    } else if !raml_type.additional_properties() {
+        facets.insert("additionalProperties".to_string(), json!(false));
+    }
+    insert_number(&mut facets, "minProperties", raml_type.min_properties().map(|n| n as f64));
+    insert_number(&mut facets, "maxProperties", raml_type.max_properties().map(|n| n as f64));
+    if let Some(items) = raml_type.items() {
+        facets.insert("items".to_string(), type_schema(items, name, warnings));
+    }
+    insert_number(&mut facets, "minItems", raml_type.min_items().map(|n| n as f64));
+    insert_number(&mut facets, "maxItems", raml_type.max_items().map(|n| n as f64));
+    if let Some(unique_items) = raml_type.unique_items() {
+        facets.insert("uniqueItems".to_string(), json!(unique_items));
+    }
+    if let Some(example) = raml_type.example() {
+        facets.insert("example".to_string(), example_value(example.value()));
+    }
+    if raml_type.examples().is_some() {
+        warnings.push(ConversionWarning::new(ConversionWarningKind::TypeExamples,
+                                             format!("The examples of type {} are left out",
+                                                     name)));
+    }
+    if let Some(discriminator) = raml_type.discriminator() {
+        facets.insert("discriminator".to_string(), json!({ "propertyName": discriminator }));
+    }
+    if let Some(xml) = raml_type.xml() {
+        // The XML object of OpenAPI has the same fields as the xml facet.
+        let mut fields = Map::new();
+        for &(field, value) in &[("attribute", xml.attribute()), ("wrapped", xml.wrapped())] {
+            if let Some(value) = value {
+                fields.insert(field.to_string(), json!(value));
+            }
+        }
+        for &(field, value) in &[("name", xml.name()),
+                                 ("namespace", xml.namespace()),
+                                 ("prefix", xml.prefix())] {
+            if let Some(value) = value {
+                fields.insert(field.to_string(), json!(value));
+            }
+        }
+        facets.insert("xml".to_string(), Value::Object(fields));
+    }
+    add_annotations(&mut facets, raml_type.annotations());
+    with_facets(schema, facets)
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use validation::ValidationRule;
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct ParseOptions {
+    /// Reject resources whose uri template variables and declared `uriParameters` do not match.
+    /// By default undeclared template variables are treated as implicit string parameters.
+    pub validate_uri_parameters: bool,
+    /// Merge the traits referenced with `is` into the methods that use them. Undeclared traits
+    /// are reported either way.
+    pub apply_traits: bool,
+    /// Merge the resource types referenced with `type` into the resources that use them, before
+    /// any traits are applied. Undeclared resource types are reported either way.
+    pub apply_resource_types: bool,
+    /// What to do with keys the parser doesn't know, such as vendor extensions.
+    pub unknown_keys: UnknownKeyBehaviour,
+    /// The deepest nesting of mappings and sequences allowed, including the nodes of included
+    /// documents. Nodes are read recursively, so deeper documents fail with a `LimitExceeded`
+    /// error rather than overflowing the stack. 200 by default.
+    pub max_depth: usize,
+    /// The deepest nesting of `!include`s allowed. 16 by default.
+    pub max_include_depth: usize,
+    /// The most `!include`s allowed, counting a document included more than once each time.
+    /// 1000 by default.
+    pub max_includes: usize,
+    /// The largest size in bytes allowed of a document together with the documents it includes.
+    /// 16 MiB by default.
+    pub max_source_bytes: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            validate_uri_parameters: false,
+            apply_traits: false,
+            apply_resource_types: false,
+            unknown_keys: UnknownKeyBehaviour::default(),
+            max_depth: 200,
+            max_include_depth: 16,
+            max_includes: 1000,
+            max_source_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub enum UnknownKeyBehaviour {
+    /// Fail with an `UnexpectedKeyRoot` error.
+    #[default]
+    Error,
+    /// Skip the key and its value, reporting an `UnknownKey` warning at its position.
+    Warn,
+    /// Skip the key and its value silently.
+    Ignore,
+}
+
+/// Options for `Raml::validate_with_options`.
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+pub struct ValidationOptions {
+    /// The rules not to check.
+    pub disabled_rules: HashSet<ValidationRule>,
+    /// The rules to check that aren't checked by default, such as
+    /// `ValidationRule::UriParametersDeclared`.
+    pub enabled_rules: HashSet<ValidationRule>,
+}
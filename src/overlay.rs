@@ -0,0 +1,109 @@
+use error_definitions::{ErrorDef, RamlError, get_error};
+use std::collections::HashMap;
+use std::hash::Hash;
+use yaml::*;
+use include::IncludeResolver;
+use options::ParseOptions;
+use parser::{parse_overlay_root, Raml};
+use fragment::get_fragment_kind;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum OverlayKind {
+    /// May only change the descriptive nodes of the API, such as descriptions, display names,
+    /// documentation, examples, types and annotations.
+    Overlay,
+    /// May add or change any node of the API.
+    Extension,
+}
+
+/// A `#%RAML 1.0 Overlay` or `#%RAML 1.0 Extension` document, applied to the API its `extends`
+/// node refers to with `Raml::apply_overlay`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct RamlOverlay {
+    kind: OverlayKind,
+    extends: String,
+    document: Raml,
+}
+
+impl RamlOverlay {
+    pub fn kind(&self) -> &OverlayKind {
+        &self.kind
+    }
+
+    pub fn extends(&self) -> &str {
+        self.extends.as_str()
+    }
+
+    /// The nodes of the overlay. Its title is empty when the overlay doesn't change it.
+    pub fn document(&self) -> &Raml {
+        &self.document
+    }
+}
+
+pub fn parse_overlay(source: &str,
+                     options: &ParseOptions,
+                     resolver: Option<&dyn IncludeResolver>,
+                     path: Option<&str>)
+                     -> Result<RamlOverlay, RamlError> {
+    let kind = match get_fragment_kind(source) {
+        Some("Overlay") => OverlayKind::Overlay,
+        Some("Extension") => OverlayKind::Extension,
+        _ => return Err(get_error(ErrorDef::MissingOverlayHeader, None)),
+    };
+    let mut cursor = match resolver {
+        Some(resolver) => ForwardCursor::with_resolver(source, options.clone(), resolver, path)?,
+        None => ForwardCursor::with_options(source, options.clone()),
+    };
+    let (document, extends) =
+        parse_overlay_root(&mut cursor).map_err(|error| cursor.annotate(error))?;
+    Ok(RamlOverlay {
+        kind,
+        extends,
+        document,
+    })
+}
+
+/// Fails when an overlay declares any of `nodes`, given as each node's name and whether it is
+/// declared. `location` names where the nodes are declared in the error, e.g. `get /users`.
+pub fn check_overlay_nodes(kind: &OverlayKind,
+                           nodes: &[(&str, bool)],
+                           location: Option<&str>)
+                           -> Result<(), RamlError> {
+    if *kind == OverlayKind::Extension {
+        return Ok(());
+    }
+    match nodes.iter().find(|&&(_, declared)| declared) {
+        Some(&(node, _)) => {
+            let node = match location {
+                Some(location) => format!("{} of {}", node, location),
+                None => node.to_string(),
+            };
+            Err(get_error(ErrorDef::RestrictedOverlayNode { node }, None))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Replaces a node with the node of an overlay, when the overlay declares it.
+pub fn override_value<T: Clone>(target: &mut Option<T>, source: &Option<T>) {
+    if source.is_some() {
+        *target = source.clone();
+    }
+}
+
+/// Adds the entries of an overlay's map to a map, replacing entries with the same key.
+pub fn override_map<K: Eq + Hash + Clone, V: Clone>(target: &mut Option<HashMap<K, V>>,
+                                                     source: &Option<HashMap<K, V>>) {
+    if let Some(ref source) = *source {
+        let target = target.get_or_insert_with(HashMap::new);
+        for (key, value) in source {
+            target.insert(key.clone(), value.clone());
+        }
+    }
+}
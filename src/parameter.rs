@@ -0,0 +1,201 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::{RamlError, HierarchyLevel};
+use std::collections::HashMap;
+use yaml::*;
+use example::{Example, Examples, get_example, get_examples, scalar_text};
+use types::{check_enum_value, check_facet_range, get_enum_values, FacetMarkers};
+
+pub type NamedParameters = HashMap<String, NamedParameter>;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct NamedParameter {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    param_type: Option<String>,
+    display_name: Option<String>,
+    description: Option<String>,
+    required: Option<bool>,
+    default: Option<String>,
+    example: Option<Example>,
+    examples: Option<Examples>,
+    #[cfg_attr(feature = "serde", serde(rename = "enum"))]
+    enum_values: Option<Vec<String>>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    multiple_of: Option<f64>,
+    min_length: Option<u64>,
+    max_length: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    implicit: bool,
+}
+
+impl NamedParameter {
+    /// A string parameter standing in for a uri template variable that has no declaration.
+    pub fn implicit() -> NamedParameter {
+        NamedParameter {
+            param_type: Some("string".to_string()),
+            implicit: true,
+            ..NamedParameter::default()
+        }
+    }
+
+    /// Whether the parameter is a template variable's implicit declaration, rather than
+    /// declared in the document.
+    pub fn is_implicit(&self) -> bool {
+        self.implicit
+    }
+
+    pub fn param_type(&self) -> Option<&str> {
+        self.param_type.as_deref()
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// The display name, or else `key`, the name the parameter is declared with.
+    pub fn display_name_or_key<'a>(&'a self, key: &'a str) -> &'a str {
+        self.display_name.as_deref().unwrap_or(key)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Whether the parameter must be given, which it must unless declared with `required: false`.
+    pub fn required(&self) -> bool {
+        self.required.unwrap_or(true)
+    }
+
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    pub fn example(&self) -> Option<&Example> {
+        self.example.as_ref()
+    }
+
+    pub fn examples(&self) -> Option<&Examples> {
+        self.examples.as_ref()
+    }
+
+    pub fn enum_values(&self) -> Option<&Vec<String>> {
+        self.enum_values.as_ref()
+    }
+
+    pub fn minimum(&self) -> Option<f64> {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> Option<f64> {
+        self.maximum
+    }
+
+    pub fn multiple_of(&self) -> Option<f64> {
+        self.multiple_of
+    }
+
+    pub fn min_length(&self) -> Option<u64> {
+        self.min_length
+    }
+
+    pub fn max_length(&self) -> Option<u64> {
+        self.max_length
+    }
+}
+
+pub struct NamedParameterEntry {
+    pub name: String,
+    pub marker: Marker,
+    pub parameter: NamedParameter,
+}
+
+pub fn get_named_parameters(cursor: &mut ForwardCursor,
+                            level: HierarchyLevel)
+                            -> Result<NamedParameters, RamlError> {
+    let entries = get_named_parameter_entries(cursor, level)?;
+    Ok(entries.into_iter().map(|e| (e.name, e.parameter)).collect())
+}
+
+/// Parses a map of named parameters, keeping the marker of each parameter name.
+pub fn get_named_parameter_entries(cursor: &mut ForwardCursor,
+                                   level: HierarchyLevel)
+                                   -> Result<Vec<NamedParameterEntry>, RamlError> {
+    let mut result: Vec<NamedParameterEntry> = vec![];
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        result.push(NamedParameterEntry {
+            name: key.value,
+            marker: key.marker,
+            parameter: get_named_parameter(cursor, level.clone())?,
+        });
+    }
+    Ok(result)
+}
+
+fn get_named_parameter(cursor: &mut ForwardCursor,
+                       level: HierarchyLevel)
+                       -> Result<NamedParameter, RamlError> {
+    let mut parameter = NamedParameter::default();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        None => {
+            // Shorthand form, e.g. `page: integer`
+            parameter.param_type = Some(get_scalar_value(cursor)?.value);
+            return Ok(parameter);
+        }
+    };
+    let mut default_marker: Option<Marker> = None;
+    let mut example_marker: Option<Marker> = None;
+    let mut facet_markers = FacetMarkers::new();
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        facet_markers.insert(key.value.clone(), cursor.peek_value_marker()?);
+        match key.value.as_str() {
+            "type" => parameter.param_type = Some(get_single_value(cursor)?),
+            "displayName" => parameter.display_name = Some(get_single_value(cursor)?),
+            "description" => parameter.description = get_optional_value(cursor)?,
+            "required" => parameter.required = Some(get_boolean_value(cursor)?),
+            "default" => {
+                default_marker = Some(key.marker);
+                parameter.default = Some(get_single_value(cursor)?);
+            }
+            "example" => {
+                example_marker = Some(key.marker);
+                parameter.example = Some(get_example(cursor)?);
+            }
+            "examples" => parameter.examples = Some(get_examples(cursor)?),
+            "enum" => parameter.enum_values = Some(get_enum_values(cursor, key.marker)?),
+            "minimum" => parameter.minimum = Some(get_number_value(cursor)?),
+            "maximum" => parameter.maximum = Some(get_number_value(cursor)?),
+            "multipleOf" => parameter.multiple_of = Some(get_number_value(cursor)?),
+            "minLength" => parameter.min_length = Some(get_unsigned_value(cursor)?),
+            "maxLength" => parameter.max_length = Some(get_unsigned_value(cursor)?),
+            _ => skip_unknown_key(cursor, key, level.clone())?,
+        }
+    }
+    check_enum_value(parameter.enum_values.as_ref(),
+                     "default",
+                     parameter.default.as_ref(),
+                     default_marker)?;
+    check_enum_value(parameter.enum_values.as_ref(),
+                     "example",
+                     parameter.example.as_ref().and_then(|e| scalar_text(e.value())).as_ref(),
+                     example_marker)?;
+    check_facet_range("minimum",
+                      parameter.minimum,
+                      "maximum",
+                      parameter.maximum,
+                      &facet_markers)?;
+    check_facet_range("minLength",
+                      parameter.min_length,
+                      "maxLength",
+                      parameter.max_length,
+                      &facet_markers)?;
+    Ok(parameter)
+}
@@ -1,50 +1,200 @@
-use yaml_rust::scanner::TokenType;
+use yaml_rust::scanner::{Marker, Token, TokenType};
 use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use yaml::*;
+use resource::{apply_resource_traits, apply_resource_types, check_resource_annotations,
+               check_resource_secured_by, expand_resource_bodies, find_resource_by_path,
+               get_annotated_nodes, get_operations, get_resource, merge_overlay_resources,
+               Operation, Resource, Resources, UriParameterValues};
+use method::Method;
+use example::{get_example_value, ExampleValue};
+use std::ptr;
+use resource_type::{get_resource_types, ResourceType, ResourceTypes};
+use parameter::{get_named_parameter_entries, get_named_parameters, NamedParameter,
+                NamedParameterEntry, NamedParameters};
+use response::{get_responses, Responses};
+use security_settings::{check_required_settings, get_settings, OAuth1Settings, OAuth2Settings,
+                        PassThroughSettings, SecuritySchemeSettings};
 use std::str::FromStr;
+use std::fmt;
+use options::{ParseOptions, ValidationOptions};
+use traits::{get_traits, Trait, Traits};
+use type_dependencies::{type_closure, types_in_dependency_order};
+use types::{check_circular_inheritance, check_parent_types, check_type_annotations,
+            check_type_facets, find_subtype_by_discriminator, get_annotated_types,
+            get_annotation_types, get_types, resolve_type, RamlType, ResolvedType, Types};
+use annotation::{check_annotations, find_annotation, get_annotation, get_annotation_and_node,
+                 get_annotation_name, AnnotatedNode, AnnotationValue, Annotations};
+use library::{find_declaration, get_uses, parse_library, Libraries, Library};
+use include::{read_source, FileResolver, IncludeResolver};
+use fragment::{get_fragment_kind, get_header, parse_fragment, RamlFragment};
+use std::path::Path;
+use std::io::Read;
+use overlay::{check_overlay_nodes, override_map, override_value, parse_overlay, RamlOverlay};
+use warnings::{get_warnings, get_wildcard_media_type_warning, RamlWarning};
+use location::{get_location, SourceFile, SourceLocation};
+use validation::{get_violations, RamlViolation};
+use emitter::emit_raml;
+use media_type::MediaType;
+use visitor::{visit_document, RamlVisitor};
+#[cfg(feature = "openapi")]
+use openapi::{convert_to_openapi, ConversionWarning};
+#[cfg(feature = "markdown")]
+use markdown::render_markdown;
 
 pub type RamlResult = Result<Raml, RamlError>;
 
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Protocol {
     Http,
     Https,
 }
 
+impl Protocol {
+    /// The protocol as written in a document, `HTTP` or `HTTPS`.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Protocol::Http => "HTTP",
+            Protocol::Https => "HTTPS",
+        }
+    }
+
+    /// The port a server listens on for the protocol unless told otherwise, 80 or 443.
+    pub fn default_port(&self) -> u16 {
+        match *self {
+            Protocol::Http => 80,
+            Protocol::Https => 443,
+        }
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Reads a protocol ignoring case, e.g. `http` or `HTTPS`.
+impl FromStr for Protocol {
+    type Err = RamlError;
+
+    fn from_str(s: &str) -> Result<Protocol, RamlError> {
+        match s.to_lowercase().as_str() {
+            "http" => Ok(Protocol::Http),
+            "https" => Ok(Protocol::Https),
+            _ => Err(get_error(ErrorDef::InvalidProtocol { protocol: s.to_string() }, None)),
+        }
+    }
+}
+
 pub type Protocols = Vec<Protocol>;
 
 #[derive(Debug)]
-#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Raml {
     title: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    title_location: Option<SourceLocation>,
     version: Option<String>,
     description: Option<String>,
     base_uri: Option<String>,
+    base_uri_parameters: Option<NamedParameters>,
     protocols: Option<Protocols>,
+    #[cfg_attr(feature = "serde", serde(rename = "mediaType"))]
     media_types: Option<MediaTypes>,
     documentation: Option<RamlDocumentationEntries>,
     security_schemes: Option<SecuritySchemes>,
+    secured_by: Option<SecuredByEntries>,
+    resources: Resources,
+    types: Option<Types>,
+    traits: Option<Traits>,
+    resource_types: Option<ResourceTypes>,
+    annotation_types: Option<Types>,
+    annotations: Option<Annotations>,
+    extensions: Option<Extensions>,
+    uses: Option<Libraries>,
+    // Whether `protocols` was not declared, but taken from the scheme of the baseUri.
+    #[cfg_attr(feature = "serde", serde(default))]
+    protocols_inferred: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source_path: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source_files: Vec<SourceFile>,
+}
+
+// Documents are equal when they declare the same nodes, wherever they are declared. The extensions
+// are left out, as their nodes keep the positions they were read from, as are the files the
+// document was read from.
+impl PartialEq for Raml {
+    fn eq(&self, other: &Raml) -> bool {
+        self.title == other.title &&
+        self.version == other.version &&
+        self.description == other.description &&
+        self.base_uri == other.base_uri &&
+        self.base_uri_parameters == other.base_uri_parameters &&
+        self.protocols == other.protocols &&
+        self.media_types == other.media_types &&
+        self.documentation == other.documentation &&
+        self.security_schemes == other.security_schemes &&
+        self.secured_by == other.secured_by &&
+        self.resources == other.resources &&
+        self.types == other.types &&
+        self.traits == other.traits &&
+        self.resource_types == other.resource_types &&
+        self.annotation_types == other.annotation_types &&
+        self.annotations == other.annotations &&
+        self.uses == other.uses &&
+        self.protocols_inferred == other.protocols_inferred
+    }
 }
 
 #[derive(Debug)]
-#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct RamlDocumentation {
     title: String,
     content: String,
+    annotations: Option<Annotations>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Option<SourceLocation>,
+}
+
+impl PartialEq for RamlDocumentation {
+    fn eq(&self, other: &RamlDocumentation) -> bool {
+        self.title == other.title && self.content == other.content &&
+        self.annotations == other.annotations
+    }
 }
 
+impl Eq for RamlDocumentation {}
+
 pub type RamlDocumentationEntries = Vec<RamlDocumentation>;
 
 impl RamlDocumentation {
     pub fn new(title: String, content: String) -> RamlDocumentation {
         RamlDocumentation {
-            title: title,
-            content: content,
+            title,
+            content,
+            annotations: None,
+            location: None,
         }
     }
 
+    /// Where the entry is declared, when it was parsed.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+
     pub fn title(&self) -> &str {
         self.title.as_str()
     }
@@ -52,12 +202,25 @@ impl RamlDocumentation {
     pub fn content(&self) -> &str {
         self.content.as_str()
     }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.annotations.as_ref()
+    }
+
+    /// The content rendered from markdown as HTML.
+    #[cfg(feature = "markdown")]
+    pub fn content_html(&self) -> String {
+        render_markdown(&self.content)
+    }
 }
 
 pub type SecuritySchemes = HashMap<String, SecurityScheme>;
 
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Eq)]
+#[derive(Hash)]
 pub enum SecuritySchemeType {
     OAuth1,
     OAuth2,
@@ -65,6 +228,29 @@ pub enum SecuritySchemeType {
     DigestAuthentication,
     PassThrough,
     XOther(String),
+    /// A scheme that only documents the nodes it adds with `describedBy`.
+    Null,
+}
+
+impl SecuritySchemeType {
+    /// The type as written in a document, e.g. `OAuth 2.0`.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            SecuritySchemeType::OAuth1 => "OAuth 1.0",
+            SecuritySchemeType::OAuth2 => "OAuth 2.0",
+            SecuritySchemeType::BasicAuthentication => "Basic Authentication",
+            SecuritySchemeType::DigestAuthentication => "Digest Authentication",
+            SecuritySchemeType::PassThrough => "Pass Through",
+            SecuritySchemeType::XOther(ref name) => name.as_str(),
+            SecuritySchemeType::Null => "null",
+        }
+    }
+}
+
+impl fmt::Display for SecuritySchemeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 impl FromStr for SecuritySchemeType {
@@ -77,48 +263,430 @@ impl FromStr for SecuritySchemeType {
             "basic authentication" => Ok(SecuritySchemeType::BasicAuthentication),
             "digest authentication" => Ok(SecuritySchemeType::DigestAuthentication),
             "pass through" => Ok(SecuritySchemeType::PassThrough),
-            s if s.starts_with("x-") => Ok(SecuritySchemeType::XOther(s.to_string())),
+            "null" | "" => Ok(SecuritySchemeType::Null),
+            lower if lower.starts_with("x-") => Ok(SecuritySchemeType::XOther(s.to_string())),
             _ => Err(get_error(ErrorDef::InvalidSecuritySchemeType, None)),
         }
     }
 }
 
-#[derive(PartialEq)]
+/// A declared security scheme. Schemes are built with `SecuritySchemeBuilder`.
 #[derive(Debug)]
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct SecurityScheme {
-    pub security_type: SecuritySchemeType,
-    pub display_name: Option<String>,
-    pub description: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    security_type: SecuritySchemeType,
+    display_name: Option<String>,
+    description: Option<String>,
+    described_by: Option<SecuritySchemeDescribedBy>,
+    settings: Option<SecuritySchemeSettings>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Option<SourceLocation>,
+}
+
+impl PartialEq for SecurityScheme {
+    fn eq(&self, other: &SecurityScheme) -> bool {
+        self.security_type == other.security_type &&
+        self.display_name == other.display_name &&
+        self.description == other.description &&
+        self.described_by == other.described_by &&
+        self.settings == other.settings
+    }
+}
+
+impl SecurityScheme {
+    pub fn security_type(&self) -> &SecuritySchemeType {
+        &self.security_type
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn described_by(&self) -> Option<&SecuritySchemeDescribedBy> {
+        self.described_by.as_ref()
+    }
+
+    pub fn settings(&self) -> Option<&SecuritySchemeSettings> {
+        self.settings.as_ref()
+    }
+
+    /// Where the scheme is declared, when it was parsed. Schemes are equal wherever they are
+    /// declared.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+
+    /// The description rendered from markdown as HTML.
+    #[cfg(feature = "markdown")]
+    pub fn description_html(&self) -> Option<String> {
+        self.description.as_ref().map(|description| render_markdown(description))
+    }
+
+    pub fn oauth1_settings(&self) -> Option<&OAuth1Settings> {
+        match self.settings {
+            Some(SecuritySchemeSettings::OAuth1(ref settings)) => Some(settings),
+            _ => None,
+        }
+    }
+
+    pub fn oauth2_settings(&self) -> Option<&OAuth2Settings> {
+        match self.settings {
+            Some(SecuritySchemeSettings::OAuth2(ref settings)) => Some(settings),
+            _ => None,
+        }
+    }
+
+    pub fn pass_through_settings(&self) -> Option<&PassThroughSettings> {
+        match self.settings {
+            Some(SecuritySchemeSettings::PassThrough(ref settings)) => Some(settings),
+            _ => None,
+        }
+    }
+}
+
+/// Builds a security scheme, e.g. to build a document with `RamlBuilder`.
+#[derive(Debug)]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct SecuritySchemeBuilder {
+    scheme: SecurityScheme,
+}
+
+impl SecuritySchemeBuilder {
+    pub fn new(security_type: SecuritySchemeType) -> SecuritySchemeBuilder {
+        SecuritySchemeBuilder {
+            scheme: SecurityScheme {
+                security_type,
+                display_name: None,
+                description: None,
+                described_by: None,
+                settings: None,
+                location: None,
+            },
+        }
+    }
+
+    pub fn display_name(mut self, display_name: &str) -> SecuritySchemeBuilder {
+        self.scheme.display_name = Some(display_name.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> SecuritySchemeBuilder {
+        self.scheme.description = Some(description.to_string());
+        self
+    }
+
+    pub fn described_by(mut self,
+                        described_by: SecuritySchemeDescribedBy)
+                        -> SecuritySchemeBuilder {
+        self.scheme.described_by = Some(described_by);
+        self
+    }
+
+    pub fn settings(mut self, settings: SecuritySchemeSettings) -> SecuritySchemeBuilder {
+        self.scheme.settings = Some(settings);
+        self
+    }
+
+    pub fn build(self) -> SecurityScheme {
+        self.scheme
+    }
+}
+
+/// The headers, query parameters and responses a security scheme adds to the methods it
+/// secures.
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SecuritySchemeDescribedBy {
+    headers: Option<NamedParameters>,
+    query_parameters: Option<NamedParameters>,
+    responses: Option<Responses>,
+}
+
+impl SecuritySchemeDescribedBy {
+    pub fn new(headers: Option<NamedParameters>,
+               query_parameters: Option<NamedParameters>,
+               responses: Option<Responses>)
+               -> SecuritySchemeDescribedBy {
+        SecuritySchemeDescribedBy {
+            headers,
+            query_parameters,
+            responses,
+        }
+    }
+
+    pub fn headers(&self) -> Option<&NamedParameters> {
+        self.headers.as_ref()
+    }
+
+    pub fn query_parameters(&self) -> Option<&NamedParameters> {
+        self.query_parameters.as_ref()
+    }
+
+    pub fn responses(&self) -> Option<&Responses> {
+        self.responses.as_ref()
+    }
 }
 
 #[derive(PartialEq)]
 #[derive(Debug)]
-pub struct SecuritySchemeDescribedBy {}
+#[derive(Clone)]
+pub enum SecuredBy {
+    /// Marks the security schemes as optional: the method may also be called without them.
+    Null,
+    Scheme(String),
+    /// A scheme given with parameters that override its settings, e.g.
+    /// `oauth_2_0: { scopes: [read] }`.
+    Parameterized(String, SecuredByParameters),
+}
+
+pub type SecuredByParameters = HashMap<String, ExampleValue>;
+
+impl SecuredBy {
+    pub fn name(&self) -> Option<&str> {
+        match *self {
+            SecuredBy::Null => None,
+            SecuredBy::Scheme(ref name) |
+            SecuredBy::Parameterized(ref name, _) => Some(name.as_str()),
+        }
+    }
+}
+
+pub type SecuredByEntries = Vec<SecuredBy>;
 
-pub type MediaTypes = Vec<String>;
+pub type MediaTypes = Vec<MediaType>;
 
+#[deprecated(note = "build the document with RamlBuilder, which doesn't break as fields are \
+                     added")]
 pub struct RamlArgs {
     pub title: String,
     pub version: Option<String>,
     pub description: Option<String>,
     pub base_uri: Option<String>,
+    pub base_uri_parameters: Option<NamedParameters>,
     pub protocols: Option<Vec<Protocol>>,
-    pub media_types: Option<Vec<String>>,
+    pub media_types: Option<MediaTypes>,
     pub documentation: Option<Vec<RamlDocumentation>>,
     pub security_schemes: Option<SecuritySchemes>,
+    pub secured_by: Option<SecuredByEntries>,
+    pub resources: Vec<Resource>,
+    pub types: Option<Types>,
+    pub traits: Option<Traits>,
+    pub resource_types: Option<ResourceTypes>,
+    pub annotation_types: Option<Types>,
+    pub annotations: Option<Annotations>,
+    pub uses: Option<Libraries>,
+}
+
+/// Builds a `Raml` document in code, e.g. as the expected value of a test. Only the title is
+/// required.
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+pub struct RamlBuilder {
+    title: Option<String>,
+    version: Option<String>,
+    description: Option<String>,
+    base_uri: Option<String>,
+    base_uri_parameters: Option<NamedParameters>,
+    protocols: Option<Protocols>,
+    media_types: Option<Vec<String>>,
+    documentation: Option<RamlDocumentationEntries>,
+    security_schemes: Option<SecuritySchemes>,
+    secured_by: Option<SecuredByEntries>,
+    resources: Resources,
+    types: Option<Types>,
+    traits: Option<Traits>,
+    resource_types: Option<ResourceTypes>,
+    annotation_types: Option<Types>,
+    annotations: Option<Annotations>,
+    uses: Option<Libraries>,
+}
+
+fn insert_entry<V>(map: &mut Option<HashMap<String, V>>, name: &str, value: V) {
+    map.get_or_insert_with(HashMap::new).insert(name.to_string(), value);
+}
+
+impl RamlBuilder {
+    pub fn new() -> RamlBuilder {
+        RamlBuilder::default()
+    }
+
+    pub fn title(mut self, title: &str) -> RamlBuilder {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn version(mut self, version: &str) -> RamlBuilder {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> RamlBuilder {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn base_uri(mut self, base_uri: &str) -> RamlBuilder {
+        self.base_uri = Some(base_uri.to_string());
+        self
+    }
+
+    pub fn base_uri_parameter(mut self, name: &str, parameter: NamedParameter) -> RamlBuilder {
+        insert_entry(&mut self.base_uri_parameters, name, parameter);
+        self
+    }
+
+    /// Adds a protocol, in the order they are added.
+    pub fn protocol(mut self, protocol: Protocol) -> RamlBuilder {
+        self.protocols.get_or_insert_with(Vec::new).push(protocol);
+        self
+    }
+
+    /// Adds a default media type, in the order they are added.
+    pub fn media_type(mut self, media_type: &str) -> RamlBuilder {
+        self.media_types.get_or_insert_with(Vec::new).push(media_type.to_string());
+        self
+    }
+
+    pub fn documentation(mut self, title: &str, content: &str) -> RamlBuilder {
+        let documentation = RamlDocumentation::new(title.to_string(), content.to_string());
+        self.documentation.get_or_insert_with(Vec::new).push(documentation);
+        self
+    }
+
+    pub fn security_scheme(mut self, name: &str, scheme: SecurityScheme) -> RamlBuilder {
+        insert_entry(&mut self.security_schemes, name, scheme);
+        self
+    }
+
+    /// Adds a security scheme that applies to every method of the API.
+    pub fn secured_by(mut self, secured_by: SecuredBy) -> RamlBuilder {
+        self.secured_by.get_or_insert_with(Vec::new).push(secured_by);
+        self
+    }
+
+    pub fn resource(mut self, resource: Resource) -> RamlBuilder {
+        self.resources.push(resource);
+        self
+    }
+
+    pub fn type_declaration(mut self, name: &str, raml_type: RamlType) -> RamlBuilder {
+        insert_entry(&mut self.types, name, raml_type);
+        self
+    }
+
+    pub fn raml_trait(mut self, name: &str, raml_trait: Trait) -> RamlBuilder {
+        insert_entry(&mut self.traits, name, raml_trait);
+        self
+    }
+
+    pub fn resource_type(mut self, name: &str, resource_type: ResourceType) -> RamlBuilder {
+        insert_entry(&mut self.resource_types, name, resource_type);
+        self
+    }
+
+    pub fn annotation_type(mut self, name: &str, annotation_type: RamlType) -> RamlBuilder {
+        insert_entry(&mut self.annotation_types, name, annotation_type);
+        self
+    }
+
+    /// Applies an annotation to the API, named without the parentheses.
+    pub fn annotation(mut self, name: &str, value: AnnotationValue) -> RamlBuilder {
+        insert_entry(&mut self.annotations, name, value);
+        self
+    }
+
+    pub fn library(mut self, name: &str, library: Library) -> RamlBuilder {
+        insert_entry(&mut self.uses, name, library);
+        self
+    }
+
+    /// The document, or a `MissingField` error when no title was given and an
+    /// `InvalidMediaType` error for a media type without the form `type/subtype`.
+    pub fn build(self) -> RamlResult {
+        let title = self.title.ok_or_else(|| {
+            get_error(ErrorDef::MissingField {
+                          field: "title".to_string(),
+                          level: HierarchyLevel::DocumentRoot,
+                      },
+                      None)
+        })?;
+        let media_types = match self.media_types {
+            Some(media_types) => {
+                Some(media_types.iter()
+                    .map(|media_type| media_type.parse())
+                    .collect::<Result<MediaTypes, RamlError>>()?)
+            }
+            None => None,
+        };
+        Ok(Raml {
+            title,
+            title_location: None,
+            version: self.version,
+            description: self.description,
+            base_uri: self.base_uri,
+            base_uri_parameters: self.base_uri_parameters,
+            protocols: self.protocols,
+            media_types,
+            documentation: self.documentation,
+            security_schemes: self.security_schemes,
+            secured_by: self.secured_by,
+            resources: self.resources,
+            types: self.types,
+            traits: self.traits,
+            resource_types: self.resource_types,
+            annotation_types: self.annotation_types,
+            annotations: self.annotations,
+            extensions: None,
+            uses: self.uses,
+            protocols_inferred: false,
+            source_path: None,
+            source_files: vec![],
+        })
+    }
 }
 
 impl Raml {
+    #[deprecated(note = "build the document with RamlBuilder")]
+    #[allow(deprecated)]
     pub fn new(args: RamlArgs) -> Raml {
         Raml {
             title: args.title,
+            title_location: None,
             version: args.version,
             description: args.description,
             base_uri: args.base_uri,
+            base_uri_parameters: args.base_uri_parameters,
             protocols: args.protocols,
             media_types: args.media_types,
             documentation: args.documentation,
             security_schemes: args.security_schemes,
+            secured_by: args.secured_by,
+            resources: args.resources,
+            types: args.types,
+            traits: args.traits,
+            resource_types: args.resource_types,
+            annotation_types: args.annotation_types,
+            annotations: args.annotations,
+            extensions: None,
+            uses: args.uses,
+            protocols_inferred: false,
+            source_path: None,
+            source_files: vec![],
         }
     }
 
@@ -126,284 +694,902 @@ impl Raml {
         self.title.as_str()
     }
 
-    pub fn version(self) -> Option<String> {
-        self.version
+    /// Where the title is declared, when the document was parsed.
+    pub fn title_location(&self) -> Option<SourceLocation> {
+        self.title_location
     }
 
-    pub fn description(self) -> Option<String> {
-        self.description
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
     }
 
-    pub fn base_uri(self) -> Option<String> {
-        self.base_uri
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
     }
 
-    pub fn protocols(self) -> Option<Protocols> {
-        self.protocols
+    /// The description rendered from markdown as HTML.
+    #[cfg(feature = "markdown")]
+    pub fn description_html(&self) -> Option<String> {
+        self.description.as_ref().map(|description| render_markdown(description))
+    }
+
+    pub fn base_uri(&self) -> Option<&str> {
+        self.base_uri.as_deref()
+    }
+
+    /// The baseUri with `{version}` replaced by the version of the API. Other uri parameters are
+    /// left as templates. A baseUri referring to `{version}` when no version is declared is a
+    /// `MissingBaseUriVersion` error.
+    pub fn base_uri_expanded(&self) -> Result<Option<String>, RamlError> {
+        let base_uri = match self.base_uri {
+            Some(ref base_uri) => base_uri,
+            None => return Ok(None),
+        };
+        if !base_uri.contains("{version}") {
+            return Ok(Some(base_uri.clone()));
+        }
+        match self.version {
+            Some(ref version) => Ok(Some(base_uri.replace("{version}", version))),
+            None => Err(get_error(ErrorDef::MissingBaseUriVersion, None)),
+        }
+    }
+
+    pub fn base_uri_parameters(&self) -> Option<&NamedParameters> {
+        self.base_uri_parameters.as_ref()
+    }
+
+    /// The protocols of the API. When the document doesn't declare them, they are the scheme of
+    /// the baseUri, as given by `protocols_inferred`.
+    pub fn protocols(&self) -> Option<&[Protocol]> {
+        self.protocols.as_deref()
+    }
+
+    /// Whether the protocols were taken from the scheme of the baseUri, rather than declared.
+    pub fn protocols_inferred(&self) -> bool {
+        self.protocols_inferred
+    }
+
+    /// The path of the file the document was loaded from, if it was loaded from a file.
+    pub fn source_path(&self) -> Option<&str> {
+        self.source_path.as_deref()
+    }
+
+    /// The files the document includes, in the order they are first included, when it was
+    /// loaded with a resolver. `SourceLocation::file` is a position in these.
+    pub fn source_files(&self) -> &[SourceFile] {
+        &self.source_files
+    }
+
+    pub fn media_types(&self) -> Option<&[MediaType]> {
+        self.media_types.as_deref()
+    }
+
+    pub fn documentation(&self) -> Option<&RamlDocumentationEntries> {
+        self.documentation.as_ref()
+    }
+
+    pub fn security_schemes(&self) -> Option<&SecuritySchemes> {
+        self.security_schemes.as_ref()
+    }
+
+    pub fn secured_by(&self) -> Option<&SecuredByEntries> {
+        self.secured_by.as_ref()
+    }
+
+    pub fn resources(&self) -> &[Resource] {
+        self.resources.as_slice()
+    }
+
+    /// Every method of every resource, depth first in document order, with the full path of its
+    /// resource. The methods of a resource are listed in the order get, post, put, delete,
+    /// patch, head, options and trace.
+    pub fn iter_operations(&self) -> impl Iterator<Item = Operation<'_>> {
+        let mut operations = vec![];
+        get_operations(&self.resources, "", self.base_uri.as_deref(), &mut operations);
+        operations.into_iter()
+    }
+
+    /// The resource a request path such as `/users/123/orders` is for, with the values of the uri
+    /// parameters in the path, e.g. `userId` is `123` for the resource `/users/{userId}/orders`.
+    pub fn resource_by_path(&self, path: &str) -> Option<(&Resource, UriParameterValues)> {
+        find_resource_by_path(&self.resources, path)
+    }
+
+    pub fn types(&self) -> Option<&Types> {
+        self.types.as_ref()
+    }
+
+    pub fn traits(&self) -> Option<&Traits> {
+        self.traits.as_ref()
+    }
+
+    pub fn resource_types(&self) -> Option<&ResourceTypes> {
+        self.resource_types.as_ref()
+    }
+
+    pub fn annotation_types(&self) -> Option<&Types> {
+        self.annotation_types.as_ref()
+    }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.annotations.as_ref()
+    }
+
+    /// The values of the root's annotations and, unless `ParseOptions::unknown_keys` rejects
+    /// them, its unknown keys, as written.
+    pub fn extra(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    pub fn uses(&self) -> Option<&Libraries> {
+        self.uses.as_ref()
+    }
+
+    /// Takes the resources of the API, for building other structures from them without
+    /// cloning.
+    pub fn into_resources(self) -> Resources {
+        self.resources
+    }
+
+    /// Takes the types declared by the API.
+    pub fn into_types(self) -> Option<Types> {
+        self.types
+    }
+
+    /// Every node the annotation `name` is applied to: the document root, the documentation,
+    /// the resources and methods, depth first in document order, then the declared types by name.
+    /// A name qualified by a library, e.g. `common.deprecated`, also matches annotations applied
+    /// with another name for the same annotation type.
+    pub fn find_annotations(&self, name: &str) -> impl Iterator<Item = AnnotatedNode<'_>> {
+        let find = |annotations| self.find_annotation(annotations, name);
+        let mut nodes = vec![];
+        if let Some(value) = find(self.annotations.as_ref()) {
+            nodes.push(AnnotatedNode::new(String::new(), "API", value));
+        }
+        for documentation in self.documentation.iter().flatten() {
+            if let Some(value) = find(documentation.annotations()) {
+                nodes.push(AnnotatedNode::new(documentation.title.clone(),
+                                              "DocumentationItem",
+                                              value));
+            }
+        }
+        get_annotated_nodes(&self.resources, "", &find, &mut nodes);
+        get_annotated_types(self.types.as_ref(), &find, &mut nodes);
+        nodes.into_iter()
     }
 
-    pub fn media_types(self) -> Option<MediaTypes> {
-        self.media_types
+    /// The value of the annotation `name` among the `annotations` of a node of this document, e.g.
+    /// `raml.find_annotation(method.annotations(), "common.deprecated")`. Like
+    /// `find_annotations`, the name matches annotations applied with another name for the same
+    /// annotation type.
+    pub fn find_annotation<'a>(&self,
+                               annotations: Option<&'a Annotations>,
+                               name: &str)
+                               -> Option<&'a AnnotationValue> {
+        find_annotation(annotations,
+                        name,
+                        self.annotation_types.as_ref(),
+                        self.uses.as_ref())
     }
 
-    pub fn documentation(self) -> Option<Vec<RamlDocumentation>> {
-        self.documentation
+    /// Passes the nodes of the document to `visitor`, depth first: the root, the documentation,
+    /// the security schemes and declared types by name, and then the resources in document
+    /// order, each followed by its methods, their bodies and responses, and its nested resources.
+    pub fn accept(&self, visitor: &mut impl RamlVisitor) {
+        visit_document(self, visitor);
     }
 
-    pub fn security_schemes(self) -> Option<SecuritySchemes> {
-        self.security_schemes
+    /// Resolves a declared type, merging in the facets and properties of the types it extends.
+    pub fn resolve_type(&self, name: &str) -> Result<ResolvedType, RamlError> {
+        resolve_type(self.types.as_ref(), self.uses.as_ref(), name)
+    }
+
+    /// The declared types, each after the types it refers to, e.g. to generate code for them.
+    /// Types that refer to each other through their properties are allowed, but types that
+    /// inherit from each other can't be ordered.
+    pub fn types_in_dependency_order(&self) -> Result<Vec<(&str, &RamlType)>, RamlError> {
+        types_in_dependency_order(self.types.as_ref())
+    }
+
+    /// The declared types `root` refers to, directly or through other types, including `root`
+    /// itself, in dependency order.
+    pub fn type_closure(&self, root: &str) -> Result<Vec<(&str, &RamlType)>, RamlError> {
+        type_closure(self.types.as_ref(), root)
+    }
+
+    /// Finds the declared type inheriting from `parent` whose discriminator value, or else name, is
+    /// `value`, e.g. to pick the type to read a polymorphic payload as.
+    pub fn find_subtype_by_discriminator(&self,
+                                         parent: &str,
+                                         value: &str)
+                                         -> Option<(&str, &RamlType)> {
+        find_subtype_by_discriminator(self.types.as_ref(), self.uses.as_ref(), parent, value)
+    }
+
+    /// Checks the API against the rules of the specification that parsing doesn't enforce, e.g.
+    /// for an API built with `RamlBuilder`.
+    pub fn validate(&self) -> Vec<RamlViolation> {
+        get_violations(self, &ValidationOptions::default())
+    }
+
+    /// Checks the API against the rules enabled by default that `options` doesn't disable, and
+    /// the rules that `options` enables.
+    pub fn validate_with_options(&self, options: &ValidationOptions) -> Vec<RamlViolation> {
+        get_violations(self, options)
+    }
+
+    /// Writes the API as a RAML 1.0 document, which parses back to an equal API. Libraries are
+    /// not written as the paths they were loaded from are not kept, so the API should not use
+    /// any. Traits and resource types are written along with the resources they were applied
+    /// to, and applying them again when the document is parsed leaves the resources unchanged,
+    /// unless a resource type itself applies traits.
+    pub fn to_raml_string(&self) -> String {
+        emit_raml(self)
+    }
+
+    /// Converts the API to an OpenAPI 3.0 document. Nodes with no OpenAPI equivalent are left
+    /// out; `to_openapi_v3_with_diagnostics` also gives a warning for each of them. Traits and
+    /// resource types are only part of the operations when the document was parsed with
+    /// `apply_traits` and `apply_resource_types`.
+    #[cfg(feature = "openapi")]
+    pub fn to_openapi_v3(&self) -> ::serde_json::Value {
+        convert_to_openapi(self).0
+    }
+
+    /// Converts the API to an OpenAPI 3.0 document, returning it with the warnings for the
+    /// nodes that could not be converted as they are.
+    #[cfg(feature = "openapi")]
+    pub fn to_openapi_v3_with_diagnostics(&self)
+                                          -> (::serde_json::Value, Vec<ConversionWarning>) {
+        convert_to_openapi(self)
+    }
+
+    /// Applies an overlay or extension to the API, giving the merged API. Nodes the overlay
+    /// declares replace the nodes of the API, and new map entries and resources are added.
+    /// Overlays may only change the descriptive nodes of the API, so any other node they declare
+    /// is an error.
+    pub fn apply_overlay(&self, overlay: &RamlOverlay) -> RamlResult {
+        let source = overlay.document();
+        let kind = overlay.kind();
+        check_overlay_nodes(kind,
+                            &[("version", source.version.is_some()),
+                              ("baseUri", source.base_uri.is_some()),
+                              ("baseUriParameters", source.base_uri_parameters.is_some()),
+                              ("protocols", source.protocols.is_some()),
+                              ("mediaType", source.media_types.is_some()),
+                              ("securitySchemes", source.security_schemes.is_some()),
+                              ("securedBy", source.secured_by.is_some()),
+                              ("traits", source.traits.is_some()),
+                              ("resourceTypes", source.resource_types.is_some())],
+                            None)?;
+        let mut raml = self.clone();
+        if !source.title.is_empty() {
+            raml.title = source.title.clone();
+            raml.title_location = source.title_location;
+        }
+        override_value(&mut raml.version, &source.version);
+        override_value(&mut raml.description, &source.description);
+        override_value(&mut raml.base_uri, &source.base_uri);
+        override_map(&mut raml.base_uri_parameters, &source.base_uri_parameters);
+        if source.protocols.is_some() {
+            raml.protocols = source.protocols.clone();
+            raml.protocols_inferred = false;
+        }
+        override_value(&mut raml.media_types, &source.media_types);
+        override_value(&mut raml.documentation, &source.documentation);
+        override_map(&mut raml.security_schemes, &source.security_schemes);
+        override_value(&mut raml.secured_by, &source.secured_by);
+        override_map(&mut raml.types, &source.types);
+        override_map(&mut raml.traits, &source.traits);
+        override_map(&mut raml.resource_types, &source.resource_types);
+        override_map(&mut raml.annotation_types, &source.annotation_types);
+        override_map(&mut raml.annotations, &source.annotations);
+        override_map(&mut raml.extensions, &source.extensions);
+        override_map(&mut raml.uses, &source.uses);
+        merge_overlay_resources(&mut raml.resources, &source.resources, kind, "")?;
+        Ok(raml)
     }
 }
 
 
-fn print_tokens(source: &str) {
+fn get_tokens(source: &str) -> Result<Vec<(SourceLocation, TokenTypeDef)>, RamlError> {
     let mut cursor = ForwardCursor::new(source);
+    let mut tokens = vec![];
     loop {
-        let token = cursor.next_token();
-        println!("Token {:?}", token.1);
-        if let TokenType::StreamEnd = token.1 {
-            break;
+        let token = cursor.next_token()?;
+        let token_type = get_token_def(&token.1);
+        let end = token_type == TokenTypeDef::StreamEnd;
+        tokens.push((get_location(token.0), token_type));
+        if end {
+            return Ok(tokens);
         }
     }
 }
 
-fn parse_raml_string(source: &str) -> RamlResult {
+fn parse_raml_string(source: &str, options: &ParseOptions) -> RamlResult {
     error_if_incorrect_raml_comment(source)?;
-    let mut cursor = ForwardCursor::new(source);
-    parse_root(&mut cursor)
+    let mut cursor = ForwardCursor::with_options(source, options.clone());
+    parse_root(&mut cursor).map_err(|error| cursor.annotate(error))
+}
+
+fn parse_raml_string_with_warnings(source: &str,
+                                   options: &ParseOptions)
+                                   -> Result<(Raml, Vec<RamlWarning>), RamlError> {
+    error_if_incorrect_raml_comment(source)?;
+    let mut cursor = ForwardCursor::with_options(source, options.clone());
+    let raml = parse_root(&mut cursor).map_err(|error| cursor.annotate(error))?;
+    let mut warnings = cursor.take_warnings();
+    warnings.extend(get_warnings(&raml));
+    Ok((raml, warnings))
+}
+
+fn parse_raml_string_with_resolver(source: &str,
+                                   options: &ParseOptions,
+                                   resolver: &dyn IncludeResolver,
+                                   path: Option<&str>)
+                                   -> RamlResult {
+    error_if_incorrect_raml_comment(source)?;
+    let mut cursor = ForwardCursor::with_resolver(source, options.clone(), resolver, path)?;
+    parse_root(&mut cursor).map_err(|error| cursor.annotate(error))
 }
 
-fn get_protocols(cursor: &mut ForwardCursor) -> Result<Protocols, RamlError> {
-    let protocols = get_multiple_values(cursor)?;
+/// Reads the value of a root node with `parse`. When the cursor collects errors, an error in the
+/// node is reported and the rest of the node is skipped, so that parsing continues with the next
+/// node, and `None` is returned.
+fn parse_root_node<T, F>(cursor: &mut ForwardCursor, parse: F) -> Result<Option<T>, RamlError>
+    where F: FnOnce(&mut ForwardCursor) -> Result<T, RamlError>
+{
+    if !cursor.collects_errors() {
+        return parse(cursor).map(Some);
+    }
+    let tokens = get_raw_value(cursor)?;
+    let mut node = cursor.replay(tokens);
+    let result = parse(&mut node);
+    cursor.extend_warnings(node.take_warnings());
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => {
+            let error = node.annotate(error);
+            cursor.report(error)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Parses a document, continuing past errors within its root nodes, and returns every error
+/// found.
+fn validate_raml_string(source: &str, options: &ParseOptions) -> Vec<RamlError> {
+    if let Err(error) = error_if_incorrect_raml_comment(source) {
+        return vec![error];
+    }
+    let mut cursor = ForwardCursor::with_options(source, options.clone());
+    cursor.collect_errors();
+    let result = parse_root(&mut cursor);
+    let mut errors = cursor.take_errors();
+    if let Err(error) = result {
+        errors.push(cursor.annotate(error));
+    }
+    errors
+}
+
+/// Reads the protocols of the node with the `protocols` key at `marker`, where an empty list is
+/// reported.
+pub fn get_protocols(cursor: &mut ForwardCursor,
+                     level: HierarchyLevel,
+                     marker: Marker)
+                     -> Result<Protocols, RamlError> {
+    let protocols = get_sequence_values(cursor)?;
     if protocols.is_empty() {
-        return Err(get_error(ErrorDef::MissingProtocols, None));
+        return Err(get_error(ErrorDef::MissingProtocols { level }, Some(marker)));
     }
-    let protocols: Result<Protocols, RamlError> = protocols.iter()
-        .map(|p| match p.value.to_lowercase().as_str() {
-            "http" => Ok(Protocol::Http),
-            "https" => Ok(Protocol::Https),
-            _ => Err(get_error(ErrorDef::UnexpectedProtocol, Some(p.marker))),
+    protocols.iter()
+        .map(|p| {
+            p.value.parse::<Protocol>().map_err(|_| {
+                get_error(ErrorDef::UnexpectedProtocol { level: level.clone() },
+                          Some(p.marker))
+            })
         })
-        .collect();
-
-    Ok(protocols?)
+        .collect()
 }
 
 fn get_media_types(cursor: &mut ForwardCursor) -> Result<MediaTypes, RamlError> {
-    let media_types = get_single_or_multiple_values(cursor)
-        ?
-        .iter()
-        .map(|e| e.value.clone())
-        .collect();
+    let mut media_types = vec![];
+    for entry in get_single_or_multiple_values(cursor)? {
+        let media_type = match entry.value.parse::<MediaType>() {
+            Ok(media_type) => media_type,
+            Err(_) => {
+                return Err(get_error(ErrorDef::InvalidMediaType { media_type: entry.value },
+                                     Some(entry.marker)))
+            }
+        };
+        if media_type.is_wildcard() {
+            cursor.warn(get_wildcard_media_type_warning(&media_type, entry.marker));
+        }
+        media_types.push(media_type);
+    }
     Ok(media_types)
 }
 
 fn get_documentation(cursor: &mut ForwardCursor) -> Result<RamlDocumentationEntries, RamlError> {
-    let documentation_result: Result<RamlDocumentationEntries, RamlError> =
-        get_multiple_sets_of_values(cursor)
-            ?
-            .iter()
-            .map(|s| {
-                let mut title: Option<String> = None;
-                let mut content: Option<String> = None;
-                for (key, entry) in s {
-                    println!("***** {}: {}", key, entry.value);
-                    if key == "title" {
-                        title = Some(entry.value.clone())
-                    } else if key == "content" {
-                        content = Some(entry.value.clone())
-                    } else {
-                        println!("unexpected key: {}", key);
-                        return Err(get_error(ErrorDef::UnexpectedKeyRoot {
-                                                 field: key.to_string(),
-                                                 level: HierarchyLevel::Documentation,
-                                             },
-                                             Some(entry.marker)));
-                    }
-                }
-                if title.is_none() {
-                    return Err(get_error(ErrorDef::MissingField {
-                                             field: "title".to_string(),
-                                             level: HierarchyLevel::Documentation,
-                                         },
-                                         None));
-                }
-                Ok(RamlDocumentation::new(title.unwrap(), content.unwrap()))
-            })
-            .collect();
-
-    Ok(documentation_result?)
+    cursor.expect(TokenTypeDef::Value)?;
+    match get_optional_sequence(cursor, get_documentation_entry)? {
+        Some(entries) => Ok(entries),
+        None => {
+            let token = cursor.next_token()?;
+            Err(get_error(ErrorDef::UnexpectedEntry {
+                              expected: TokenTypeDef::BlockSequenceStart,
+                              found: get_token_def(&token.1),
+                          },
+                          Some(token.0)))
+        }
+    }
 }
 
-fn get_security_schemes(cursor: &mut ForwardCursor) -> Result<SecuritySchemes, RamlError> {
-    let mut result: SecuritySchemes = HashMap::new();
+/// Parses a single documentation entry, as declared by a DocumentationItem fragment.
+pub fn get_documentation_item(cursor: &mut ForwardCursor) -> Result<RamlDocumentation, RamlError> {
     cursor.expect(TokenTypeDef::Value)?;
-    cursor.expect(TokenTypeDef::BlockMappingStart)?;
+    get_documentation_entry(cursor)
+}
 
-    loop {
-        let token = cursor.next_token();
-        match token.1 {
-            TokenType::Key => {
-                let token = cursor.next_token();
-                match token.1 {
-                    TokenType::Scalar(_, v) => {
-                        result.insert(v, get_security_scheme(cursor)?);
-                    }
-                    _ => {
-                        return Err(get_error(ErrorDef::UnexpectedEntry {
-                                                 expected: TokenTypeDef::Scalar,
-                                                 found: get_token_def(&token.1),
-                                             },
-                                             Some(token.0)))
-                    }
-                }
-            }
-            TokenType::BlockEnd => {
-                break;
-            }
-            _ => {
-                return Err(get_error(ErrorDef::UnexpectedEntry {
-                                         expected: TokenTypeDef::Key,
-                                         found: get_token_def(&token.1),
-                                     },
-                                     Some(token.0)))
+// Reads the mapping of a documentation entry. Errors about missing fields are at the start of
+// the mapping.
+fn get_documentation_entry(cursor: &mut ForwardCursor) -> Result<RamlDocumentation, RamlError> {
+    let mut title: Option<String> = None;
+    let mut content: Option<String> = None;
+    let mut annotations: Option<Annotations> = None;
+    let style = get_mapping_start(cursor)?;
+    let marker = cursor.peek_marker()?;
+    let location = cursor.location(marker);
+    let mut keys = HashSet::new();
+    while let Some(mut key) = get_mapping_key(cursor, &style)? {
+        // Some YAML writers leave whitespace around keys.
+        key.value = key.value.trim().to_string();
+        check_duplicate_key(&mut keys, &key.value, key.marker, HierarchyLevel::Documentation)?;
+        match key.value.as_str() {
+            "title" => title = Some(get_single_value(cursor)?),
+            "content" => content = Some(get_single_value(cursor)?),
+            name if get_annotation_name(name).is_some() => {
+                get_annotation(cursor, &mut annotations, key)?
             }
+            _ => skip_unknown_key(cursor, key, HierarchyLevel::Documentation)?,
+        }
+    }
+    match (title, content) {
+        (Some(title), Some(content)) => {
+            Ok(RamlDocumentation {
+                title,
+                content,
+                annotations,
+                location: Some(location),
+            })
+        }
+        (title, _) => {
+            let field = if title.is_none() { "title" } else { "content" };
+            Err(get_error(ErrorDef::MissingField {
+                              field: field.to_string(),
+                              level: HierarchyLevel::Documentation,
+                          },
+                          Some(marker)))
         }
     }
+}
 
+pub fn get_uri_template_variables(uri: &str) -> Vec<String> {
+    uri.split('{')
+        .skip(1)
+        .filter_map(|s| s.find('}').map(|end| s[..end].to_string()))
+        .collect()
+}
 
-    // let entries = self.get_multiple_sets_of_values()?;
+/// The protocol of a baseUri's scheme, when it is HTTP or HTTPS.
+pub fn get_base_uri_protocol(base_uri: &str) -> Option<Protocol> {
+    let (scheme, _) = base_uri.split_once("://")?;
+    scheme.parse().ok()
+}
 
-    // result.insert("oauth_2_0".to_string(),
-    //               SecurityScheme { security_type: SecuritySchemeType::OAuth2 });
+/// Checks that a baseUri is absolute, starting with a scheme or a template, and that the
+/// version it refers to with `{version}` is declared.
+fn check_base_uri(base_uri: &str,
+                  marker: Marker,
+                  version: &Option<String>)
+                  -> Result<(), RamlError> {
+    let scheme = base_uri.split(':').next().unwrap_or_default();
+    let has_scheme = base_uri.contains(':') &&
+                     scheme.starts_with(|c: char| c.is_ascii_alphabetic()) &&
+                     scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c));
+    if !has_scheme && !base_uri.starts_with('{') {
+        return Err(get_error(ErrorDef::InvalidBaseUri { uri: base_uri.to_string() },
+                             Some(marker)));
+    }
+    if version.is_none() && get_uri_template_variables(base_uri).iter().any(|v| v == "version") {
+        return Err(get_error(ErrorDef::MissingBaseUriVersion, Some(marker)));
+    }
+    Ok(())
+}
+
+fn validate_base_uri_parameters(entries: Vec<NamedParameterEntry>,
+                                base_uri: &Option<String>)
+                                -> Result<NamedParameters, RamlError> {
+    let template_variables = match *base_uri {
+        Some(ref uri) => get_uri_template_variables(uri),
+        None => vec![],
+    };
+    let mut result: NamedParameters = HashMap::new();
+    for entry in entries {
+        if entry.name == "version" {
+            return Err(get_error(ErrorDef::ReservedBaseUriParameter { name: entry.name },
+                                 Some(entry.marker)));
+        }
+        if !template_variables.contains(&entry.name) {
+            return Err(get_error(ErrorDef::UnusedBaseUriParameter { name: entry.name },
+                                 Some(entry.marker)));
+        }
+        result.insert(entry.name, entry.parameter);
+    }
+    Ok(result)
+}
 
+pub fn get_security_schemes(cursor: &mut ForwardCursor) -> Result<SecuritySchemes, RamlError> {
+    let mut result: SecuritySchemes = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        if result.contains_key(&key.value) {
+            return Err(get_error(ErrorDef::DuplicateKey {
+                                     key: key.value,
+                                     level: HierarchyLevel::SecurityScheme,
+                                 },
+                                 Some(key.marker)));
+        }
+        let security_scheme = get_security_scheme(cursor, Some(&key.value), key.marker)?;
+        result.insert(key.value, security_scheme);
+    }
     Ok(result)
 }
 
+/// Reads a `securedBy` node, returning each entry with its marker.
+pub fn get_secured_by_entries(cursor: &mut ForwardCursor,
+                              level: HierarchyLevel)
+                              -> Result<Vec<(SecuredBy, Marker)>, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    match get_optional_sequence(cursor, |cursor| get_secured_by_entry(cursor, level.clone()))? {
+        Some(entries) => Ok(entries),
+        None => Ok(vec![get_secured_by_entry(cursor, level)?]),
+    }
+}
+
+fn get_secured_by_entry(cursor: &mut ForwardCursor,
+                        level: HierarchyLevel)
+                        -> Result<(SecuredBy, Marker), RamlError> {
+    if let Some(name) = get_optional_scalar(cursor)? {
+        let secured_by = if name.value == "null" {
+            SecuredBy::Null
+        } else {
+            SecuredBy::Scheme(name.value)
+        };
+        return Ok((secured_by, name.marker));
+    }
+    let style = get_mapping_start(cursor)?;
+    let marker = cursor.peek_marker()?;
+    let name = match get_mapping_key(cursor, &style)? {
+        Some(name) => name,
+        None => {
+            return Err(get_error(ErrorDef::MissingField {
+                                     field: "name".to_string(),
+                                     level,
+                                 },
+                                 Some(marker)))
+        }
+    };
+    let mut parameters: SecuredByParameters = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let parameter_style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &parameter_style)? {
+        cursor.expect(TokenTypeDef::Value)?;
+        parameters.insert(key.value, get_example_value(cursor)?);
+    }
+    if let Some(key) = get_mapping_key(cursor, &style)? {
+        return Err(get_error(ErrorDef::UnexpectedKeyRoot {
+                                 field: key.value,
+                                 level,
+                             },
+                             Some(key.marker)));
+    }
+    Ok((SecuredBy::Parameterized(name.value, parameters), name.marker))
+}
+
+/// Checks the security scheme a `securedBy` entry applies is declared.
+pub fn check_secured_by(secured_by: &SecuredBy,
+                        marker: Option<Marker>,
+                        security_schemes: Option<&SecuritySchemes>,
+                        libraries: Option<&Libraries>,
+                        level: HierarchyLevel)
+                        -> Result<(), RamlError> {
+    let name = match secured_by.name() {
+        Some(name) => name,
+        None => return Ok(()),
+    };
+    let declared =
+        find_declaration(name, security_schemes, libraries, Library::security_schemes, marker)?
+            .is_some();
+    if declared {
+        Ok(())
+    } else {
+        Err(get_error(ErrorDef::UndeclaredSecurityScheme {
+                          name: name.to_string(),
+                          level,
+                      },
+                      marker))
+    }
+}
+
+/// The security schemes that apply to a method of the API: those the method declares, or else
+/// those of the resource declaring the method, or else those of the API.
+pub fn get_effective_secured_by<'a>(raml: &'a Raml,
+                                    method: &'a Method)
+                                    -> Option<&'a SecuredByEntries> {
+    if let Some(secured_by) = method.secured_by() {
+        return Some(secured_by);
+    }
+    match find_method_resource(&raml.resources, method) {
+        Some(resource) if resource.secured_by().is_some() => resource.secured_by(),
+        _ => raml.secured_by.as_ref(),
+    }
+}
+
+fn find_method_resource<'a>(resources: &'a [Resource], method: &Method) -> Option<&'a Resource> {
+    for resource in resources {
+        if resource.methods().values().any(|m| ptr::eq(m, method)) {
+            return Some(resource);
+        }
+        if let Some(resource) = find_method_resource(resource.resources(), method) {
+            return Some(resource);
+        }
+    }
+    None
+}
+
 fn get_security_scheme_described_by(cursor: &mut ForwardCursor)
                                     -> Result<SecuritySchemeDescribedBy, RamlError> {
+    let mut described_by = SecuritySchemeDescribedBy::default();
     cursor.expect(TokenTypeDef::Value)?;
-    cursor.expect(TokenTypeDef::BlockMappingStart)?;
-    Ok(SecuritySchemeDescribedBy {})
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "headers" => {
+                described_by.headers = Some(get_named_parameters(cursor, HierarchyLevel::Header)?)
+            }
+            "queryParameters" => {
+                described_by.query_parameters =
+                    Some(get_named_parameters(cursor, HierarchyLevel::QueryParameter)?)
+            }
+            "responses" => described_by.responses = Some(get_responses(cursor)?),
+            _ => skip_unknown_key(cursor, key, HierarchyLevel::SecurityScheme)?,
+        }
+    }
+    Ok(described_by)
 }
 
-fn get_security_scheme(cursor: &mut ForwardCursor) -> Result<SecurityScheme, RamlError> {
+/// Parses a security scheme. `name` is the name the scheme is declared with, if any, and `marker`
+/// locates it, for errors about the scheme as a whole.
+pub fn get_security_scheme(cursor: &mut ForwardCursor,
+                           name: Option<&str>,
+                           marker: Marker)
+                           -> Result<SecurityScheme, RamlError> {
     let mut security_type: Option<SecuritySchemeType> = None;
     let mut display_name: Option<String> = None;
     let mut description: Option<String> = None;
-    let mut describedBy: Option<SecuritySchemeDescribedBy> = None;
+    let mut described_by: Option<SecuritySchemeDescribedBy> = None;
+    // Settings depend on the type, which may be declared after them.
+    let mut settings_tokens: Option<Vec<Token>> = None;
+    let location = cursor.location(marker);
     cursor.expect(TokenTypeDef::Value)?;
-    cursor.expect(TokenTypeDef::BlockMappingStart)?;
-    loop {
-        let token = cursor.next_token();
-        match token.1 {
-            TokenType::Key => {
-                let token = cursor.next_token();
-                match token.1 {
-                    TokenType::Scalar(_, ref v) if v == "type" => {
-                        let security_type_str = get_single_value(cursor)?;
-                        security_type = Some(security_type_str.parse::<SecuritySchemeType>()?);
-                    }
-                    TokenType::Scalar(_, ref v) if v == "displayName" => {
-                        display_name = Some(get_single_value(cursor)?);
-                    }
-                    TokenType::Scalar(_, ref v) if v == "description" => {
-                        description = Some(get_single_value(cursor)?);
-                    }
-                    TokenType::Scalar(_, ref v) if v == "describedBy" => {
-                        describedBy = Some(get_security_scheme_described_by(cursor)?);
+    let style = get_mapping_start(cursor)?;
+    let mut keys = HashSet::new();
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        check_duplicate_key(&mut keys, &key.value, key.marker, HierarchyLevel::SecurityScheme)?;
+        match key.value.as_str() {
+            "type" => {
+                cursor.expect(TokenTypeDef::Value)?;
+                // An empty value is a null type.
+                let (security_type_str, marker) = match get_optional_scalar(cursor)? {
+                    Some(value) => (value.value, value.marker),
+                    None => (String::new(), key.marker),
+                };
+                security_type = match security_type_str.parse::<SecuritySchemeType>() {
+                    Ok(security_type) => Some(security_type),
+                    Err(_) => {
+                        return Err(get_error(ErrorDef::InvalidSecuritySchemeType, Some(marker)))
                     }
-                    TokenType::Scalar(_, v) => {
-                        return Err(get_error(ErrorDef::UnexpectedKeyRoot {
-                                                 field: v,
-                                                 level: HierarchyLevel::SecurityScheme,
-                                             },
-                                             Some(token.0)));
-                    }
-                    _ => {
-                        return Err(get_error(ErrorDef::UnexpectedEntry {
-                                                 expected: TokenTypeDef::Scalar,
-                                                 found: get_token_def(&token.1),
-                                             },
-                                             Some(token.0)))
-                    }
-                }
-            }
-            TokenType::BlockEnd => {
-                break;
-            }
-            _ => {
-                return Err(get_error(ErrorDef::UnexpectedEntry {
-                                         expected: TokenTypeDef::Key,
-                                         found: get_token_def(&token.1),
-                                     },
-                                     Some(token.0)))
+                };
             }
+            "displayName" => display_name = Some(get_single_value(cursor)?),
+            "description" => description = get_optional_value(cursor)?,
+            "describedBy" => described_by = Some(get_security_scheme_described_by(cursor)?),
+            "settings" => settings_tokens = Some(get_raw_value(cursor)?),
+            _ => skip_unknown_key(cursor, key, HierarchyLevel::SecurityScheme)?,
         }
     }
-
-    if security_type.is_none() {
-        return Err(get_error(ErrorDef::MissingField {
-                                 field: "type".to_string(),
-                                 level: HierarchyLevel::SecurityScheme,
-                             },
-                             None));
-    }
-
+    let security_type = match security_type {
+        Some(security_type) => security_type,
+        None => {
+            return Err(get_error(ErrorDef::MissingField {
+                                     field: "type".to_string(),
+                                     level: HierarchyLevel::SecurityScheme,
+                                 },
+                                 Some(marker)))
+        }
+    };
+    let settings = match settings_tokens {
+        Some(tokens) => {
+            let mut settings_cursor = cursor.nested(tokens);
+            Some(get_settings(&mut settings_cursor, &security_type, name)
+                .map_err(|error| settings_cursor.annotate(error))?)
+        }
+        None => None,
+    };
+    check_required_settings(&security_type, settings.as_ref(), marker)?;
     Ok(SecurityScheme {
-        security_type: security_type.unwrap(),
-        display_name: display_name,
-        description: description,
+        security_type,
+        display_name,
+        description,
+        described_by,
+        settings,
+        location: Some(location),
     })
 }
 
 fn parse_root(cursor: &mut ForwardCursor) -> RamlResult {
+    parse_document(cursor, false).map(|(raml, _)| raml)
+}
+
+/// Parses the root of an overlay or extension, returning it with the value of its `extends`
+/// node.
+pub fn parse_overlay_root(cursor: &mut ForwardCursor) -> Result<(Raml, String), RamlError> {
+    let (raml, extends) = parse_document(cursor, true)?;
+    // The document is only parsed as an overlay when it declares `extends`.
+    Ok((raml, extends.unwrap_or_default()))
+}
+
+fn parse_document(cursor: &mut ForwardCursor,
+                  is_overlay: bool)
+                  -> Result<(Raml, Option<String>), RamlError> {
     cursor.expect(TokenTypeDef::StreamStart)?;
-    cursor.expect(TokenTypeDef::BlockMappingStart)?;
+    get_mapping_start(cursor)?;
+    let root_marker = cursor.peek_marker()?;
     let mut title: Option<String> = None;
+    let mut title_location: Option<SourceLocation> = None;
     let mut version: Option<String> = None;
     let mut description: Option<String> = None;
-    let mut base_uri: Option<String> = None;
+    let mut base_uri: Option<ScalarValue> = None;
+    let mut base_uri_parameters: Option<Vec<NamedParameterEntry>> = None;
     let mut protocols: Option<Protocols> = None;
     let mut media_types: Option<MediaTypes> = None;
     let mut documentation: Option<RamlDocumentationEntries> = None;
     let mut security_schemes: Option<SecuritySchemes> = None;
+    let mut secured_by: Option<Vec<(SecuredBy, Marker)>> = None;
+    let mut resources: Resources = vec![];
+    let mut types: Option<Types> = None;
+    let mut traits: Option<Traits> = None;
+    let mut resource_types: Option<ResourceTypes> = None;
+    let mut annotation_types: Option<Types> = None;
+    let mut annotations: Option<Annotations> = None;
+    let mut extensions: Option<Extensions> = None;
+    let mut uses: Option<Libraries> = None;
+    let mut extends: Option<String> = None;
+    let mut keys = HashSet::new();
     loop {
-        let token = cursor.next_token();
+        let token = cursor.next_token()?;
         match token.1 {
             TokenType::Key => {
-                let token = cursor.next_token();
+                let token = cursor.next_token()?;
+                if let TokenType::Scalar(_, ref v) = token.1 {
+                    cursor.enter_key(v);
+                    let duplicate =
+                        check_duplicate_key(&mut keys, v, token.0, HierarchyLevel::DocumentRoot);
+                    cursor.check(duplicate)?;
+                }
                 match token.1 {
                     TokenType::Scalar(_, ref v) if v == "title" => {
-                        title = Some(get_single_value(cursor)?);
+                        title = match parse_root_node(cursor, get_optional_value)? {
+                            Some(Some(value)) if !value.trim().is_empty() => Some(value),
+                            Some(_) => {
+                                // An empty title is reported here, at its key, rather than as a
+                                // title missing from the root.
+                                cursor.report(get_error(ErrorDef::MissingField {
+                                                            field: "title".to_string(),
+                                                            level: HierarchyLevel::DocumentRoot,
+                                                        },
+                                                        Some(token.0)))?;
+                                Some(String::new())
+                            }
+                            None => None,
+                        };
+                        title_location = Some(get_location(token.0));
+                    }
+                    TokenType::Scalar(_, ref v) if is_overlay && v == "extends" => {
+                        extends = parse_root_node(cursor, get_single_value)?;
                     }
                     TokenType::Scalar(_, ref v) if v == "version" => {
-                        version = Some(get_single_value(cursor)?);
+                        version = parse_root_node(cursor, get_single_value)?;
                     }
                     TokenType::Scalar(_, ref v) if v == "description" => {
-                        description = Some(get_single_value(cursor)?);
+                        description =
+                            parse_root_node(cursor, get_optional_value)?.unwrap_or_default();
                     }
                     TokenType::Scalar(_, ref v) if v == "baseUri" => {
-                        base_uri = Some(get_single_value(cursor)?);
+                        base_uri = parse_root_node(cursor, get_single_scalar)?;
+                    }
+                    TokenType::Scalar(_, ref v) if v == "baseUriParameters" => {
+                        base_uri_parameters = parse_root_node(cursor, |cursor| {
+                            get_named_parameter_entries(cursor, HierarchyLevel::UriParameter)
+                        })?;
                     }
                     TokenType::Scalar(_, ref v) if v == "protocols" => {
-                        protocols = Some(get_protocols(cursor)?);
+                        protocols = parse_root_node(cursor, |cursor| {
+                            get_protocols(cursor, HierarchyLevel::DocumentRoot, token.0)
+                        })?;
                     }
                     TokenType::Scalar(_, ref v) if v == "mediaType" => {
-                        media_types = Some(get_media_types(cursor)?);
+                        media_types = parse_root_node(cursor, get_media_types)?;
                     }
                     TokenType::Scalar(_, ref v) if v == "documentation" => {
-                        documentation = Some(get_documentation(cursor)?);
+                        documentation = parse_root_node(cursor, get_documentation)?;
                     }
                     TokenType::Scalar(_, ref v) if v == "securitySchemes" => {
-                        security_schemes = Some(get_security_schemes(cursor)?);
+                        security_schemes = parse_root_node(cursor, get_security_schemes)?;
                     }
-                    TokenType::Scalar(_, v) => {
-                        return Err(get_error(ErrorDef::UnexpectedKeyRoot {
-                                                 field: v,
-                                                 level: HierarchyLevel::DocumentRoot,
-                                             },
-                                             Some(token.0)));
+                    TokenType::Scalar(_, ref v) if v == "securedBy" => {
+                        secured_by = parse_root_node(cursor, |cursor| {
+                            get_secured_by_entries(cursor, HierarchyLevel::DocumentRoot)
+                        })?;
+                    }
+                    TokenType::Scalar(_, ref v) if v == "types" => {
+                        types = parse_root_node(cursor, get_types)?;
+                    }
+                    TokenType::Scalar(_, ref v) if v == "traits" => {
+                        traits = parse_root_node(cursor, get_traits)?;
+                    }
+                    TokenType::Scalar(_, ref v) if v == "resourceTypes" => {
+                        resource_types = parse_root_node(cursor, get_resource_types)?;
+                    }
+                    TokenType::Scalar(_, ref v) if v == "uses" => {
+                        uses = parse_root_node(cursor, get_uses)?;
+                    }
+                    TokenType::Scalar(_, ref v) if v == "annotationTypes" => {
+                        annotation_types = parse_root_node(cursor, get_annotation_types)?;
+                    }
+                    TokenType::Scalar(_, v) if v.starts_with('/') => {
+                        let marker = token.0;
+                        let resource =
+                            parse_root_node(cursor, |cursor| get_resource(cursor, v, marker))?;
+                        resources.extend(resource);
+                    }
+                    TokenType::Scalar(style, v) if get_annotation_name(&v).is_some() => {
+                        let key = MappingKey {
+                            value: v,
+                            marker: token.0,
+                            style,
+                        };
+                        parse_root_node(cursor, |cursor| {
+                                            get_annotation_and_node(cursor,
+                                                                    &mut annotations,
+                                                                    &mut extensions,
+                                                                    key)
+                                        })?;
+                    }
+                    TokenType::Scalar(style, v) => {
+                        let key = MappingKey {
+                            value: v,
+                            marker: token.0,
+                            style,
+                        };
+                        parse_root_node(cursor, |cursor| {
+                                            keep_unknown_key(cursor,
+                                                             key,
+                                                             HierarchyLevel::DocumentRoot,
+                                                             &mut extensions)
+                                        })?;
                     }
                     _ => {
                         return Err(get_error(ErrorDef::UnexpectedEntry {
@@ -413,17 +1599,27 @@ fn parse_root(cursor: &mut ForwardCursor) -> RamlResult {
                                              Some(token.0)))
                     }
                 }
-            } 
-            TokenType::BlockEnd => {
-                if title.is_none() {
-                    return Err(get_error(ErrorDef::MissingField {
-                                             field: "title".to_string(),
-                                             level: HierarchyLevel::DocumentRoot,
-                                         },
-                                         None));
+            }
+            // Separates the keys of a root written as a flow mapping.
+            TokenType::FlowEntry => {}
+            TokenType::BlockEnd |
+            TokenType::FlowMappingEnd => {
+                cursor.leave_mapping();
+                let missing = if title.is_none() && !is_overlay {
+                    Some("title")
+                } else if extends.is_none() && is_overlay {
+                    Some("extends")
                 } else {
-                    break;
+                    None
+                };
+                if let Some(field) = missing {
+                    cursor.report(get_error(ErrorDef::MissingField {
+                                                field: field.to_string(),
+                                                level: HierarchyLevel::DocumentRoot,
+                                            },
+                                            Some(root_marker)))?;
                 }
+                break;
             }
             _ => {
                 return Err(get_error(ErrorDef::UnexpectedEntry {
@@ -434,34 +1630,214 @@ fn parse_root(cursor: &mut ForwardCursor) -> RamlResult {
             }
         }
     }
-    Ok(Raml::new(RamlArgs {
-        title: title.unwrap(),
-        version: version,
-        description: description,
-        base_uri: base_uri,
-        protocols: protocols,
-        media_types: media_types,
-        documentation: documentation,
-        security_schemes: security_schemes,
-    }))
+    let base_uri_marker = base_uri.as_ref().map(|scalar| scalar.marker);
+    let base_uri = base_uri.map(|scalar| scalar.value);
+    let base_uri_parameters = match base_uri_parameters {
+        Some(entries) if is_overlay => {
+            Some(entries.into_iter().map(|entry| (entry.name, entry.parameter)).collect())
+        }
+        Some(entries) => Some(validate_base_uri_parameters(entries, &base_uri)?),
+        None => None,
+    };
+    let mut protocols_inferred = false;
+    if !is_overlay {
+        if let (Some(uri), Some(marker)) = (base_uri.as_ref(), base_uri_marker) {
+            cursor.check(check_base_uri(uri, marker, &version))?;
+            if protocols.is_none() {
+                protocols = get_base_uri_protocol(uri).map(|protocol| vec![protocol]);
+                protocols_inferred = protocols.is_some();
+            }
+        }
+        let options = cursor.options().clone();
+        let applied = apply_resource_types(&mut resources,
+                                           resource_types.as_ref(),
+                                           uses.as_ref(),
+                                           options.apply_resource_types,
+                                           "");
+        cursor.check(applied)?;
+        let applied = apply_resource_traits(&mut resources,
+                                            traits.as_ref(),
+                                            uses.as_ref(),
+                                            options.apply_traits,
+                                            "");
+        cursor.check(applied)?;
+        cursor.check(expand_resource_bodies(&mut resources, media_types.as_ref()))?;
+        cursor.check(check_annotations(annotations.as_ref(),
+                                       annotation_types.as_ref(),
+                                       uses.as_ref(),
+                                       "API"))?;
+        for entry in documentation.iter().flatten() {
+            cursor.check(check_annotations(entry.annotations(),
+                                           annotation_types.as_ref(),
+                                           uses.as_ref(),
+                                           "DocumentationItem"))?;
+        }
+        cursor.check(check_resource_annotations(&resources,
+                                                annotation_types.as_ref(),
+                                                uses.as_ref()))?;
+        cursor.check(check_type_annotations(types.as_ref(),
+                                            annotation_types.as_ref(),
+                                            uses.as_ref()))?;
+        cursor.check(check_parent_types(types.as_ref(), uses.as_ref()))?;
+        cursor.check(check_circular_inheritance(types.as_ref()))?;
+        cursor.check(check_type_facets(types.as_ref(), uses.as_ref()))?;
+        for &(ref entry, marker) in secured_by.iter().flatten() {
+            cursor.check(check_secured_by(entry,
+                                          Some(marker),
+                                          security_schemes.as_ref(),
+                                          uses.as_ref(),
+                                          HierarchyLevel::DocumentRoot))?;
+        }
+        cursor.check(check_resource_secured_by(&resources,
+                                               security_schemes.as_ref(),
+                                               uses.as_ref()))?;
+    }
+    let secured_by =
+        secured_by.map(|entries| entries.into_iter().map(|(entry, _)| entry).collect());
+    let raml = Raml {
+        title: title.unwrap_or_default(),
+        title_location,
+        version,
+        description,
+        base_uri,
+        base_uri_parameters,
+        protocols,
+        media_types,
+        documentation,
+        security_schemes,
+        secured_by,
+        resources,
+        types,
+        traits,
+        resource_types,
+        annotation_types,
+        annotations,
+        extensions,
+        uses,
+        protocols_inferred,
+        source_path: cursor.path().map(|path| path.to_string()),
+        source_files: cursor.source_files().to_vec(),
+    };
+    Ok((raml, extends))
 }
 
 fn error_if_incorrect_raml_comment(s: &str) -> Result<(), RamlError> {
-    let first_line: &str = s.lines().next().unwrap_or_default().trim();
-    if first_line != "#%RAML 1.0" {
-        return Err(get_error(ErrorDef::MissingRamlVersion, None));
+    if let Some(kind) = get_fragment_kind(s) {
+        return Err(get_error(ErrorDef::FragmentNotApiDocument { kind: kind.to_string() }, None));
+    }
+    let first_line = get_header(s);
+    if first_line == "#%RAML 1.0" {
+        return Ok(());
+    }
+    // A comment line declaring another version, such as `#%RAML 0.8`.
+    match first_line.strip_prefix("#%RAML ").map(str::trim) {
+        Some(version) if !version.is_empty() && !version.contains(char::is_whitespace) => {
+            Err(get_error(ErrorDef::UnsupportedRamlVersion { version: version.to_string() },
+                          None))
+        }
+        _ => Err(get_error(ErrorDef::MissingRamlVersion, None)),
     }
-    Ok(())
 }
 
 pub struct RamlParser {}
 
 impl RamlParser {
-    pub fn debug(source: &str) {
-        print_tokens(source);
+    /// The YAML tokens of a source with where they start, up to the end of the stream, for
+    /// finding out how a document is read.
+    pub fn debug(source: &str) -> Result<Vec<(SourceLocation, TokenTypeDef)>, RamlError> {
+        get_tokens(source)
     }
 
     pub fn load_from_str(source: &str) -> RamlResult {
-        parse_raml_string(source)
+        parse_raml_string(source, &ParseOptions::default())
+    }
+
+    pub fn load_from_str_with_options(source: &str, options: &ParseOptions) -> RamlResult {
+        parse_raml_string(source, options)
+    }
+
+    /// Loads a document read from `reader`, such as the body of an upload. The document is read
+    /// to the end before it is parsed, and must be UTF-8.
+    pub fn load_from_reader<R: Read>(reader: R) -> RamlResult {
+        RamlParser::load_from_reader_with_options(reader, &ParseOptions::default())
+    }
+
+    /// Loads a document read from `reader` with `options`. Reading stops once the document is
+    /// over `options.max_source_bytes`, which fails the load.
+    pub fn load_from_reader_with_options<R: Read>(reader: R,
+                                                  options: &ParseOptions)
+                                                  -> RamlResult {
+        parse_raml_string(&read_source(reader, options.max_source_bytes)?, options)
+    }
+
+    /// Checks a document, returning every error found rather than only the first, in the order
+    /// they appear. An error within a root node, such as `protocols` or a resource, skips the
+    /// rest of that node. The document is valid when no errors are returned.
+    pub fn validate(source: &str) -> Vec<RamlError> {
+        validate_raml_string(source, &ParseOptions::default())
+    }
+
+    /// Loads a document, returning it with the warnings found in it. Warnings point out nodes
+    /// that are valid but probably mistaken, and don't stop the document from loading.
+    pub fn load_from_str_with_diagnostics(source: &str)
+                                          -> Result<(Raml, Vec<RamlWarning>), RamlError> {
+        parse_raml_string_with_warnings(source, &ParseOptions::default())
+    }
+
+    /// Loads a document with `options`, returning it with the warnings found in it. These
+    /// include the unknown keys skipped when `options.unknown_keys` is
+    /// `UnknownKeyBehaviour::Warn`, which come first.
+    pub fn load_from_str_with_options_and_diagnostics
+        (source: &str,
+         options: &ParseOptions)
+         -> Result<(Raml, Vec<RamlWarning>), RamlError> {
+        parse_raml_string_with_warnings(source, options)
+    }
+
+    /// Loads a document whose libraries and included files are loaded with `resolver`.
+    pub fn load_from_str_with_resolver(source: &str,
+                                       resolver: &dyn IncludeResolver)
+                                       -> RamlResult {
+        parse_raml_string_with_resolver(source, &ParseOptions::default(), resolver, None)
+    }
+
+    /// Loads a document with `options`, whose libraries and included files are loaded with
+    /// `resolver`, e.g. to limit what an uploaded document may include.
+    pub fn load_from_str_with_resolver_and_options(source: &str,
+                                                   resolver: &dyn IncludeResolver,
+                                                   options: &ParseOptions)
+                                                   -> RamlResult {
+        parse_raml_string_with_resolver(source, options, resolver, None)
+    }
+
+    /// Loads a document from a file. Files it includes are loaded relative to the file that
+    /// includes them.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> RamlResult {
+        RamlParser::load_from_file_with_options(path, &ParseOptions::default())
+    }
+
+    /// Loads a document from a file with `options`. No file is read further than
+    /// `options.max_source_bytes` allows.
+    pub fn load_from_file_with_options<P: AsRef<Path>>(path: P,
+                                                       options: &ParseOptions)
+                                                       -> RamlResult {
+        let path = path.as_ref().to_string_lossy().into_owned();
+        let source = FileResolver.resolve_with_limit(&path, options.max_source_bytes)?;
+        parse_raml_string_with_resolver(&source, options, &FileResolver, Some(&path))
+    }
+
+    /// Loads a fragment document, e.g. one starting with `#%RAML 1.0 DataType`.
+    pub fn load_fragment_from_str(source: &str) -> Result<RamlFragment, RamlError> {
+        parse_fragment(source, &ParseOptions::default())
+    }
+
+    pub fn load_library_from_str(source: &str) -> Result<Library, RamlError> {
+        parse_library(source, &ParseOptions::default(), None, None)
+    }
+
+    /// Loads an overlay or extension, to be applied to the API it extends with
+    /// `Raml::apply_overlay`.
+    pub fn load_overlay_from_str(source: &str) -> Result<RamlOverlay, RamlError> {
+        parse_overlay(source, &ParseOptions::default(), None, None)
     }
 }
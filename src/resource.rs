@@ -0,0 +1,677 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use yaml::*;
+use method::{apply_method_traits, expand_method_bodies, get_method, is_method_name, merge_method,
+             merge_overlay_method, Method, Methods, METHOD_NAMES};
+use std::collections::{HashMap, HashSet};
+use std::ptr;
+use parameter::{get_named_parameters, NamedParameter, NamedParameters};
+use parser::{check_secured_by, get_secured_by_entries, get_uri_template_variables, MediaTypes,
+             Raml, SecuredByEntries, SecuritySchemes};
+use traits::{get_referenced_traits, get_trait_references, TraitReferences, Traits};
+use resource_type::{apply_resource_type, get_referenced_resource_types,
+                    get_resource_type_reference, ResourceTypeReference, ResourceTypes};
+use template::TemplateContext;
+use annotation::{check_annotations, get_annotation_and_node, get_annotation_name, AnnotatedNode,
+                 AnnotationValue, Annotations};
+use types::Types;
+use library::Libraries;
+use overlay::{check_overlay_nodes, override_map, override_value, OverlayKind};
+use location::SourceLocation;
+
+pub type Resources = Vec<Resource>;
+
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Resource {
+    relative_uri: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    resources: Resources,
+    methods: Methods,
+    uri_parameters: Option<NamedParameters>,
+    is: Option<TraitReferences>,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    resource_type: Option<ResourceTypeReference>,
+    annotations: Option<Annotations>,
+    extensions: Option<Extensions>,
+    secured_by: Option<SecuredByEntries>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Option<SourceLocation>,
+}
+
+// Resources are equal when they have the same nodes, wherever they are declared. The extensions
+// are left out, as their nodes keep the positions they were read from.
+impl PartialEq for Resource {
+    fn eq(&self, other: &Resource) -> bool {
+        self.relative_uri == other.relative_uri &&
+        self.display_name == other.display_name &&
+        self.description == other.description &&
+        self.resources == other.resources &&
+        self.methods == other.methods &&
+        self.uri_parameters == other.uri_parameters &&
+        self.is == other.is &&
+        self.resource_type == other.resource_type &&
+        self.annotations == other.annotations &&
+        self.secured_by == other.secured_by
+    }
+}
+
+impl Resource {
+    pub fn relative_uri(&self) -> &str {
+        self.relative_uri.as_str()
+    }
+
+    /// The uri of the resource in `raml`: the baseUri of the API, with `{version}` replaced as
+    /// by `Raml::base_uri_expanded`, followed by the relative uris of the resources it is nested
+    /// in and its own, e.g. `https://api.example.com/v1/users/{userId}`. Other uri parameters are
+    /// left as templates, and repeated slashes are removed. Without a baseUri this is the full
+    /// path of the resource. A resource that isn't one of `raml`'s is taken to be top-level.
+    pub fn absolute_uri(&self, raml: &Raml) -> Result<String, RamlError> {
+        let path = self.resource_chain(raml)
+            .iter()
+            .fold(String::new(), |path, resource| join_uri(&path, &resource.relative_uri));
+        let uri = match raml.base_uri_expanded()? {
+            Some(base_uri) => join_uri(&base_uri, &path),
+            None => path,
+        };
+        Ok(remove_repeated_slashes(&uri))
+    }
+
+    /// The resources of `raml` this resource is nested in, outermost first, followed by the
+    /// resource itself. A resource that isn't one of `raml`'s is taken to be top-level.
+    pub fn resource_chain<'a>(&'a self, raml: &'a Raml) -> Vec<&'a Resource> {
+        find_resource_chain(raml.resources(), self).unwrap_or_else(|| vec![self])
+    }
+
+    /// The uri parameters of the resource and the resources it is nested in, the closest
+    /// declaration of each winning. The template variables of their uris without a declaration
+    /// are given implicit string parameters.
+    pub fn inherited_uri_parameters(&self, raml: &Raml) -> NamedParameters {
+        get_inherited_uri_parameters(&self.resource_chain(raml))
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// The display name, or else the key of the resource, its relative uri.
+    pub fn display_name_or_key(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.relative_uri)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Where the resource is declared, when it was parsed. A resource added by an overlay is
+    /// located in the overlay.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+
+    pub fn resources(&self) -> &[Resource] {
+        self.resources.as_slice()
+    }
+
+    pub fn methods(&self) -> &Methods {
+        &self.methods
+    }
+
+    pub fn uri_parameters(&self) -> Option<&NamedParameters> {
+        self.uri_parameters.as_ref()
+    }
+
+    pub fn is(&self) -> Option<&TraitReferences> {
+        self.is.as_ref()
+    }
+
+    pub fn resource_type(&self) -> Option<&ResourceTypeReference> {
+        self.resource_type.as_ref()
+    }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.annotations.as_ref()
+    }
+
+    /// The values of the resource's annotations and, unless `ParseOptions::unknown_keys` rejects
+    /// them, its unknown keys, as written.
+    pub fn extra(&self) -> Option<&Extensions> {
+        self.extensions.as_ref()
+    }
+
+    pub fn secured_by(&self) -> Option<&SecuredByEntries> {
+        self.secured_by.as_ref()
+    }
+}
+
+pub fn get_resource(cursor: &mut ForwardCursor,
+                    relative_uri: String,
+                    marker: Marker)
+                    -> Result<Resource, RamlError> {
+    let mut resource = Resource {
+        relative_uri,
+        location: Some(cursor.location(marker)),
+        ..Resource::default()
+    };
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        if key.value.starts_with('/') {
+            let nested = get_resource(cursor, key.value, key.marker)?;
+            resource.resources.push(nested);
+        } else {
+            get_resource_facet(cursor, &mut resource, key, HierarchyLevel::Resource)?;
+        }
+    }
+
+    if cursor.options().validate_uri_parameters {
+        validate_uri_parameters(&resource.relative_uri, &resource.uri_parameters, marker)?;
+    }
+
+    Ok(resource)
+}
+
+/// Parses one of the nodes a resource can have, other than nested resources. Resource types
+/// share these nodes, so the hierarchy level used for errors is given by the caller.
+pub fn get_resource_facet(cursor: &mut ForwardCursor,
+                          resource: &mut Resource,
+                          key: MappingKey,
+                          level: HierarchyLevel)
+                          -> Result<(), RamlError> {
+    match key.value.as_str() {
+        "displayName" => resource.display_name = Some(get_single_value(cursor)?),
+        "description" => resource.description = get_optional_value(cursor)?,
+        "uriParameters" => {
+            resource.uri_parameters =
+                Some(get_named_parameters(cursor, HierarchyLevel::UriParameter)?)
+        }
+        "is" => resource.is = Some(get_trait_references(cursor, level)?),
+        "type" => resource.resource_type = Some(get_resource_type_reference(cursor, level)?),
+        "securedBy" => {
+            let entries = get_secured_by_entries(cursor, level)?;
+            resource.secured_by = Some(entries.into_iter().map(|(entry, _)| entry).collect());
+        }
+        name if is_method_name(name) => {
+            let method = get_method(cursor, key.marker)?;
+            resource.methods.insert(key.value, method);
+        }
+        name if get_annotation_name(name).is_some() => {
+            get_annotation_and_node(cursor,
+                                    &mut resource.annotations,
+                                    &mut resource.extensions,
+                                    key)?
+        }
+        _ => keep_unknown_key(cursor, key, level, &mut resource.extensions)?,
+    }
+    Ok(())
+}
+
+/// Checks the template variables of a resource's own relative uri against its declared
+/// `uriParameters`, in both directions.
+fn validate_uri_parameters(relative_uri: &str,
+                           uri_parameters: &Option<NamedParameters>,
+                           marker: Marker)
+                           -> Result<(), RamlError> {
+    let template_variables = get_uri_template_variables(relative_uri);
+    for variable in &template_variables {
+        let declared = match *uri_parameters {
+            Some(ref parameters) => parameters.contains_key(variable),
+            None => false,
+        };
+        if !declared {
+            return Err(get_error(ErrorDef::UndeclaredUriParameter { name: variable.clone() },
+                                 Some(marker)));
+        }
+    }
+    if let Some(ref parameters) = *uri_parameters {
+        let mut names: Vec<&String> = parameters.keys().collect();
+        names.sort();
+        for name in names {
+            if !template_variables.contains(name) {
+                return Err(get_error(ErrorDef::UnusedUriParameter { name: name.clone() },
+                                     Some(marker)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Expands bodies declared without media types throughout the resource tree, using the media
+/// types declared at the document root.
+pub fn expand_resource_bodies(resources: &mut Resources,
+                              media_types: Option<&MediaTypes>)
+                              -> Result<(), RamlError> {
+    for resource in resources.iter_mut() {
+        for method in resource.methods.values_mut() {
+            expand_method_bodies(method, media_types)?;
+        }
+        expand_resource_bodies(&mut resource.resources, media_types)?;
+    }
+    Ok(())
+}
+
+/// Checks the traits referenced throughout the resource tree are declared, merging them into the
+/// methods that use them when `merge` is set. `parent_path` is the path of the enclosing
+/// resource, used for the `resourcePath` parameter.
+pub fn apply_resource_traits(resources: &mut Resources,
+                             traits: Option<&Traits>,
+                             libraries: Option<&Libraries>,
+                             merge: bool,
+                             parent_path: &str)
+                             -> Result<(), RamlError> {
+    for resource in resources.iter_mut() {
+        let path = format!("{}{}", parent_path, resource.relative_uri);
+        if resource.methods.is_empty() {
+            // Still check the traits are declared, though there is nothing to apply them to.
+            get_referenced_traits(resource.is.as_ref(),
+                                  traits,
+                                  libraries,
+                                  HierarchyLevel::Resource,
+                                  &TemplateContext::new(&path, None))?;
+        }
+        for (name, method) in resource.methods.iter_mut() {
+            // Traits are instantiated for each method, as they may use the method name.
+            let context = TemplateContext::new(&path, Some(name));
+            let resource_traits = get_referenced_traits(resource.is.as_ref(),
+                                                        traits,
+                                                        libraries,
+                                                        HierarchyLevel::Resource,
+                                                        &context)?;
+            apply_method_traits(method, &resource_traits, traits, libraries, merge, &context)?;
+        }
+        apply_resource_traits(&mut resource.resources, traits, libraries, merge, &path)?;
+    }
+    Ok(())
+}
+
+/// Checks the resource types referenced throughout the resource tree are declared, merging them
+/// into the resources that use them when `merge` is set. `parent_path` is the path of the
+/// enclosing resource, used for the `resourcePath` parameter.
+pub fn apply_resource_types(resources: &mut Resources,
+                            resource_types: Option<&ResourceTypes>,
+                            libraries: Option<&Libraries>,
+                            merge: bool,
+                            parent_path: &str)
+                            -> Result<(), RamlError> {
+    for resource in resources.iter_mut() {
+        let path = format!("{}{}", parent_path, resource.relative_uri);
+        let referenced = get_referenced_resource_types(resource.resource_type.as_ref(),
+                                                       resource_types,
+                                                       libraries,
+                                                       &TemplateContext::new(&path, None))?;
+        if merge {
+            for resource_type in &referenced {
+                apply_resource_type(resource, resource_type);
+            }
+        }
+        apply_resource_types(&mut resource.resources, resource_types, libraries, merge, &path)?;
+    }
+    Ok(())
+}
+
+/// Checks the annotations applied throughout the resource tree, on resources and methods.
+pub fn check_resource_annotations(resources: &Resources,
+                                  annotation_types: Option<&Types>,
+                                  libraries: Option<&Libraries>)
+                                  -> Result<(), RamlError> {
+    for resource in resources {
+        check_annotations(resource.annotations.as_ref(),
+                          annotation_types,
+                          libraries,
+                          "Resource")?;
+        for method in resource.methods.values() {
+            check_annotations(method.annotations(), annotation_types, libraries, "Method")?;
+        }
+        check_resource_annotations(&resource.resources, annotation_types, libraries)?;
+    }
+    Ok(())
+}
+
+/// Checks the security schemes applied by resources and their methods are declared.
+pub fn check_resource_secured_by(resources: &Resources,
+                                 security_schemes: Option<&SecuritySchemes>,
+                                 libraries: Option<&Libraries>)
+                                 -> Result<(), RamlError> {
+    for resource in resources {
+        for entry in resource.secured_by.iter().flatten() {
+            check_secured_by(entry, None, security_schemes, libraries, HierarchyLevel::Resource)?;
+        }
+        for method in resource.methods.values() {
+            for entry in method.secured_by().into_iter().flatten() {
+                check_secured_by(entry, None, security_schemes, libraries, HierarchyLevel::Method)?;
+            }
+        }
+        check_resource_secured_by(&resource.resources, security_schemes, libraries)?;
+    }
+    Ok(())
+}
+
+/// Merges a resource type into a resource. Nodes the resource already declares are kept, and
+/// optional methods are only merged into methods the resource declares.
+pub fn merge_resource(resource: &mut Resource,
+                      source: &Resource,
+                      optional_methods: &HashSet<String>) {
+    if resource.display_name.is_none() {
+        resource.display_name = source.display_name.clone();
+    }
+    if resource.description.is_none() {
+        resource.description = source.description.clone();
+    }
+    if let Some(ref source_parameters) = source.uri_parameters {
+        let parameters = resource.uri_parameters.get_or_insert_with(HashMap::new);
+        for (name, parameter) in source_parameters {
+            parameters.entry(name.clone()).or_insert_with(|| parameter.clone());
+        }
+    }
+    if let Some(ref source_annotations) = source.annotations {
+        let annotations = resource.annotations.get_or_insert_with(HashMap::new);
+        for (name, annotation) in source_annotations {
+            annotations.entry(name.clone()).or_insert_with(|| annotation.clone());
+        }
+    }
+    if let Some(ref source_extensions) = source.extensions {
+        let extensions = resource.extensions.get_or_insert_with(HashMap::new);
+        for (key, node) in source_extensions {
+            extensions.entry(key.clone()).or_insert_with(|| node.clone());
+        }
+    }
+    if resource.secured_by.is_none() {
+        resource.secured_by = source.secured_by.clone();
+    }
+    if let Some(ref source_is) = source.is {
+        let is = resource.is.get_or_insert_with(Vec::new);
+        is.extend(source_is.iter().cloned());
+    }
+    for (name, source_method) in &source.methods {
+        match resource.methods.get_mut(name) {
+            Some(method) => merge_method(method, source_method),
+            None if optional_methods.contains(name) => {}
+            None => {
+                resource.methods.insert(name.clone(), source_method.clone());
+            }
+        }
+    }
+}
+
+/// Merges the resources of an overlay or extension into the resources of the API it extends,
+/// matching them by their relative uri.
+pub fn merge_overlay_resources(resources: &mut Resources,
+                               source: &[Resource],
+                               kind: &OverlayKind,
+                               parent_path: &str)
+                               -> Result<(), RamlError> {
+    for source_resource in source {
+        let path = format!("{}{}", parent_path, source_resource.relative_uri);
+        match resources.iter_mut().find(|r| r.relative_uri == source_resource.relative_uri) {
+            Some(resource) => merge_overlay_resource(resource, source_resource, kind, &path)?,
+            None => {
+                check_overlay_nodes(kind, &[(&format!("resource {}", path), true)], None)?;
+                resources.push(source_resource.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn merge_overlay_resource(resource: &mut Resource,
+                          source: &Resource,
+                          kind: &OverlayKind,
+                          path: &str)
+                          -> Result<(), RamlError> {
+    let location = format!("resource {}", path);
+    check_overlay_nodes(kind,
+                        &[("uriParameters", source.uri_parameters.is_some()),
+                          ("is", source.is.is_some()),
+                          ("type", source.resource_type.is_some()),
+                          ("securedBy", source.secured_by.is_some())],
+                        Some(&location))?;
+    override_value(&mut resource.display_name, &source.display_name);
+    override_value(&mut resource.description, &source.description);
+    override_map(&mut resource.uri_parameters, &source.uri_parameters);
+    override_map(&mut resource.annotations, &source.annotations);
+    override_map(&mut resource.extensions, &source.extensions);
+    override_value(&mut resource.resource_type, &source.resource_type);
+    override_value(&mut resource.secured_by, &source.secured_by);
+    if let Some(ref source_is) = source.is {
+        let is = resource.is.get_or_insert_with(Vec::new);
+        is.extend(source_is.iter().cloned());
+    }
+    for (name, source_method) in &source.methods {
+        let location = format!("{} {}", name, path);
+        match resource.methods.get_mut(name) {
+            Some(method) => merge_overlay_method(method, source_method, kind, &location)?,
+            None => {
+                check_overlay_nodes(kind, &[(&format!("method {}", location), true)], None)?;
+                resource.methods.insert(name.clone(), source_method.clone());
+            }
+        }
+    }
+    merge_overlay_resources(&mut resource.resources, &source.resources, kind, path)
+}
+
+/// The values of the uri parameters of a path matched by `find_resource_by_path`.
+pub type UriParameterValues = HashMap<String, String>;
+
+/// Finds the resource whose full uri matches `path`, e.g. `/users/123/orders`, returning it with
+/// the values its uri parameters take in the path. At each level, resources whose segments are
+/// literal are tried before those with uri parameters. A trailing slash is ignored.
+pub fn find_resource_by_path<'a>(resources: &'a [Resource],
+                                 path: &str)
+                                 -> Option<(&'a Resource, UriParameterValues)> {
+    let mut values = HashMap::new();
+    find_resource(resources, &get_path_segments(path), &mut values).map(|found| (found, values))
+}
+
+fn get_path_segments(uri: &str) -> Vec<&str> {
+    uri.split('/').filter(|segment| !segment.is_empty()).collect()
+}
+
+fn find_resource<'a>(resources: &'a [Resource],
+                     path: &[&str],
+                     values: &mut UriParameterValues)
+                     -> Option<&'a Resource> {
+    let mut candidates: Vec<(&Resource, Vec<&str>)> = resources.iter()
+        .map(|resource| (resource, get_path_segments(&resource.relative_uri)))
+        .collect();
+    candidates.sort_by_key(|(_, segments)| {
+        segments.iter().map(|segment| segment.contains('{')).collect::<Vec<bool>>()
+    });
+    for (resource, segments) in candidates {
+        if segments.len() > path.len() {
+            continue;
+        }
+        let mut found = HashMap::new();
+        if !segments.iter().zip(path).all(|(pattern, segment)| {
+            match_segment(pattern, segment, &mut found)
+        }) {
+            continue;
+        }
+        let rest = &path[segments.len()..];
+        let matched = if rest.is_empty() {
+            Some(resource)
+        } else {
+            find_resource(&resource.resources, rest, &mut found)
+        };
+        if matched.is_some() {
+            values.extend(found);
+            return matched;
+        }
+    }
+    None
+}
+
+// Matches a segment of a path against a segment of a relative uri, in which each uri parameter
+// matches the text up to the literal text that follows it.
+fn match_segment(pattern: &str, segment: &str, values: &mut UriParameterValues) -> bool {
+    let start = match pattern.find('{') {
+        Some(start) => start,
+        None => return pattern == segment,
+    };
+    let end = match pattern[start..].find('}') {
+        Some(end) => start + end,
+        None => return pattern == segment,
+    };
+    let (prefix, name, suffix) = (&pattern[..start], &pattern[start + 1..end], &pattern[end + 1..]);
+    let rest = match segment.strip_prefix(prefix) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let literal = &suffix[..suffix.find('{').unwrap_or(suffix.len())];
+    let length = if !literal.is_empty() {
+        rest.find(literal)
+    } else if suffix.is_empty() {
+        Some(rest.len())
+    } else {
+        // Two uri parameters next to each other can't be told apart.
+        None
+    };
+    match length {
+        Some(length) if length > 0 => {
+            values.insert(name.to_string(), rest[..length].to_string());
+            match_segment(suffix, &rest[length..], values)
+        }
+        _ => false,
+    }
+}
+
+/// A method of a resource, as listed by `Raml::iter_operations`.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Operation<'a> {
+    path: String,
+    base_uri: Option<&'a str>,
+    verb: &'a str,
+    resource: &'a Resource,
+    method: &'a Method,
+}
+
+impl<'a> Operation<'a> {
+    /// The full uri of the resource, relative to the baseUri, e.g. `/users/{userId}/orders`.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// The path prefixed with the baseUri of the API, when it has one.
+    pub fn uri(&self) -> String {
+        match self.base_uri {
+            Some(base_uri) => join_uri(base_uri, &self.path),
+            None => self.path.clone(),
+        }
+    }
+
+    /// The name of the method, e.g. `get`.
+    pub fn verb(&self) -> &'a str {
+        self.verb
+    }
+
+    pub fn resource(&self) -> &'a Resource {
+        self.resource
+    }
+
+    pub fn method(&self) -> &'a Method {
+        self.method
+    }
+}
+
+/// The methods of resources and their nested resources, depth first in document order. As the
+/// methods of a resource are kept in a map, they are listed in the order of `METHOD_NAMES`.
+pub fn get_operations<'a>(resources: &'a [Resource],
+                          parent_path: &str,
+                          base_uri: Option<&'a str>,
+                          operations: &mut Vec<Operation<'a>>) {
+    for resource in resources {
+        let path = join_uri(parent_path, &resource.relative_uri);
+        for verb in METHOD_NAMES.iter() {
+            if let Some(method) = resource.methods.get(*verb) {
+                operations.push(Operation {
+                    path: path.clone(),
+                    base_uri,
+                    verb,
+                    resource,
+                    method,
+                });
+            }
+        }
+        get_operations(&resource.resources, &path, base_uri, operations);
+    }
+}
+
+/// The resources and methods to which `find` gives an annotation, depth first in document order,
+/// with each resource before its methods.
+pub fn get_annotated_nodes<'a, F>(resources: &'a [Resource],
+                                  parent_path: &str,
+                                  find: &F,
+                                  nodes: &mut Vec<AnnotatedNode<'a>>)
+    where F: Fn(Option<&'a Annotations>) -> Option<&'a AnnotationValue>
+{
+    for resource in resources {
+        let path = join_uri(parent_path, &resource.relative_uri);
+        if let Some(value) = find(resource.annotations.as_ref()) {
+            nodes.push(AnnotatedNode::new(path.clone(), "Resource", value));
+        }
+        for verb in METHOD_NAMES.iter() {
+            let method = resource.methods.get(*verb);
+            if let Some(value) = method.and_then(|method| find(method.annotations())) {
+                nodes.push(AnnotatedNode::new(format!("{} {}", verb, path), "Method", value));
+            }
+        }
+        get_annotated_nodes(&resource.resources, &path, find, nodes);
+    }
+}
+
+// The resources from one of `resources` to `target`, which is either one of them or nested in
+// one of them.
+fn find_resource_chain<'a>(resources: &'a [Resource],
+                           target: &Resource)
+                           -> Option<Vec<&'a Resource>> {
+    resources.iter().find_map(|resource| {
+        if ptr::eq(resource, target) {
+            return Some(vec![resource]);
+        }
+        let mut chain = find_resource_chain(&resource.resources, target)?;
+        chain.insert(0, resource);
+        Some(chain)
+    })
+}
+
+/// The uri parameters of a chain of nested resources, outermost first. A parameter declared by
+/// more than one resource is taken from the innermost, and template variables without a
+/// declaration get `NamedParameter::implicit`.
+pub fn get_inherited_uri_parameters(resource_chain: &[&Resource]) -> NamedParameters {
+    let mut parameters = NamedParameters::new();
+    for resource in resource_chain {
+        for name in get_uri_template_variables(&resource.relative_uri) {
+            parameters.entry(name).or_insert_with(NamedParameter::implicit);
+        }
+        for (name, parameter) in resource.uri_parameters.iter().flatten() {
+            parameters.insert(name.clone(), parameter.clone());
+        }
+    }
+    parameters
+}
+
+// Replaces repeated slashes in a uri by one, other than those following its scheme.
+fn remove_repeated_slashes(uri: &str) -> String {
+    let (scheme, rest) = match uri.find("://") {
+        Some(index) => uri.split_at(index + 3),
+        None => ("", uri),
+    };
+    let mut result = scheme.to_string();
+    for c in rest.chars() {
+        if !(c == '/' && result.ends_with('/') && result.len() > scheme.len()) {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Appends a relative uri to a uri with a single slash between them.
+pub fn join_uri(uri: &str, relative_uri: &str) -> String {
+    format!("{}/{}",
+            uri.trim_end_matches('/'),
+            relative_uri.trim_start_matches('/'))
+}
@@ -0,0 +1,258 @@
+use yaml_rust::scanner::{Marker, Token};
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use std::collections::{HashMap, HashSet};
+use yaml::*;
+use method::{is_method_name, Methods};
+use parameter::NamedParameters;
+use resource::{get_resource_facet, merge_resource, Resource};
+use traits::{get_parameterized_reference, TemplateParameters, TraitReferences};
+use options::{ParseOptions, UnknownKeyBehaviour};
+use template::{substitute_parameters, TemplateContext};
+use library::{find_library, Libraries};
+use parser::SecuredByEntries;
+use annotation::Annotations;
+
+pub type ResourceTypes = HashMap<String, ResourceType>;
+
+/// A reusable resource definition, applied to resources with the `type` node.
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ResourceType {
+    usage: Option<String>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    resource: Resource,
+    // Methods declared as `get?:`, which only apply to resources that declare the method.
+    optional_methods: HashSet<String>,
+    // The declaration as written, from which the resource type is instantiated with its
+    // parameters.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tokens: Vec<Token>,
+}
+
+// Compared by its nodes, as the tokens it is instantiated from depend on how it was written.
+impl PartialEq for ResourceType {
+    fn eq(&self, other: &ResourceType) -> bool {
+        self.usage == other.usage && self.resource == other.resource &&
+        self.optional_methods == other.optional_methods
+    }
+}
+
+impl ResourceType {
+    pub fn usage(&self) -> Option<&str> {
+        self.usage.as_deref()
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.resource.display_name()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.resource.description()
+    }
+
+    pub fn uri_parameters(&self) -> Option<&NamedParameters> {
+        self.resource.uri_parameters()
+    }
+
+    pub fn methods(&self) -> &Methods {
+        self.resource.methods()
+    }
+
+    pub fn is_optional_method(&self, name: &str) -> bool {
+        self.optional_methods.contains(name)
+    }
+
+    pub fn is(&self) -> Option<&TraitReferences> {
+        self.resource.is()
+    }
+
+    pub fn resource_type(&self) -> Option<&ResourceTypeReference> {
+        self.resource.resource_type()
+    }
+
+    pub fn secured_by(&self) -> Option<&SecuredByEntries> {
+        self.resource.secured_by()
+    }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.resource.annotations()
+    }
+}
+
+/// A resource type applied with the `type` node, with the parameters given to a parameterized
+/// resource type.
+#[derive(Debug)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ResourceTypeReference {
+    name: String,
+    parameters: Option<TemplateParameters>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "::serialization::default_marker"))]
+    marker: Marker,
+}
+
+impl PartialEq for ResourceTypeReference {
+    fn eq(&self, other: &ResourceTypeReference) -> bool {
+        self.name == other.name && self.parameters == other.parameters
+    }
+}
+
+impl ResourceTypeReference {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn parameters(&self) -> Option<&TemplateParameters> {
+        self.parameters.as_ref()
+    }
+}
+
+pub fn get_resource_types(cursor: &mut ForwardCursor) -> Result<ResourceTypes, RamlError> {
+    let mut resource_types: ResourceTypes = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    // Resource types may also be given in the older form of a sequence of maps.
+    let sequence =
+        get_optional_sequence(cursor,
+                              |cursor| get_resource_type_map(cursor, &mut resource_types))?;
+    if sequence.is_none() {
+        get_resource_type_map(cursor, &mut resource_types)?;
+    }
+    Ok(resource_types)
+}
+
+fn get_resource_type_map(cursor: &mut ForwardCursor,
+                         resource_types: &mut ResourceTypes)
+                         -> Result<(), RamlError> {
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        if resource_types.contains_key(&key.value) {
+            return Err(get_error(ErrorDef::DuplicateKey {
+                                     key: key.value,
+                                     level: HierarchyLevel::ResourceType,
+                                 },
+                                 Some(key.marker)));
+        }
+        let resource_type = get_resource_type(cursor)?;
+        resource_types.insert(key.value, resource_type);
+    }
+    Ok(())
+}
+
+pub fn get_resource_type(cursor: &mut ForwardCursor) -> Result<ResourceType, RamlError> {
+    let tokens = get_raw_value(cursor)?;
+    let mut declaration = cursor.nested(tokens.clone());
+    let result = parse_resource_type(&mut declaration, tokens)
+        .map_err(|error| declaration.annotate(error));
+    cursor.extend_warnings(declaration.take_warnings());
+    result
+}
+
+fn parse_resource_type(cursor: &mut ForwardCursor,
+                       tokens: Vec<Token>)
+                       -> Result<ResourceType, RamlError> {
+    let mut resource_type = ResourceType {
+        tokens,
+        ..ResourceType::default()
+    };
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        None => return Ok(resource_type),
+    };
+    while let Some(mut key) = get_mapping_key(cursor, &style)? {
+        if key.value == "usage" {
+            resource_type.usage = Some(get_single_value(cursor)?);
+            continue;
+        }
+        if key.value.ends_with('?') && is_method_name(&key.value[..key.value.len() - 1]) {
+            key.value.pop();
+            resource_type.optional_methods.insert(key.value.clone());
+        }
+        let level = HierarchyLevel::ResourceType;
+        get_resource_facet(cursor, &mut resource_type.resource, key, level)?;
+    }
+    Ok(resource_type)
+}
+
+pub fn get_resource_type_reference(cursor: &mut ForwardCursor,
+                                   level: HierarchyLevel)
+                                   -> Result<ResourceTypeReference, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    let (name, parameters) = get_parameterized_reference(cursor, level)?;
+    Ok(ResourceTypeReference {
+        name: name.value,
+        parameters,
+        marker: name.marker,
+    })
+}
+
+/// Looks up the resource type a resource references, followed by the types that resource type
+/// is based on, in the order they are applied. Each is instantiated with the parameters given
+/// by the reference to it. A resource type declared in a library is based on the types declared
+/// alongside it.
+pub fn get_referenced_resource_types(reference: Option<&ResourceTypeReference>,
+                                     resource_types: Option<&ResourceTypes>,
+                                     libraries: Option<&Libraries>,
+                                     context: &TemplateContext)
+                                     -> Result<Vec<ResourceType>, RamlError> {
+    let mut resource_types = resource_types;
+    let mut libraries = libraries;
+    let mut result: Vec<ResourceType> = vec![];
+    let mut names: Vec<String> = vec![];
+    let mut reference = reference.cloned();
+    while let Some(current) = reference {
+        if names.contains(&current.name) {
+            names.push(current.name.clone());
+            return Err(get_error(ErrorDef::CircularResourceType { cycle: names },
+                                 Some(current.marker)));
+        }
+        names.push(current.name.clone());
+        let mut name = current.name.as_str();
+        while let Some((library, name_in_library)) =
+            find_library(name, libraries, Some(current.marker))? {
+            resource_types = library.resource_types();
+            libraries = library.uses();
+            name = name_in_library;
+        }
+        let resource_type =
+            match resource_types.and_then(|resource_types| resource_types.get(name)) {
+                Some(resource_type) => resource_type,
+                None => {
+                    return Err(get_error(ErrorDef::UndeclaredResourceType {
+                                             name: current.name.clone(),
+                                         },
+                                         Some(current.marker)))
+                }
+            };
+        let resource_type = instantiate_resource_type(resource_type, &current, context)?;
+        reference = resource_type.resource_type().cloned();
+        result.push(resource_type);
+    }
+    Ok(result)
+}
+
+fn instantiate_resource_type(resource_type: &ResourceType,
+                             reference: &ResourceTypeReference,
+                             context: &TemplateContext)
+                             -> Result<ResourceType, RamlError> {
+    let tokens = substitute_parameters(&resource_type.tokens,
+                                       reference.parameters.as_ref(),
+                                       context,
+                                       HierarchyLevel::ResourceType,
+                                       &reference.name)?;
+    // Unknown keys were reported, or failed, when the declaration was parsed.
+    let options = ParseOptions {
+        unknown_keys: UnknownKeyBehaviour::Ignore,
+        ..ParseOptions::default()
+    };
+    let mut cursor = ForwardCursor::from_tokens(tokens.clone(), options);
+    parse_resource_type(&mut cursor, tokens)
+}
+
+pub fn apply_resource_type(resource: &mut Resource, resource_type: &ResourceType) {
+    merge_resource(resource, &resource_type.resource, &resource_type.optional_methods);
+}
@@ -0,0 +1,83 @@
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use std::collections::HashMap;
+use yaml::*;
+use parameter::{get_named_parameters, NamedParameters};
+use body::{expand_default_media_types, get_bodies, Bodies};
+use parser::MediaTypes;
+
+pub type Responses = HashMap<u16, Response>;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Response {
+    description: Option<String>,
+    headers: Option<NamedParameters>,
+    body: Option<Bodies>,
+}
+
+impl Response {
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn headers(&self) -> Option<&NamedParameters> {
+        self.headers.as_ref()
+    }
+
+    pub fn body(&self) -> Option<&Bodies> {
+        self.body.as_ref()
+    }
+}
+
+pub fn get_responses(cursor: &mut ForwardCursor) -> Result<Responses, RamlError> {
+    let mut result: Responses = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        let status_code = match key.value.parse::<u16>() {
+            Ok(code) if key.value.len() == 3 && (100..600).contains(&code) => code,
+            _ => {
+                return Err(get_error(ErrorDef::InvalidStatusCode { code: key.value },
+                                     Some(key.marker)))
+            }
+        };
+        result.insert(status_code, get_response(cursor)?);
+    }
+    Ok(result)
+}
+
+fn get_response(cursor: &mut ForwardCursor) -> Result<Response, RamlError> {
+    let mut response = Response::default();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        // A response declared without a body, e.g. `200:`
+        None => return Ok(response),
+    };
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "description" => response.description = get_optional_value(cursor)?,
+            "headers" => {
+                response.headers = Some(get_named_parameters(cursor, HierarchyLevel::Header)?)
+            }
+            "body" => response.body = Some(get_bodies(cursor, key.marker)?),
+            _ => skip_unknown_key(cursor, key, HierarchyLevel::Response)?,
+        }
+    }
+    Ok(response)
+}
+
+pub fn expand_response_bodies(responses: &mut Responses,
+                              media_types: Option<&MediaTypes>)
+                              -> Result<(), RamlError> {
+    for response in responses.values_mut() {
+        if let Some(ref mut body) = response.body {
+            expand_default_media_types(body, media_types)?;
+        }
+    }
+    Ok(())
+}
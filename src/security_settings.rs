@@ -0,0 +1,372 @@
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use yaml::*;
+use yaml_rust::scanner::Marker;
+use example::{get_example_value, ExampleValue};
+use parser::SecuritySchemeType;
+
+/// The `settings` node of a security scheme, whose contents depend on the scheme's type.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum SecuritySchemeSettings {
+    OAuth1(OAuth1Settings),
+    OAuth2(OAuth2Settings),
+    PassThrough(PassThroughSettings),
+    /// The settings of any other type of scheme, as written.
+    Other(ExampleValue),
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", deny_unknown_fields))]
+pub struct OAuth1Settings {
+    request_token_uri: String,
+    authorization_uri: String,
+    token_credentials_uri: String,
+    signatures: Option<Vec<OAuth1Signature>>,
+}
+
+impl OAuth1Settings {
+    pub fn request_token_uri(&self) -> &str {
+        self.request_token_uri.as_str()
+    }
+
+    pub fn authorization_uri(&self) -> &str {
+        self.authorization_uri.as_str()
+    }
+
+    pub fn token_credentials_uri(&self) -> &str {
+        self.token_credentials_uri.as_str()
+    }
+
+    /// The signature methods the server supports. All are supported when not given.
+    pub fn signatures(&self) -> Option<&[OAuth1Signature]> {
+        self.signatures.as_deref()
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Eq)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OAuth1Signature {
+    #[cfg_attr(feature = "serde", serde(rename = "HMAC-SHA1"))]
+    HmacSha1,
+    #[cfg_attr(feature = "serde", serde(rename = "RSA-SHA1"))]
+    RsaSha1,
+    #[cfg_attr(feature = "serde", serde(rename = "PLAINTEXT"))]
+    Plaintext,
+}
+
+impl OAuth1Signature {
+    /// The signature method as written in a document, e.g. `HMAC-SHA1`.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            OAuth1Signature::HmacSha1 => "HMAC-SHA1",
+            OAuth1Signature::RsaSha1 => "RSA-SHA1",
+            OAuth1Signature::Plaintext => "PLAINTEXT",
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", deny_unknown_fields))]
+pub struct OAuth2Settings {
+    authorization_uri: Option<String>,
+    access_token_uri: Option<String>,
+    authorization_grants: Vec<AuthorizationGrant>,
+    scopes: Option<Vec<String>>,
+}
+
+impl OAuth2Settings {
+    pub fn authorization_uri(&self) -> Option<&str> {
+        self.authorization_uri.as_deref()
+    }
+
+    pub fn access_token_uri(&self) -> Option<&str> {
+        self.access_token_uri.as_deref()
+    }
+
+    pub fn authorization_grants(&self) -> &[AuthorizationGrant] {
+        self.authorization_grants.as_slice()
+    }
+
+    pub fn scopes(&self) -> Option<&[String]> {
+        self.scopes.as_deref()
+    }
+}
+
+/// The query parameter and header the credentials of a Pass Through scheme are sent in. At least
+/// one of them is given.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", deny_unknown_fields))]
+pub struct PassThroughSettings {
+    query_parameter_name: Option<String>,
+    header_name: Option<String>,
+}
+
+impl PassThroughSettings {
+    pub fn query_parameter_name(&self) -> Option<&str> {
+        self.query_parameter_name.as_deref()
+    }
+
+    pub fn header_name(&self) -> Option<&str> {
+        self.header_name.as_deref()
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum AuthorizationGrant {
+    AuthorizationCode,
+    Password,
+    ClientCredentials,
+    Implicit,
+    /// An extension grant, identified by an absolute URI.
+    Extension(String),
+}
+
+impl AuthorizationGrant {
+    /// The grant as written in a document, e.g. `authorization_code`.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            AuthorizationGrant::AuthorizationCode => "authorization_code",
+            AuthorizationGrant::Password => "password",
+            AuthorizationGrant::ClientCredentials => "client_credentials",
+            AuthorizationGrant::Implicit => "implicit",
+            AuthorizationGrant::Extension(ref uri) => uri.as_str(),
+        }
+    }
+
+    fn requires_authorization_uri(&self) -> bool {
+        matches!(*self, AuthorizationGrant::AuthorizationCode | AuthorizationGrant::Implicit)
+    }
+
+    fn requires_access_token_uri(&self) -> bool {
+        *self != AuthorizationGrant::Implicit
+    }
+}
+
+/// Parses the `settings` of a security scheme of the given type. `scheme` is the name of the
+/// scheme, used for errors.
+pub fn get_settings(cursor: &mut ForwardCursor,
+                    security_type: &SecuritySchemeType,
+                    scheme: Option<&str>)
+                    -> Result<SecuritySchemeSettings, RamlError> {
+    match *security_type {
+        SecuritySchemeType::OAuth1 => {
+            Ok(SecuritySchemeSettings::OAuth1(get_oauth1_settings(cursor, scheme)?))
+        }
+        SecuritySchemeType::OAuth2 => {
+            Ok(SecuritySchemeSettings::OAuth2(get_oauth2_settings(cursor, scheme)?))
+        }
+        SecuritySchemeType::PassThrough => {
+            Ok(SecuritySchemeSettings::PassThrough(get_pass_through_settings(cursor, scheme)?))
+        }
+        _ => {
+            cursor.expect(TokenTypeDef::Value)?;
+            Ok(SecuritySchemeSettings::Other(get_example_value(cursor)?))
+        }
+    }
+}
+
+/// Checks a security scheme declares the settings its type requires. `marker` is the position
+/// of the scheme.
+pub fn check_required_settings(security_type: &SecuritySchemeType,
+                               settings: Option<&SecuritySchemeSettings>,
+                               marker: Marker)
+                               -> Result<(), RamlError> {
+    match (security_type, settings) {
+        (&SecuritySchemeType::OAuth1, None) => Err(missing_setting("settings", marker)),
+        _ => Ok(()),
+    }
+}
+
+fn get_oauth1_settings(cursor: &mut ForwardCursor,
+                       scheme: Option<&str>)
+                       -> Result<OAuth1Settings, RamlError> {
+    let mut request_token_uri: Option<String> = None;
+    let mut authorization_uri: Option<String> = None;
+    let mut token_credentials_uri: Option<String> = None;
+    let mut signatures: Option<Vec<OAuth1Signature>> = None;
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    let marker = cursor.peek_marker()?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "requestTokenUri" => request_token_uri = Some(get_single_value(cursor)?),
+            "authorizationUri" => authorization_uri = Some(get_single_value(cursor)?),
+            "tokenCredentialsUri" => token_credentials_uri = Some(get_single_value(cursor)?),
+            "signatures" => {
+                let values = get_sequence_values(cursor)?
+                    .into_iter()
+                    .map(get_oauth1_signature)
+                    .collect::<Result<Vec<_>, _>>()?;
+                signatures = Some(values);
+            }
+            _ => return Err(unexpected_setting(key, scheme)),
+        }
+    }
+    let request_token_uri =
+        request_token_uri.ok_or_else(|| missing_setting("requestTokenUri", marker))?;
+    let authorization_uri =
+        authorization_uri.ok_or_else(|| missing_setting("authorizationUri", marker))?;
+    let token_credentials_uri =
+        token_credentials_uri.ok_or_else(|| missing_setting("tokenCredentialsUri", marker))?;
+    Ok(OAuth1Settings {
+        request_token_uri,
+        authorization_uri,
+        token_credentials_uri,
+        signatures,
+    })
+}
+
+fn get_oauth1_signature(entry: FlowSequenceEntry) -> Result<OAuth1Signature, RamlError> {
+    match entry.value.as_str() {
+        "HMAC-SHA1" => Ok(OAuth1Signature::HmacSha1),
+        "RSA-SHA1" => Ok(OAuth1Signature::RsaSha1),
+        "PLAINTEXT" => Ok(OAuth1Signature::Plaintext),
+        _ => {
+            Err(get_error(ErrorDef::InvalidOAuth1Signature { signature: entry.value },
+                          Some(entry.marker)))
+        }
+    }
+}
+
+fn get_oauth2_settings(cursor: &mut ForwardCursor,
+                       scheme: Option<&str>)
+                       -> Result<OAuth2Settings, RamlError> {
+    let mut settings = OAuth2Settings::default();
+    let mut authorization_grants: Option<Vec<AuthorizationGrant>> = None;
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    let marker = cursor.peek_marker()?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "authorizationUri" => settings.authorization_uri = Some(get_single_value(cursor)?),
+            "accessTokenUri" => settings.access_token_uri = Some(get_single_value(cursor)?),
+            "authorizationGrants" => {
+                let grants = get_sequence_values(cursor)?
+                    .into_iter()
+                    .map(get_authorization_grant)
+                    .collect::<Result<Vec<_>, _>>()?;
+                authorization_grants = Some(grants);
+            }
+            "scopes" => {
+                let scopes = get_sequence_values(cursor)?;
+                settings.scopes = Some(scopes.into_iter().map(|scope| scope.value).collect());
+            }
+            _ => return Err(unexpected_setting(key, scheme)),
+        }
+    }
+    settings.authorization_grants = match authorization_grants {
+        Some(grants) => grants,
+        None => return Err(missing_setting("authorizationGrants", marker)),
+    };
+    let grants = &settings.authorization_grants;
+    if settings.authorization_uri.is_none() &&
+       grants.iter().any(|grant| grant.requires_authorization_uri()) {
+        return Err(missing_setting("authorizationUri", marker));
+    }
+    if settings.access_token_uri.is_none() &&
+       grants.iter().any(|grant| grant.requires_access_token_uri()) {
+        return Err(missing_setting("accessTokenUri", marker));
+    }
+    Ok(settings)
+}
+
+fn get_pass_through_settings(cursor: &mut ForwardCursor,
+                             scheme: Option<&str>)
+                             -> Result<PassThroughSettings, RamlError> {
+    let mut settings = PassThroughSettings::default();
+    cursor.expect(TokenTypeDef::Value)?;
+    let mut marker = cursor.peek_marker()?;
+    if let Some(style) = get_optional_mapping_start(cursor)? {
+        marker = cursor.peek_marker()?;
+        while let Some(key) = get_mapping_key(cursor, &style)? {
+            match key.value.as_str() {
+                "queryParameterName" => {
+                    settings.query_parameter_name = Some(get_single_value(cursor)?)
+                }
+                "headerName" => settings.header_name = Some(get_single_value(cursor)?),
+                _ => return Err(unexpected_setting(key, scheme)),
+            }
+        }
+    }
+    if settings.query_parameter_name.is_none() && settings.header_name.is_none() {
+        return Err(missing_setting("queryParameterName or headerName", marker));
+    }
+    Ok(settings)
+}
+
+fn get_authorization_grant(entry: FlowSequenceEntry) -> Result<AuthorizationGrant, RamlError> {
+    match parse_authorization_grant(&entry.value) {
+        Some(grant) => Ok(grant),
+        None => {
+            Err(get_error(ErrorDef::InvalidAuthorizationGrant { grant: entry.value },
+                          Some(entry.marker)))
+        }
+    }
+}
+
+/// Reads one of the grants defined by OAuth 2.0, or an extension grant's absolute URI.
+pub fn parse_authorization_grant(value: &str) -> Option<AuthorizationGrant> {
+    match value {
+        "authorization_code" => Some(AuthorizationGrant::AuthorizationCode),
+        "password" => Some(AuthorizationGrant::Password),
+        "client_credentials" => Some(AuthorizationGrant::ClientCredentials),
+        "implicit" => Some(AuthorizationGrant::Implicit),
+        grant if is_absolute_uri(grant) => Some(AuthorizationGrant::Extension(value.to_string())),
+        _ => None,
+    }
+}
+
+// An absolute URI starts with a scheme: a letter followed by letters, digits, `+`, `-` or `.`,
+// ending with a colon.
+fn is_absolute_uri(value: &str) -> bool {
+    match value.find(':') {
+        Some(end) if end > 0 => {
+            let scheme = &value[..end];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic()) &&
+            scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        _ => false,
+    }
+}
+
+fn unexpected_setting(key: MappingKey, scheme: Option<&str>) -> RamlError {
+    get_error(ErrorDef::UnexpectedSetting {
+                  key: key.value,
+                  scheme: scheme.map(|scheme| scheme.to_string()),
+              },
+              Some(key.marker))
+}
+
+fn missing_setting(field: &str, marker: Marker) -> RamlError {
+    get_error(ErrorDef::MissingField {
+                  field: field.to_string(),
+                  level: HierarchyLevel::SecurityScheme,
+              },
+              Some(marker))
+}
@@ -0,0 +1,288 @@
+//! `Serialize` and `Deserialize` for the types that are written as plain values in a document,
+//! rather than as a map of their fields.
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use yaml_rust::scanner::{Marker, Scanner};
+use example::ExampleValue;
+use parser::{SecuredBy, SecuritySchemeType};
+use security_settings::{parse_authorization_grant, AuthorizationGrant};
+use type_expression::{parse_type_expression, TypeExpression};
+use media_type::MediaType;
+use yaml::YamlNode;
+
+/// The marker given to references and annotations read back from a serialized document, which
+/// has no position in a source.
+pub fn default_marker() -> Marker {
+    Scanner::new("".chars()).mark()
+}
+
+impl Serialize for SecuritySchemeType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecuritySchemeType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse()
+            .map_err(|_| de::Error::custom(format!("invalid security scheme type: {}", value)))
+    }
+}
+
+impl Serialize for AuthorizationGrant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthorizationGrant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_authorization_grant(&value)
+            .ok_or_else(|| de::Error::custom(format!("invalid authorization grant: {}", value)))
+    }
+}
+
+impl Serialize for TypeExpression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeExpression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_type_expression(&value, default_marker())
+            .map_err(|_| de::Error::custom(format!("invalid type expression: {}", value)))
+    }
+}
+
+impl Serialize for MediaType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(|_| de::Error::custom(format!("invalid media type: {}", value)))
+    }
+}
+
+// Written as the value itself, keeping the entries of a map in document order.
+impl Serialize for ExampleValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            ExampleValue::String(ref value) => serializer.serialize_str(value),
+            ExampleValue::Number(value) => serializer.serialize_f64(value),
+            ExampleValue::Boolean(value) => serializer.serialize_bool(value),
+            ExampleValue::Null => serializer.serialize_none(),
+            ExampleValue::Sequence(ref values) => serializer.collect_seq(values),
+            ExampleValue::Mapping(ref entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ExampleValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ExampleValueVisitor)
+    }
+}
+
+struct ExampleValueVisitor;
+
+impl<'de> Visitor<'de> for ExampleValueVisitor {
+    type Value = ExampleValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an example value")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::String(value.to_string()))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::Number(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::Number(value as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::Number(value as f64))
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::Boolean(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<ExampleValue, E> {
+        Ok(ExampleValue::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ExampleValue, A::Error> {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(ExampleValue::Sequence(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ExampleValue, A::Error> {
+        let mut entries = vec![];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(ExampleValue::Mapping(entries))
+    }
+}
+
+// Written as the value itself, with every scalar a string. A node read back has no position in a
+// source.
+impl Serialize for YamlNode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            YamlNode::Scalar(ref value, _) => serializer.serialize_str(value),
+            YamlNode::Sequence(ref values, _) => serializer.collect_seq(values),
+            YamlNode::Mapping(ref entries, _) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for YamlNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(YamlNodeVisitor)
+    }
+}
+
+struct YamlNodeVisitor;
+
+impl<'de> Visitor<'de> for YamlNodeVisitor {
+    type Value = YamlNode;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a YAML node")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(value.to_string(), default_marker()))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(value.to_string(), default_marker()))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(value.to_string(), default_marker()))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(value.to_string(), default_marker()))
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(value.to_string(), default_marker()))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(String::new(), default_marker()))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<YamlNode, E> {
+        Ok(YamlNode::Scalar(String::new(), default_marker()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<YamlNode, A::Error> {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(YamlNode::Sequence(values, default_marker()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<YamlNode, A::Error> {
+        let mut entries = vec![];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(YamlNode::Mapping(entries, default_marker()))
+    }
+}
+
+// Written as in a `securedBy` node: `null`, the name of a scheme, or a map from the name of a
+// scheme to its parameters.
+impl Serialize for SecuredBy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            SecuredBy::Null => serializer.serialize_none(),
+            SecuredBy::Scheme(ref name) => serializer.serialize_str(name),
+            SecuredBy::Parameterized(ref name, ref parameters) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(name, parameters)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SecuredBy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SecuredByVisitor)
+    }
+}
+
+struct SecuredByVisitor;
+
+impl<'de> Visitor<'de> for SecuredByVisitor {
+    type Value = SecuredBy;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "null, the name of a security scheme or a map from its name to parameters")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<SecuredBy, E> {
+        Ok(SecuredBy::Scheme(value.to_string()))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<SecuredBy, E> {
+        Ok(SecuredBy::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<SecuredBy, E> {
+        Ok(SecuredBy::Null)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<SecuredBy, A::Error> {
+        let (name, parameters): (String, HashMap<String, ExampleValue>) = match map.next_entry()? {
+            Some(entry) => entry,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        if map.next_key::<String>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(SecuredBy::Parameterized(name, parameters))
+    }
+}
@@ -0,0 +1,211 @@
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use yaml_rust::scanner::{Marker, Token, TokenType};
+use traits::TemplateParameters;
+
+/// The values of the reserved parameters available to traits and resource types.
+pub struct TemplateContext {
+    resource_path: String,
+    resource_path_name: String,
+    // Only set when applying traits.
+    method_name: Option<String>,
+}
+
+impl TemplateContext {
+    pub fn new(resource_path: &str, method_name: Option<&str>) -> TemplateContext {
+        // The rightmost path segment that doesn't contain a uri parameter.
+        let resource_path_name = resource_path.split('/')
+            .rev()
+            .find(|segment| !segment.is_empty() && !segment.contains('{'))
+            .unwrap_or("");
+        TemplateContext {
+            resource_path: resource_path.to_string(),
+            resource_path_name: resource_path_name.to_string(),
+            method_name: method_name.map(|name| name.to_string()),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "resourcePath" => Some(self.resource_path.as_str()),
+            "resourcePathName" => Some(self.resource_path_name.as_str()),
+            "methodName" => self.method_name.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces the `<<parameter>>` placeholders in the scalars of a trait or resource type
+/// declaration. `template` is the name of the declaration, used for errors.
+pub fn substitute_parameters(tokens: &[Token],
+                             parameters: Option<&TemplateParameters>,
+                             context: &TemplateContext,
+                             level: HierarchyLevel,
+                             template: &str)
+                             -> Result<Vec<Token>, RamlError> {
+    let mut result = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let token = match token.1 {
+            TokenType::Scalar(style, ref value) if value.contains("<<") => {
+                let value = substitute_value(value,
+                                             parameters,
+                                             context,
+                                             &level,
+                                             template,
+                                             token.0)?;
+                Token(token.0, TokenType::Scalar(style, value))
+            }
+            _ => token.clone(),
+        };
+        result.push(token);
+    }
+    Ok(result)
+}
+
+fn substitute_value(value: &str,
+                    parameters: Option<&TemplateParameters>,
+                    context: &TemplateContext,
+                    level: &HierarchyLevel,
+                    template: &str,
+                    marker: Marker)
+                    -> Result<String, RamlError> {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("<<") {
+        let end = match rest[start..].find(">>") {
+            Some(end) => start + end,
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        let mut parts = rest[start + 2..end].split('|').map(|part| part.trim());
+        let name = parts.next().unwrap_or("");
+        let declared = parameters.and_then(|parameters| parameters.get(name))
+            .map(|value| value.as_str())
+            .or_else(|| context.get(name));
+        let mut replacement = match declared {
+            Some(replacement) => replacement.to_string(),
+            None => {
+                return Err(get_error(ErrorDef::UndeclaredTemplateParameter {
+                                         name: name.to_string(),
+                                         level: level.clone(),
+                                         template: template.to_string(),
+                                     },
+                                     Some(marker)))
+            }
+        };
+        for function in parts {
+            replacement = apply_function(function, &replacement, marker)?;
+        }
+        result.push_str(&replacement);
+        rest = &rest[end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn apply_function(function: &str, value: &str, marker: Marker) -> Result<String, RamlError> {
+    let result = match function {
+        "!singularize" => singularize(value),
+        "!pluralize" => pluralize(value),
+        "!uppercase" => value.to_uppercase(),
+        "!lowercase" => value.to_lowercase(),
+        "!uppercamelcase" => {
+            split_words(value).iter().map(|word| capitalize(word)).collect::<Vec<_>>().concat()
+        }
+        "!lowercamelcase" => {
+            let words = split_words(value);
+            let mut result = String::new();
+            for (index, word) in words.iter().enumerate() {
+                if index == 0 {
+                    result.push_str(&word.to_lowercase());
+                } else {
+                    result.push_str(&capitalize(word));
+                }
+            }
+            result
+        }
+        "!upperunderscorecase" => split_words(value).join("_").to_uppercase(),
+        "!lowerunderscorecase" => split_words(value).join("_").to_lowercase(),
+        "!upperhyphencase" => split_words(value).join("-").to_uppercase(),
+        "!lowerhyphencase" => split_words(value).join("-").to_lowercase(),
+        _ => {
+            return Err(get_error(ErrorDef::UnknownTemplateFunction { name: function.to_string() },
+                                 Some(marker)))
+        }
+    };
+    Ok(result)
+}
+
+/// Splits a value into words at separators and at the start of each capitalized word.
+fn split_words(value: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut previous_lowercase = false;
+    for c in value.chars() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            previous_lowercase = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lowercase {
+            words.push(word);
+            word = String::new();
+        }
+        previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect(),
+        None => String::new(),
+    }
+}
+
+// A word that is the plural of its singular, such as the resource path name `users`, is left as
+// it is. Others, such as `status`, are given a plural ending.
+fn pluralize(value: &str) -> String {
+    let singular = singularize(value);
+    if singular != value && add_plural_ending(&singular) == value {
+        value.to_string()
+    } else {
+        add_plural_ending(value)
+    }
+}
+
+fn add_plural_ending(value: &str) -> String {
+    let lower = value.to_lowercase();
+    if lower.ends_with('y') && !ends_with_vowel_y(&lower) {
+        format!("{}ies", &value[..value.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| lower.ends_with(suffix)) {
+        format!("{}es", value)
+    } else {
+        format!("{}s", value)
+    }
+}
+
+// A word without a plural ending, such as `user` or `status`, is taken to be singular already.
+fn singularize(value: &str) -> String {
+    let lower = value.to_lowercase();
+    if lower.ends_with("ies") && lower.len() > 4 {
+        format!("{}y", &value[..value.len() - 3])
+    } else if ["sses", "xes", "zes", "ches", "shes"].iter().any(|s| lower.ends_with(s)) {
+        value[..value.len() - 2].to_string()
+    } else if lower.ends_with('s') && !["ss", "us", "is"].iter().any(|s| lower.ends_with(s)) {
+        value[..value.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn ends_with_vowel_y(value: &str) -> bool {
+    ["ay", "ey", "iy", "oy", "uy"].iter().any(|suffix| value.ends_with(suffix))
+}
@@ -0,0 +1,275 @@
+use yaml_rust::scanner::{Marker, Token};
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use std::collections::HashMap;
+use yaml::*;
+use method::{get_method_facet, merge_method, Method};
+use parameter::NamedParameters;
+use response::Responses;
+use body::Bodies;
+use options::{ParseOptions, UnknownKeyBehaviour};
+use template::{substitute_parameters, TemplateContext};
+use library::{find_declaration, Libraries, Library};
+use parser::SecuredByEntries;
+use annotation::Annotations;
+
+pub type Traits = HashMap<String, Trait>;
+
+pub type TraitReferences = Vec<TraitReference>;
+
+/// The parameters given to a parameterized trait or resource type.
+pub type TemplateParameters = HashMap<String, String>;
+
+/// A reusable set of method nodes, applied to methods with the `is` node.
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Trait {
+    usage: Option<String>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    method: Method,
+    // The declaration as written, from which the trait is instantiated with its parameters.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tokens: Vec<Token>,
+}
+
+// The tokens are only the source of the declaration, which is compared by its nodes.
+impl PartialEq for Trait {
+    fn eq(&self, other: &Trait) -> bool {
+        self.usage == other.usage && self.method == other.method
+    }
+}
+
+impl Trait {
+    pub fn usage(&self) -> Option<&str> {
+        self.usage.as_deref()
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.method.display_name()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.method.description()
+    }
+
+    pub fn query_parameters(&self) -> Option<&NamedParameters> {
+        self.method.query_parameters()
+    }
+
+    pub fn headers(&self) -> Option<&NamedParameters> {
+        self.method.headers()
+    }
+
+    pub fn responses(&self) -> Option<&Responses> {
+        self.method.responses()
+    }
+
+    pub fn body(&self) -> Option<&Bodies> {
+        self.method.body()
+    }
+
+    pub fn secured_by(&self) -> Option<&SecuredByEntries> {
+        self.method.secured_by()
+    }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.method.annotations()
+    }
+}
+
+/// A trait applied with the `is` node, with the parameters given to a parameterized trait.
+#[derive(Debug)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TraitReference {
+    name: String,
+    parameters: Option<TemplateParameters>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "::serialization::default_marker"))]
+    marker: Marker,
+}
+
+impl PartialEq for TraitReference {
+    fn eq(&self, other: &TraitReference) -> bool {
+        self.name == other.name && self.parameters == other.parameters
+    }
+}
+
+impl TraitReference {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn parameters(&self) -> Option<&TemplateParameters> {
+        self.parameters.as_ref()
+    }
+}
+
+pub fn get_traits(cursor: &mut ForwardCursor) -> Result<Traits, RamlError> {
+    let mut traits: Traits = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    // Traits may also be given in the older form of a sequence of maps.
+    let sequence = get_optional_sequence(cursor, |cursor| get_trait_map(cursor, &mut traits))?;
+    if sequence.is_none() {
+        get_trait_map(cursor, &mut traits)?;
+    }
+    Ok(traits)
+}
+
+fn get_trait_map(cursor: &mut ForwardCursor, traits: &mut Traits) -> Result<(), RamlError> {
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        if traits.contains_key(&key.value) {
+            return Err(get_error(ErrorDef::DuplicateKey {
+                                     key: key.value,
+                                     level: HierarchyLevel::Trait,
+                                 },
+                                 Some(key.marker)));
+        }
+        let raml_trait = get_trait(cursor)?;
+        traits.insert(key.value, raml_trait);
+    }
+    Ok(())
+}
+
+pub fn get_trait(cursor: &mut ForwardCursor) -> Result<Trait, RamlError> {
+    let tokens = get_raw_value(cursor)?;
+    let mut declaration = cursor.nested(tokens.clone());
+    let result = parse_trait(&mut declaration, tokens)
+        .map_err(|error| declaration.annotate(error));
+    cursor.extend_warnings(declaration.take_warnings());
+    result
+}
+
+fn parse_trait(cursor: &mut ForwardCursor, tokens: Vec<Token>) -> Result<Trait, RamlError> {
+    let mut raml_trait = Trait {
+        tokens,
+        ..Trait::default()
+    };
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        None => return Ok(raml_trait),
+    };
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "usage" => raml_trait.usage = Some(get_single_value(cursor)?),
+            _ => get_method_facet(cursor, &mut raml_trait.method, key, HierarchyLevel::Trait)?,
+        }
+    }
+    Ok(raml_trait)
+}
+
+pub fn get_trait_references(cursor: &mut ForwardCursor,
+                            level: HierarchyLevel)
+                            -> Result<TraitReferences, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    match get_optional_sequence(cursor, |cursor| get_trait_reference(cursor, level.clone()))? {
+        Some(references) => Ok(references),
+        None => Ok(vec![get_trait_reference(cursor, level)?]),
+    }
+}
+
+fn get_trait_reference(cursor: &mut ForwardCursor,
+                       level: HierarchyLevel)
+                       -> Result<TraitReference, RamlError> {
+    let (name, parameters) = get_parameterized_reference(cursor, level)?;
+    Ok(TraitReference {
+        name: name.value,
+        parameters,
+        marker: name.marker,
+    })
+}
+
+/// Reads a reference to a trait or resource type: either its name, or a map from its name to
+/// the parameters it is given.
+pub fn get_parameterized_reference(cursor: &mut ForwardCursor,
+                                   level: HierarchyLevel)
+                                   -> Result<(ScalarValue, Option<TemplateParameters>), RamlError> {
+    if let Some(name) = get_optional_scalar(cursor)? {
+        return Ok((name, None));
+    }
+    let style = get_mapping_start(cursor)?;
+    let marker = cursor.peek_marker()?;
+    let name = match get_mapping_key(cursor, &style)? {
+        Some(name) => name,
+        None => {
+            return Err(get_error(ErrorDef::MissingField {
+                                     field: "name".to_string(),
+                                     level,
+                                 },
+                                 Some(marker)))
+        }
+    };
+    let mut parameters = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let parameter_style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &parameter_style)? {
+        parameters.insert(key.value, get_single_value(cursor)?);
+    }
+    if let Some(key) = get_mapping_key(cursor, &style)? {
+        return Err(get_error(ErrorDef::UnexpectedKeyRoot {
+                                 field: key.value,
+                                 level,
+                             },
+                             Some(key.marker)));
+    }
+    let name = ScalarValue {
+        value: name.value,
+        marker: name.marker,
+        style: name.style,
+    };
+    Ok((name, Some(parameters)))
+}
+
+/// Looks up the declared traits referenced by an `is` node, in the order they are referenced,
+/// and instantiates them with the parameters they are given.
+pub fn get_referenced_traits(references: Option<&TraitReferences>,
+                             traits: Option<&Traits>,
+                             libraries: Option<&Libraries>,
+                             level: HierarchyLevel,
+                             context: &TemplateContext)
+                             -> Result<Vec<Trait>, RamlError> {
+    let mut result = vec![];
+    for reference in references.into_iter().flatten() {
+        match find_declaration(&reference.name,
+                               traits,
+                               libraries,
+                               Library::traits,
+                               Some(reference.marker))? {
+            Some(raml_trait) => result.push(instantiate_trait(raml_trait, reference, context)?),
+            None => {
+                return Err(get_error(ErrorDef::UndeclaredTrait {
+                                         name: reference.name.clone(),
+                                         level,
+                                     },
+                                     Some(reference.marker)))
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn instantiate_trait(raml_trait: &Trait,
+                     reference: &TraitReference,
+                     context: &TemplateContext)
+                     -> Result<Trait, RamlError> {
+    let tokens = substitute_parameters(&raml_trait.tokens,
+                                       reference.parameters.as_ref(),
+                                       context,
+                                       HierarchyLevel::Trait,
+                                       &reference.name)?;
+    // Unknown keys were reported, or failed, when the declaration was parsed.
+    let options = ParseOptions {
+        unknown_keys: UnknownKeyBehaviour::Ignore,
+        ..ParseOptions::default()
+    };
+    let mut cursor = ForwardCursor::from_tokens(tokens.clone(), options);
+    parse_trait(&mut cursor, tokens)
+}
+
+pub fn apply_trait(method: &mut Method, raml_trait: &Trait) {
+    merge_method(method, &raml_trait.method);
+}
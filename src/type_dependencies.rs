@@ -0,0 +1,202 @@
+use std::collections::{HashMap, HashSet};
+use error_definitions::{ErrorDef, RamlError, get_error};
+use type_expression::TypeExpression;
+use types::{RamlType, Types};
+
+pub type TypeEntries<'a> = Vec<(&'a str, &'a RamlType)>;
+
+// The declared types a type refers to. A type can't be ordered before the types it inherits
+// from, while the types its properties and items refer to are only references, which may be
+// recursive.
+struct References<'a> {
+    inherited: Vec<&'a str>,
+    referenced: Vec<&'a str>,
+}
+
+/// Orders the declared types so that every type comes after the types it refers to. Types that
+/// refer to each other through their properties, e.g. a tree, are ordered by name, after the
+/// types they inherit from.
+pub fn types_in_dependency_order(types: Option<&Types>) -> Result<TypeEntries<'_>, RamlError> {
+    let types = match types {
+        Some(types) => types,
+        None => return Ok(vec![]),
+    };
+    let mut names: Vec<&str> = types.keys().map(String::as_str).collect();
+    names.sort();
+    order_types(types, &names)
+}
+
+/// The declared types `root` refers to, directly or through other types, including `root`
+/// itself, in dependency order. Types declared in libraries are left out.
+pub fn type_closure<'a>(types: Option<&'a Types>,
+                        root: &str)
+                        -> Result<TypeEntries<'a>, RamlError> {
+    let types = match types {
+        Some(types) if types.contains_key(root) => types,
+        _ => {
+            return Err(get_error(ErrorDef::UndeclaredType {
+                                     name: root.to_string(),
+                                     type_name: root.to_string(),
+                                 },
+                                 None))
+        }
+    };
+    let mut reached: HashSet<&str> = HashSet::new();
+    let mut pending: Vec<&str> = vec![types.get_key_value(root).unwrap().0];
+    while let Some(name) = pending.pop() {
+        if reached.insert(name) {
+            let references = get_references(&types[name], types);
+            pending.extend(references.inherited);
+            pending.extend(references.referenced);
+        }
+    }
+    let mut names: Vec<&str> = reached.into_iter().collect();
+    names.sort();
+    order_types(types, &names)
+}
+
+fn order_types<'a>(types: &'a Types, names: &[&'a str]) -> Result<TypeEntries<'a>, RamlError> {
+    let references: HashMap<&str, References> =
+        names.iter().map(|&name| (name, get_references(&types[name], types))).collect();
+
+    // Inheritance alone must not be circular.
+    let inherited = |name: &str| references[name].inherited.clone();
+    for component in strongly_connected_components(names, &inherited) {
+        let is_cycle = component.len() > 1 || inherited(component[0]).contains(&component[0]);
+        if is_cycle {
+            let mut component: Vec<String> = component.iter().map(|n| n.to_string()).collect();
+            component.sort();
+            return Err(get_error(ErrorDef::CircularTypeDependency { types: component }, None));
+        }
+    }
+
+    // The components of all the references come out with the types they refer to first. Within
+    // a component only inheritance decides the order.
+    let all = |name: &str| {
+        let mut all = references[name].inherited.clone();
+        all.extend(&references[name].referenced);
+        all
+    };
+    let mut result: TypeEntries = vec![];
+    for mut component in strongly_connected_components(names, &all) {
+        component.sort();
+        let mut added: HashSet<&str> = HashSet::new();
+        while added.len() < component.len() {
+            let next = component.iter()
+                .find(|&&name| {
+                    !added.contains(name) &&
+                    references[name]
+                        .inherited
+                        .iter()
+                        .all(|parent| added.contains(parent) || !component.contains(parent))
+                })
+                .cloned()
+                .unwrap();
+            added.insert(next);
+            result.push((next, &types[next]));
+        }
+    }
+    Ok(result)
+}
+
+fn get_references<'a>(raml_type: &'a RamlType, types: &'a Types) -> References<'a> {
+    let mut inherited = vec![];
+    for expression in raml_type.parent_types() {
+        add_expression_names(expression, types, &mut inherited);
+    }
+    let mut referenced = vec![];
+    add_type_names(raml_type, types, &mut referenced, false);
+    References {
+        inherited,
+        referenced,
+    }
+}
+
+// Adds the names of the declared types an inline type refers to through its properties and
+// items, and through its own type when `include_type` is set.
+fn add_type_names<'a>(raml_type: &'a RamlType,
+                      types: &'a Types,
+                      names: &mut Vec<&'a str>,
+                      include_type: bool) {
+    if include_type {
+        for expression in raml_type.parent_types() {
+            add_expression_names(expression, types, names);
+        }
+    }
+    let properties = raml_type.properties().into_iter().chain(raml_type.pattern_properties());
+    for property in properties.flat_map(|properties| properties.values()) {
+        add_type_names(property.property_type(), types, names, true);
+    }
+    if let Some(items) = raml_type.items() {
+        add_type_names(items, types, names, true);
+    }
+}
+
+fn add_expression_names<'a>(expression: &'a TypeExpression,
+                            types: &'a Types,
+                            names: &mut Vec<&'a str>) {
+    match *expression {
+        TypeExpression::Named(ref name) => {
+            if types.contains_key(name) && !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        TypeExpression::Array(ref items) => add_expression_names(items, types, names),
+        TypeExpression::Union(ref members) => {
+            for member in members {
+                add_expression_names(member, types, names);
+            }
+        }
+    }
+}
+
+// Tarjan's algorithm. A component comes after the components it has edges to.
+fn strongly_connected_components<'a, F>(names: &[&'a str], edges: &F) -> Vec<Vec<&'a str>>
+    where F: Fn(&str) -> Vec<&'a str>
+{
+    struct State<'a> {
+        index: HashMap<&'a str, usize>,
+        low_link: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        components: Vec<Vec<&'a str>>,
+    }
+
+    fn visit<'a, F>(name: &'a str, edges: &F, state: &mut State<'a>)
+        where F: Fn(&str) -> Vec<&'a str>
+    {
+        let index = state.index.len();
+        state.index.insert(name, index);
+        state.low_link.insert(name, index);
+        state.stack.push(name);
+        let mut targets = edges(name);
+        targets.sort();
+        for target in targets {
+            if !state.index.contains_key(target) {
+                visit(target, edges, state);
+                let low_link = state.low_link[name].min(state.low_link[target]);
+                state.low_link.insert(name, low_link);
+            } else if state.stack.contains(&target) {
+                let low_link = state.low_link[name].min(state.index[target]);
+                state.low_link.insert(name, low_link);
+            }
+        }
+        if state.low_link[name] == index {
+            let position = state.stack.iter().position(|&n| n == name).unwrap();
+            let component = state.stack.split_off(position);
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        index: HashMap::new(),
+        low_link: HashMap::new(),
+        stack: vec![],
+        components: vec![],
+    };
+    for &name in names {
+        if !state.index.contains_key(name) {
+            visit(name, edges, &mut state);
+        }
+    }
+    state.components
+}
@@ -0,0 +1,138 @@
+use error_definitions::{ErrorDef, RamlError, get_error};
+use yaml_rust::scanner::Marker;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum TypeExpression {
+    /// A built-in or declared type. The name of a type declared in a library is qualified with
+    /// the namespace the library is used with, e.g. `shapes.Circle`.
+    Named(String),
+    Array(Box<TypeExpression>),
+    Union(Vec<TypeExpression>),
+}
+
+impl TypeExpression {
+    /// The namespace a named type is qualified with, e.g. `shapes` for `shapes.Circle`. A type
+    /// declared in a library used by a library has more than one segment, e.g. `geo.shapes`.
+    pub fn namespace(&self) -> Option<&str> {
+        match *self {
+            TypeExpression::Named(ref name) => name.rfind('.').map(|index| &name[..index]),
+            _ => None,
+        }
+    }
+
+    /// The name of a named type without its namespace, e.g. `Circle` for `shapes.Circle`.
+    pub fn local_name(&self) -> Option<&str> {
+        match *self {
+            TypeExpression::Named(ref name) => {
+                Some(name.rfind('.').map_or(name.as_str(), |index| &name[index + 1..]))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TypeExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TypeExpression::Named(ref name) => write!(f, "{}", name),
+            TypeExpression::Array(ref items) => {
+                match **items {
+                    TypeExpression::Union(_) => write!(f, "({})[]", items),
+                    _ => write!(f, "{}[]", items),
+                }
+            }
+            TypeExpression::Union(ref members) => {
+                let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+        }
+    }
+}
+
+/// Parses a type expression such as `Person`, `string[]` or `(Cat | Dog)[]`.
+pub fn parse_type_expression(expression: &str,
+                             marker: Marker)
+                             -> Result<TypeExpression, RamlError> {
+    let mut chars = expression.chars().peekable();
+    let result = parse_union(&mut chars);
+    skip_whitespace(&mut chars);
+    match result {
+        Some(type_expression) if chars.peek().is_none() => Ok(type_expression),
+        _ => {
+            Err(get_error(ErrorDef::InvalidTypeExpression { expression: expression.to_string() },
+                          Some(marker)))
+        }
+    }
+}
+
+fn parse_union(chars: &mut Peekable<Chars>) -> Option<TypeExpression> {
+    let mut members = vec![parse_array(chars)?];
+    loop {
+        skip_whitespace(chars);
+        if chars.peek() != Some(&'|') {
+            break;
+        }
+        chars.next();
+        members.push(parse_array(chars)?);
+    }
+    if members.len() == 1 {
+        members.pop()
+    } else {
+        Some(TypeExpression::Union(members))
+    }
+}
+
+fn parse_array(chars: &mut Peekable<Chars>) -> Option<TypeExpression> {
+    let mut result = parse_primary(chars)?;
+    loop {
+        skip_whitespace(chars);
+        if chars.peek() != Some(&'[') {
+            break;
+        }
+        chars.next();
+        skip_whitespace(chars);
+        if chars.next() != Some(']') {
+            return None;
+        }
+        result = TypeExpression::Array(Box::new(result));
+    }
+    Some(result)
+}
+
+fn parse_primary(chars: &mut Peekable<Chars>) -> Option<TypeExpression> {
+    skip_whitespace(chars);
+    if chars.peek() == Some(&'(') {
+        chars.next();
+        let result = parse_union(chars)?;
+        skip_whitespace(chars);
+        return match chars.next() {
+            Some(')') => Some(result),
+            _ => None,
+        };
+    }
+    let mut name = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || "|()[]".contains(c) {
+            break;
+        }
+        name.push(c);
+        chars.next();
+    }
+    // Each segment of a qualified name, e.g. `shapes.Circle`, must be given.
+    if name.split('.').any(str::is_empty) {
+        None
+    } else {
+        Some(TypeExpression::Named(name))
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
@@ -0,0 +1,1659 @@
+use yaml_rust::scanner::Marker;
+use error_definitions::{ErrorDef, RamlError, get_error, HierarchyLevel};
+use std::collections::{HashMap, HashSet};
+use yaml::*;
+use type_expression::{TypeExpression, parse_type_expression};
+use example::{Example, ExampleValue, Examples, get_example, get_example_value, get_examples,
+              scalar_text};
+use annotation::{check_annotations, get_annotation, get_annotation_name, AnnotatedNode,
+                 AnnotationValue, Annotations};
+use library::{find_declaration, find_library, Libraries, Library};
+use error_definitions::marker_position;
+use location::SourceLocation;
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+pub type Types = HashMap<String, RamlType>;
+
+pub type Properties = HashMap<String, Property>;
+
+pub type FacetValues = HashMap<String, FacetValue>;
+
+/// The language of a schema a type is declared with.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SchemaKind {
+    Json,
+    Xsd,
+}
+
+/// A JSON schema or XML schema given as the type of a type, as written in RAML 0.8 documents,
+/// usually included from a file, e.g. `type: !include person-schema.json`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ExternalSchema {
+    kind: SchemaKind,
+    raw: String,
+}
+
+impl ExternalSchema {
+    pub fn kind(&self) -> SchemaKind {
+        self.kind
+    }
+
+    /// The schema as written.
+    pub fn raw(&self) -> &str {
+        self.raw.as_str()
+    }
+}
+
+/// A type built into RAML, which every declared type is ultimately based on.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum BuiltInType {
+    Any,
+    Object,
+    Array,
+    String,
+    Number,
+    Integer,
+    Boolean,
+    DateOnly,
+    TimeOnly,
+    DatetimeOnly,
+    Datetime,
+    File,
+    Nil,
+}
+
+const BUILT_IN_TYPES: [BuiltInType; 13] = [BuiltInType::Any,
+                                           BuiltInType::Object,
+                                           BuiltInType::Array,
+                                           BuiltInType::String,
+                                           BuiltInType::Number,
+                                           BuiltInType::Integer,
+                                           BuiltInType::Boolean,
+                                           BuiltInType::DateOnly,
+                                           BuiltInType::TimeOnly,
+                                           BuiltInType::DatetimeOnly,
+                                           BuiltInType::Datetime,
+                                           BuiltInType::File,
+                                           BuiltInType::Nil];
+
+impl BuiltInType {
+    /// The built-in type with the given name, e.g. `datetime-only`.
+    pub fn from_name(name: &str) -> Option<BuiltInType> {
+        BUILT_IN_TYPES.iter().cloned().find(|built_in| built_in.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            BuiltInType::Any => "any",
+            BuiltInType::Object => "object",
+            BuiltInType::Array => "array",
+            BuiltInType::String => "string",
+            BuiltInType::Number => "number",
+            BuiltInType::Integer => "integer",
+            BuiltInType::Boolean => "boolean",
+            BuiltInType::DateOnly => "date-only",
+            BuiltInType::TimeOnly => "time-only",
+            BuiltInType::DatetimeOnly => "datetime-only",
+            BuiltInType::Datetime => "datetime",
+            BuiltInType::File => "file",
+            BuiltInType::Nil => "nil",
+        }
+    }
+}
+
+/// The `format` facet of a `datetime` type.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum DatetimeFormat {
+    /// e.g. `2016-02-28T16:41:41.090Z`, the default.
+    Rfc3339,
+    /// e.g. `Sun, 28 Feb 2016 16:41:41 GMT`.
+    Rfc2616,
+}
+
+impl DatetimeFormat {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            DatetimeFormat::Rfc3339 => "rfc3339",
+            DatetimeFormat::Rfc2616 => "rfc2616",
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Property {
+    required: bool,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    property_type: RamlType,
+}
+
+impl Property {
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn property_type(&self) -> &RamlType {
+        &self.property_type
+    }
+
+    /// How the property is represented in XML.
+    pub fn xml(&self) -> Option<&XmlFacets> {
+        self.property_type.xml()
+    }
+}
+
+/// The `xml` facet of a type, which controls how instances are represented in XML.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct XmlFacets {
+    attribute: Option<bool>,
+    wrapped: Option<bool>,
+    name: Option<String>,
+    namespace: Option<String>,
+    prefix: Option<String>,
+}
+
+impl XmlFacets {
+    /// Whether the instance is an attribute rather than an element.
+    pub fn attribute(&self) -> Option<bool> {
+        self.attribute
+    }
+
+    /// Whether an array is wrapped in an element of its own.
+    pub fn wrapped(&self) -> Option<bool> {
+        self.wrapped
+    }
+
+    /// The name of the element or attribute, which defaults to the name of the type or property.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+}
+
+/// The value a type gives to a facet declared by a type it inherits from.
+#[derive(Debug)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FacetValue {
+    value: ExampleValue,
+    #[cfg_attr(feature = "serde", serde(skip, default = "::serialization::default_marker"))]
+    marker: Marker,
+}
+
+// Where the facet is given is not part of its value.
+impl PartialEq for FacetValue {
+    fn eq(&self, other: &FacetValue) -> bool {
+        self.value == other.value
+    }
+}
+
+impl FacetValue {
+    pub fn value(&self) -> &ExampleValue {
+        &self.value
+    }
+}
+
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct RamlType {
+    // More than one entry when the type uses multiple inheritance.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    type_: Vec<TypeExpression>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    type_marker: Option<Marker>,
+    schema: Option<ExternalSchema>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Option<SourceLocation>,
+    display_name: Option<String>,
+    description: Option<String>,
+    default: Option<String>,
+    pattern: Option<String>,
+    min_length: Option<u64>,
+    max_length: Option<u64>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    multiple_of: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "enum"))]
+    enum_values: Option<Vec<String>>,
+    properties: Option<Properties>,
+    pattern_properties: Option<Properties>,
+    additional_properties: Option<bool>,
+    min_properties: Option<u64>,
+    max_properties: Option<u64>,
+    items: Option<Box<RamlType>>,
+    min_items: Option<u64>,
+    max_items: Option<u64>,
+    unique_items: Option<bool>,
+    example: Option<Example>,
+    examples: Option<Examples>,
+    annotations: Option<Annotations>,
+    facets: Option<Properties>,
+    facet_values: Option<FacetValues>,
+    xml: Option<XmlFacets>,
+    discriminator: Option<String>,
+    discriminator_value: Option<String>,
+    // Only declared by datetime types.
+    format: Option<DatetimeFormat>,
+    // Only declared by file types.
+    file_types: Option<Vec<String>>,
+    // Only declared by annotation types.
+    allowed_targets: Option<Vec<String>>,
+}
+
+// Where the type is declared, or its parent given, is not part of the declaration.
+impl PartialEq for RamlType {
+    fn eq(&self, other: &RamlType) -> bool {
+        self.type_ == other.type_ &&
+        self.schema == other.schema &&
+        self.display_name == other.display_name &&
+        self.description == other.description &&
+        self.default == other.default &&
+        self.pattern == other.pattern &&
+        self.min_length == other.min_length &&
+        self.max_length == other.max_length &&
+        self.minimum == other.minimum &&
+        self.maximum == other.maximum &&
+        self.multiple_of == other.multiple_of &&
+        self.enum_values == other.enum_values &&
+        self.properties == other.properties &&
+        self.pattern_properties == other.pattern_properties &&
+        self.additional_properties == other.additional_properties &&
+        self.min_properties == other.min_properties &&
+        self.max_properties == other.max_properties &&
+        self.items == other.items &&
+        self.min_items == other.min_items &&
+        self.max_items == other.max_items &&
+        self.unique_items == other.unique_items &&
+        self.example == other.example &&
+        self.examples == other.examples &&
+        self.annotations == other.annotations &&
+        self.facets == other.facets &&
+        self.facet_values == other.facet_values &&
+        self.xml == other.xml &&
+        self.discriminator == other.discriminator &&
+        self.discriminator_value == other.discriminator_value &&
+        self.format == other.format &&
+        self.file_types == other.file_types &&
+        self.allowed_targets == other.allowed_targets
+    }
+}
+
+impl RamlType {
+    /// The type this type extends, unless it extends more than one.
+    pub fn type_(&self) -> Option<&TypeExpression> {
+        if self.type_.len() == 1 {
+            self.type_.first()
+        } else {
+            None
+        }
+    }
+
+    pub fn parent_types(&self) -> &[TypeExpression] {
+        &self.type_
+    }
+
+    /// The schema the type is declared with, when its type is a JSON schema or an XML schema
+    /// rather than a type expression. Such a type has no parent types.
+    /// The built-in type the type is declared with, if it names one. The type of a resolved
+    /// type is always built in.
+    pub fn built_in_type(&self) -> Option<BuiltInType> {
+        match self.type_.as_slice() {
+            [TypeExpression::Named(ref name)] => BuiltInType::from_name(name),
+            _ => None,
+        }
+    }
+
+    pub fn schema(&self) -> Option<&ExternalSchema> {
+        self.schema.as_ref()
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// The display name, or else `key`, the name the type or property is declared with.
+    pub fn display_name_or_key<'a>(&'a self, key: &'a str) -> &'a str {
+        self.display_name.as_deref().unwrap_or(key)
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Where the type or property is declared, when it was parsed.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+
+    /// The minimum length of a string, or of a `file` type in bytes.
+    pub fn min_length(&self) -> Option<u64> {
+        self.min_length
+    }
+
+    /// The maximum length of a string, or of a `file` type in bytes.
+    pub fn max_length(&self) -> Option<u64> {
+        self.max_length
+    }
+
+    pub fn minimum(&self) -> Option<f64> {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> Option<f64> {
+        self.maximum
+    }
+
+    pub fn multiple_of(&self) -> Option<f64> {
+        self.multiple_of
+    }
+
+    pub fn enum_values(&self) -> Option<&Vec<String>> {
+        self.enum_values.as_ref()
+    }
+
+    pub fn properties(&self) -> Option<&Properties> {
+        self.properties.as_ref()
+    }
+
+    /// The properties whose names are regular expressions, e.g. `/^note_\d+$/`, keyed by the
+    /// regular expression without its slashes.
+    pub fn pattern_properties(&self) -> Option<&Properties> {
+        self.pattern_properties.as_ref()
+    }
+
+    /// Whether an instance may have properties besides those declared, true when not given.
+    pub fn additional_properties(&self) -> bool {
+        self.additional_properties.unwrap_or(true)
+    }
+
+    pub fn min_properties(&self) -> Option<u64> {
+        self.min_properties
+    }
+
+    pub fn max_properties(&self) -> Option<u64> {
+        self.max_properties
+    }
+
+    /// Whether an instance may have a property with the given name: it's declared, matches a
+    /// pattern property, or additional properties are allowed. Pattern properties are only
+    /// matched with the `regex` feature; without it any name matches them.
+    pub fn allows_property(&self, name: &str) -> bool {
+        let declared = self.properties.as_ref().is_some_and(|p| p.contains_key(name));
+        let matches_pattern = self.pattern_properties
+            .iter()
+            .flat_map(|patterns| patterns.keys())
+            .any(|pattern| matches_property_pattern(pattern, name));
+        declared || matches_pattern || self.additional_properties()
+    }
+
+    pub fn items(&self) -> Option<&RamlType> {
+        self.items.as_deref()
+    }
+
+    pub fn min_items(&self) -> Option<u64> {
+        self.min_items
+    }
+
+    pub fn max_items(&self) -> Option<u64> {
+        self.max_items
+    }
+
+    pub fn unique_items(&self) -> Option<bool> {
+        self.unique_items
+    }
+
+    pub fn example(&self) -> Option<&Example> {
+        self.example.as_ref()
+    }
+
+    pub fn examples(&self) -> Option<&Examples> {
+        self.examples.as_ref()
+    }
+
+    pub fn annotations(&self) -> Option<&Annotations> {
+        self.annotations.as_ref()
+    }
+
+    /// The facets the type declares for the types that inherit from it, with their types. Like a
+    /// property, a facet whose name ends with `?` is optional.
+    pub fn facets(&self) -> Option<&Properties> {
+        self.facets.as_ref()
+    }
+
+    /// The values the type gives to the facets declared by the types it inherits from.
+    pub fn facet_values(&self) -> Option<&FacetValues> {
+        self.facet_values.as_ref()
+    }
+
+    pub fn xml(&self) -> Option<&XmlFacets> {
+        self.xml.as_ref()
+    }
+
+    /// The property whose value names the type of an instance, among the types that inherit
+    /// from this one.
+    pub fn discriminator(&self) -> Option<&str> {
+        self.discriminator.as_deref()
+    }
+
+    /// The value of the discriminator that identifies this type. The name of the type is used
+    /// when not given.
+    pub fn discriminator_value(&self) -> Option<&str> {
+        self.discriminator_value.as_deref()
+    }
+
+    /// The format of a `datetime` type, RFC 3339 when not given.
+    pub fn format(&self) -> Option<DatetimeFormat> {
+        self.format
+    }
+
+    /// The media types a `file` type accepts, e.g. `image/png`.
+    pub fn file_types(&self) -> Option<&[String]> {
+        self.file_types.as_deref()
+    }
+
+    pub fn allowed_targets(&self) -> Option<&Vec<String>> {
+        self.allowed_targets.as_ref()
+    }
+}
+
+pub fn get_types(cursor: &mut ForwardCursor) -> Result<Types, RamlError> {
+    get_type_declarations(cursor, DeclarationKind::Type)
+}
+
+pub fn get_annotation_types(cursor: &mut ForwardCursor) -> Result<Types, RamlError> {
+    get_type_declarations(cursor, DeclarationKind::AnnotationType)
+}
+
+fn get_type_declarations(cursor: &mut ForwardCursor,
+                         kind: DeclarationKind)
+                         -> Result<Types, RamlError> {
+    let mut result: Types = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        if result.contains_key(&key.value) {
+            return Err(get_error(ErrorDef::DuplicateKey {
+                                     key: key.value,
+                                     level: HierarchyLevel::Type,
+                                 },
+                                 Some(key.marker)));
+        }
+        let location = cursor.location(key.marker);
+        let mut raml_type = get_type_declaration(cursor, &key.value, &kind)?.raml_type;
+        raml_type.location = Some(location);
+        result.insert(key.value, raml_type);
+    }
+    Ok(result)
+}
+
+/// What a type declaration declares, which decides the facets it accepts besides those of types.
+#[derive(PartialEq)]
+enum DeclarationKind {
+    Type,
+    Property,
+    AnnotationType,
+}
+
+/// A type declaration as written in the document. `required` is only accepted on properties.
+struct TypeDeclaration {
+    raml_type: RamlType,
+    required: Option<bool>,
+}
+
+pub fn get_type(cursor: &mut ForwardCursor, name: &str) -> Result<RamlType, RamlError> {
+    Ok(get_type_declaration(cursor, name, &DeclarationKind::Type)?.raml_type)
+}
+
+pub fn get_annotation_type(cursor: &mut ForwardCursor, name: &str) -> Result<RamlType, RamlError> {
+    Ok(get_type_declaration(cursor, name, &DeclarationKind::AnnotationType)?.raml_type)
+}
+
+fn get_type_declaration(cursor: &mut ForwardCursor,
+                        name: &str,
+                        kind: &DeclarationKind)
+                        -> Result<TypeDeclaration, RamlError> {
+    let mut raml_type = RamlType::default();
+    let mut required: Option<bool> = None;
+    let mut items_marker: Option<Marker> = None;
+    let mut default_marker: Option<Marker> = None;
+    let mut example_marker: Option<Marker> = None;
+    let mut examples_marker: Option<Marker> = None;
+    let mut format_marker: Option<Marker> = None;
+    let mut file_types_marker: Option<Marker> = None;
+    let mut facet_markers = FacetMarkers::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = match get_optional_mapping_start(cursor)? {
+        Some(style) => style,
+        None => {
+            // Shorthand form, e.g. `Email: string`, or an empty declaration.
+            if let Some(expression) = get_optional_scalar(cursor)? {
+                raml_type.type_marker = Some(expression.marker);
+                set_type_value(&mut raml_type, expression.value, expression.marker)?;
+            }
+            return Ok(TypeDeclaration {
+                raml_type,
+                required,
+            });
+        }
+    };
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        facet_markers.insert(key.value.clone(), cursor.peek_value_marker()?);
+        match key.value.as_str() {
+            "required" if *kind == DeclarationKind::Property => {
+                required = Some(get_boolean_value(cursor)?)
+            }
+            "allowedTargets" if *kind == DeclarationKind::AnnotationType => {
+                let targets = get_single_or_multiple_values(cursor)?;
+                raml_type.allowed_targets = Some(targets.into_iter().map(|t| t.value).collect());
+            }
+            "items" => {
+                items_marker = Some(key.marker);
+                raml_type.items = Some(Box::new(get_type(cursor, name)?));
+            }
+            _ => {
+                match key.value.as_str() {
+                    "default" => default_marker = Some(key.marker),
+                    "example" => example_marker = Some(key.marker),
+                    "examples" => examples_marker = Some(key.marker),
+                    "format" => format_marker = Some(key.marker),
+                    "fileTypes" => file_types_marker = Some(key.marker),
+                    _ => {}
+                }
+                get_type_facet(cursor, &mut raml_type, key, name)?
+            }
+        }
+    }
+    check_enum_value(raml_type.enum_values.as_ref(),
+                     "default",
+                     raml_type.default.as_ref(),
+                     default_marker)?;
+    check_enum_value(raml_type.enum_values.as_ref(),
+                     "example",
+                     raml_type.example.as_ref().and_then(|e| scalar_text(e.value())).as_ref(),
+                     example_marker)?;
+    check_facet_range("minLength",
+                      raml_type.min_length,
+                      "maxLength",
+                      raml_type.max_length,
+                      &facet_markers)?;
+    check_facet_range("minimum",
+                      raml_type.minimum,
+                      "maximum",
+                      raml_type.maximum,
+                      &facet_markers)?;
+    check_facet_range("minItems",
+                      raml_type.min_items,
+                      "maxItems",
+                      raml_type.max_items,
+                      &facet_markers)?;
+    check_facet_range("minProperties",
+                      raml_type.min_properties,
+                      "maxProperties",
+                      raml_type.max_properties,
+                      &facet_markers)?;
+    if let Some(ref example) = raml_type.example {
+        check_example_properties(&raml_type, example.value(), example_marker, name)?;
+    }
+    for example in raml_type.examples.iter().flat_map(|examples| examples.values()) {
+        check_example_properties(&raml_type, example.value(), examples_marker, name)?;
+    }
+    // Facets given before the type may turn out to have no type to be declared by.
+    if !inherits_declared_type(&raml_type) {
+        let first = raml_type.facet_values
+            .iter()
+            .flatten()
+            .min_by_key(|&(_, value)| marker_position(value.marker).0);
+        if let Some((facet, value)) = first {
+            return Err(get_error(ErrorDef::UnexpectedTypeFacet {
+                                     facet: facet.clone(),
+                                     type_name: name.to_string(),
+                                 },
+                                 Some(value.marker)));
+        }
+    }
+
+    check_built_in_facet(&raml_type, "format", format_marker, BuiltInType::Datetime, name)?;
+    check_built_in_facet(&raml_type, "fileTypes", file_types_marker, BuiltInType::File, name)?;
+    check_datetime_example(&raml_type, example_marker, name)?;
+
+    if let Some(marker) = items_marker {
+        match raml_type.type_.as_slice() {
+            [] => {}
+            [TypeExpression::Named(t)] if t == "array" => {}
+            _ => {
+                return Err(get_error(ErrorDef::ItemsOnNonArrayType { type_name: name.to_string() },
+                                     Some(marker)))
+            }
+        }
+    }
+
+    Ok(TypeDeclaration {
+        raml_type,
+        required,
+    })
+}
+
+fn get_datetime_format(cursor: &mut ForwardCursor) -> Result<DatetimeFormat, RamlError> {
+    let value = get_single_scalar(cursor)?;
+    match value.value.as_str() {
+        "rfc3339" => Ok(DatetimeFormat::Rfc3339),
+        "rfc2616" => Ok(DatetimeFormat::Rfc2616),
+        _ => {
+            Err(get_error(ErrorDef::InvalidDatetimeFormat { format: value.value },
+                          Some(value.marker)))
+        }
+    }
+}
+
+/// Checks that a facet only declared by one built-in type is given to a type based on it. A
+/// type inheriting from a declared type is checked when it's resolved.
+fn check_built_in_facet(raml_type: &RamlType,
+                        facet: &str,
+                        marker: Option<Marker>,
+                        built_in: BuiltInType,
+                        type_name: &str)
+                        -> Result<(), RamlError> {
+    match marker {
+        Some(marker) if raml_type.built_in_type() != Some(built_in) &&
+                        !inherits_declared_type(raml_type) => {
+            Err(get_error(ErrorDef::UnexpectedTypeFacet {
+                              facet: facet.to_string(),
+                              type_name: type_name.to_string(),
+                          },
+                          Some(marker)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that an example of an object type has between `minProperties` and `maxProperties`
+/// properties and, when `additionalProperties` is false, only has properties that are declared
+/// or match a pattern property. A type inheriting from a declared type may have properties
+/// declared by its parents, so its property names are not checked here.
+fn check_example_properties(raml_type: &RamlType,
+                            example: &ExampleValue,
+                            marker: Option<Marker>,
+                            type_name: &str)
+                            -> Result<(), RamlError> {
+    let entries = match *example {
+        ExampleValue::Mapping(ref entries) => entries,
+        _ => return Ok(()),
+    };
+    if !raml_type.additional_properties() && !inherits_declared_type(raml_type) {
+        let undeclared = entries.iter().find(|(name, _)| !raml_type.allows_property(name));
+        if let Some((name, _)) = undeclared {
+            return Err(get_error(ErrorDef::UndeclaredExampleProperty {
+                                     property: name.clone(),
+                                     type_name: type_name.to_string(),
+                                 },
+                                 marker));
+        }
+    }
+    let count = entries.len() as u64;
+    let too_few = raml_type.min_properties.is_some_and(|min| count < min);
+    let too_many = raml_type.max_properties.is_some_and(|max| count > max);
+    if too_few || too_many {
+        return Err(get_error(ErrorDef::ExamplePropertyCount {
+                                 count,
+                                 type_name: type_name.to_string(),
+                             },
+                             marker));
+    }
+    Ok(())
+}
+
+/// Checks that the example of a `datetime` type in the RFC 3339 format is a valid datetime.
+fn check_datetime_example(raml_type: &RamlType,
+                          marker: Option<Marker>,
+                          type_name: &str)
+                          -> Result<(), RamlError> {
+    if raml_type.built_in_type() != Some(BuiltInType::Datetime) ||
+       raml_type.format == Some(DatetimeFormat::Rfc2616) {
+        return Ok(());
+    }
+    match raml_type.example.as_ref().and_then(|e| scalar_text(e.value())) {
+        Some(ref value) if !is_rfc3339_datetime(value) => {
+            Err(get_error(ErrorDef::InvalidDatetimeExample {
+                              value: value.clone(),
+                              type_name: type_name.to_string(),
+                          },
+                          marker))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether a value has the syntax of an RFC 3339 datetime, e.g. `2016-02-28T16:41:41.090Z`.
+fn is_rfc3339_datetime(value: &str) -> bool {
+    // Whether the value matches a pattern in which `9` stands for any digit.
+    let fixed = |s: &str, pattern: &str| {
+        s.len() == pattern.len() &&
+        s.bytes().zip(pattern.bytes()).all(|(b, p)| b == p || p == b'9' && b.is_ascii_digit())
+    };
+    if value.len() < 20 || !value.is_ascii() {
+        return false;
+    }
+    let (date_time, rest) = value.split_at(19);
+    if !fixed(&date_time[..10], "9999-99-99") || !fixed(&date_time[11..], "99:99:99") ||
+       !"Tt".contains(&date_time[10..11]) {
+        return false;
+    }
+    let offset = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            if end == 0 {
+                return false;
+            }
+            &rest[end..]
+        }
+        None => rest,
+    };
+    offset == "Z" || offset == "z" ||
+    (offset.len() == 6 && "+-".contains(&offset[..1]) && fixed(&offset[1..], "99:99"))
+}
+
+/// Sets the type of a declaration from the value of its type, either a schema or a type
+/// expression.
+fn set_type_value(raml_type: &mut RamlType,
+                  value: String,
+                  marker: Marker)
+                  -> Result<(), RamlError> {
+    let kind = match value.trim_start().chars().next() {
+        Some('{') | Some('[') => Some(SchemaKind::Json),
+        Some('<') => Some(SchemaKind::Xsd),
+        _ => None,
+    };
+    match kind {
+        Some(kind) => {
+            raml_type.schema = Some(ExternalSchema {
+                kind,
+                raw: value,
+            })
+        }
+        None => {
+            let expression = parse_type_expression(&value, marker)?;
+            set_type_expression(raml_type, expression);
+        }
+    }
+    Ok(())
+}
+
+/// Sets the type of a declaration, expanding array expressions such as `Person[]` into an
+/// array type whose items are of the element type.
+fn set_type_expression(raml_type: &mut RamlType, expression: TypeExpression) {
+    match expression {
+        TypeExpression::Array(item_expression) => {
+            let mut items = RamlType::default();
+            set_type_expression(&mut items, *item_expression);
+            raml_type.type_ = vec![TypeExpression::Named("array".to_string())];
+            raml_type.items = Some(Box::new(items));
+        }
+        _ => raml_type.type_ = vec![expression],
+    }
+}
+
+pub fn get_properties(cursor: &mut ForwardCursor) -> Result<Properties, RamlError> {
+    let mut result: Properties = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        let (name, property) = get_property(cursor, key)?;
+        result.insert(name, property);
+    }
+    Ok(result)
+}
+
+/// Reads the properties of an object type. A property whose name is wrapped in slashes, e.g.
+/// `/^note_\d+$/`, is a pattern property, which is returned separately keyed by its regular
+/// expression. With the `regex` feature, a pattern that doesn't compile is an error.
+fn get_object_properties(cursor: &mut ForwardCursor,
+                         type_name: &str)
+                         -> Result<(Properties, Properties), RamlError> {
+    let mut properties: Properties = HashMap::new();
+    let mut pattern_properties: Properties = HashMap::new();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        let pattern = match get_property_pattern(&key.value) {
+            Some(pattern) => pattern.to_string(),
+            None => {
+                let (name, property) = get_property(cursor, key)?;
+                properties.insert(name, property);
+                continue;
+            }
+        };
+        check_property_pattern(&pattern, type_name, key.marker)?;
+        let (_, mut property) = get_property(cursor, key)?;
+        // No instance has to have a property matching the pattern.
+        property.required = false;
+        pattern_properties.insert(pattern, property);
+    }
+    Ok((properties, pattern_properties))
+}
+
+/// Whether `name` matches the regular expression of a pattern property. Without the `regex`
+/// feature patterns can't be compiled, so no name matches them.
+#[cfg(feature = "regex")]
+fn matches_property_pattern(pattern: &str, name: &str) -> bool {
+    Regex::new(pattern).is_ok_and(|regex| regex.is_match(name))
+}
+
+#[cfg(not(feature = "regex"))]
+fn matches_property_pattern(_pattern: &str, _name: &str) -> bool {
+    false
+}
+
+#[cfg(feature = "regex")]
+fn check_property_pattern(pattern: &str,
+                          type_name: &str,
+                          marker: Marker)
+                          -> Result<(), RamlError> {
+    match Regex::new(pattern) {
+        Ok(_) => Ok(()),
+        Err(_) => {
+            Err(get_error(ErrorDef::InvalidPropertyPattern {
+                              pattern: pattern.to_string(),
+                              type_name: type_name.to_string(),
+                          },
+                          Some(marker)))
+        }
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+fn check_property_pattern(pattern: &str,
+                          type_name: &str,
+                          marker: Marker)
+                          -> Result<(), RamlError> {
+    Err(get_error(ErrorDef::UnsupportedPropertyPattern {
+                      pattern: pattern.to_string(),
+                      type_name: type_name.to_string(),
+                  },
+                  Some(marker)))
+}
+
+/// The regular expression of a pattern property, the name without its slashes.
+fn get_property_pattern(name: &str) -> Option<&str> {
+    if name.len() > 2 && name.starts_with('/') && name.ends_with('/') {
+        Some(&name[1..name.len() - 1])
+    } else {
+        None
+    }
+}
+
+fn get_property(cursor: &mut ForwardCursor,
+                key: MappingKey)
+                -> Result<(String, Property), RamlError> {
+    let location = cursor.location(key.marker);
+    let declaration = get_type_declaration(cursor, &key.value, &DeclarationKind::Property)?;
+
+    // A trailing `?` marks the property as optional, unless `required` is given explicitly
+    // in which case the question mark is part of the name.
+    let (name, required) = match declaration.required {
+        Some(required) => (key.value, required),
+        None if key.value.ends_with('?') => (key.value[..key.value.len() - 1].to_string(), false),
+        None => (key.value, true),
+    };
+    let mut property_type = declaration.raml_type;
+    property_type.location = Some(location);
+    Ok((name,
+        Property {
+            required,
+            property_type,
+        }))
+}
+
+/// Reads the values of an `enum` facet, in declaration order.
+pub fn get_enum_values(cursor: &mut ForwardCursor,
+                       marker: Marker)
+                       -> Result<Vec<String>, RamlError> {
+    let values = get_sequence_values(cursor)?;
+    if values.is_empty() {
+        return Err(get_error(ErrorDef::EmptyEnum, Some(marker)));
+    }
+    Ok(values.into_iter().map(|e| e.value).collect())
+}
+
+/// Checks that a `default` or `example` value given alongside an enum is one of its values.
+pub fn check_enum_value(enum_values: Option<&Vec<String>>,
+                        facet: &str,
+                        value: Option<&String>,
+                        marker: Option<Marker>)
+                        -> Result<(), RamlError> {
+    match (enum_values, value) {
+        (Some(enum_values), Some(value)) if !enum_values.contains(value) => {
+            Err(get_error(ErrorDef::ValueNotInEnum {
+                              facet: facet.to_string(),
+                              value: value.clone(),
+                          },
+                          marker))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The positions of the values of the facets of a declaration, by facet name.
+pub type FacetMarkers = HashMap<String, Marker>;
+
+/// Checks that a lower bound facet is not greater than its upper bound. The error is at the
+/// value of whichever of the two facets is given last.
+pub fn check_facet_range<T: PartialOrd>(min_facet: &str,
+                                        min: Option<T>,
+                                        max_facet: &str,
+                                        max: Option<T>,
+                                        markers: &FacetMarkers)
+                                        -> Result<(), RamlError> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => {
+            let marker = [min_facet, max_facet]
+                .iter()
+                .filter_map(|facet| markers.get(*facet).cloned())
+                .max_by_key(|&marker| marker_position(marker).0);
+            Err(get_error(ErrorDef::InvalidFacetRange {
+                              min_facet: min_facet.to_string(),
+                              max_facet: max_facet.to_string(),
+                          },
+                          marker))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn get_type_facet(cursor: &mut ForwardCursor,
+                  raml_type: &mut RamlType,
+                  key: MappingKey,
+                  type_name: &str)
+                  -> Result<(), RamlError> {
+    match key.value.as_str() {
+        "type" => {
+            raml_type.type_marker = Some(key.marker);
+            let mut expressions = get_single_or_multiple_values(cursor)?;
+            if expressions.len() == 1 {
+                let expression = expressions.remove(0);
+                set_type_value(raml_type, expression.value, expression.marker)?;
+            } else {
+                raml_type.type_ = expressions.iter()
+                    .map(|e| parse_type_expression(&e.value, e.marker))
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+        "displayName" => raml_type.display_name = Some(get_single_value(cursor)?),
+        "description" => raml_type.description = get_optional_value(cursor)?,
+        "default" => raml_type.default = Some(get_single_value(cursor)?),
+        "pattern" => raml_type.pattern = Some(get_single_value(cursor)?),
+        "minLength" => raml_type.min_length = Some(get_unsigned_value(cursor)?),
+        "maxLength" => raml_type.max_length = Some(get_unsigned_value(cursor)?),
+        "minimum" => raml_type.minimum = Some(get_number_value(cursor)?),
+        "maximum" => raml_type.maximum = Some(get_number_value(cursor)?),
+        "multipleOf" => raml_type.multiple_of = Some(get_number_value(cursor)?),
+        "enum" => raml_type.enum_values = Some(get_enum_values(cursor, key.marker)?),
+        "properties" => {
+            let (properties, pattern_properties) = get_object_properties(cursor, type_name)?;
+            raml_type.properties = Some(properties);
+            if !pattern_properties.is_empty() {
+                raml_type.pattern_properties = Some(pattern_properties);
+            }
+        }
+        "additionalProperties" => {
+            raml_type.additional_properties = Some(get_boolean_value(cursor)?)
+        }
+        "minProperties" => raml_type.min_properties = Some(get_unsigned_value(cursor)?),
+        "maxProperties" => raml_type.max_properties = Some(get_unsigned_value(cursor)?),
+        "minItems" => raml_type.min_items = Some(get_unsigned_value(cursor)?),
+        "maxItems" => raml_type.max_items = Some(get_unsigned_value(cursor)?),
+        "uniqueItems" => raml_type.unique_items = Some(get_boolean_value(cursor)?),
+        "example" => raml_type.example = Some(get_example(cursor)?),
+        "examples" => raml_type.examples = Some(get_examples(cursor)?),
+        "facets" => raml_type.facets = Some(get_properties(cursor)?),
+        "xml" => raml_type.xml = Some(get_xml_facets(cursor, type_name)?),
+        "discriminator" => raml_type.discriminator = Some(get_single_value(cursor)?),
+        "discriminatorValue" => raml_type.discriminator_value = Some(get_single_value(cursor)?),
+        "format" => raml_type.format = Some(get_datetime_format(cursor)?),
+        "fileTypes" => {
+            let file_types = get_single_or_multiple_values(cursor)?;
+            raml_type.file_types = Some(file_types.into_iter().map(|t| t.value).collect());
+        }
+        name if get_annotation_name(name).is_some() => {
+            get_annotation(cursor, &mut raml_type.annotations, key)?
+        }
+        // Only a type inheriting from a declared type can be given the facets it declares,
+        // which are checked once all the types are read.
+        _ if !raml_type.type_.is_empty() && !inherits_declared_type(raml_type) => {
+            return Err(get_error(ErrorDef::UnexpectedTypeFacet {
+                                     facet: key.value,
+                                     type_name: type_name.to_string(),
+                                 },
+                                 Some(key.marker)));
+        }
+        _ => {
+            cursor.expect(TokenTypeDef::Value)?;
+            let value = FacetValue {
+                value: get_example_value(cursor)?,
+                marker: key.marker,
+            };
+            raml_type.facet_values.get_or_insert_with(HashMap::new).insert(key.value, value);
+        }
+    }
+    Ok(())
+}
+
+fn get_xml_facets(cursor: &mut ForwardCursor, type_name: &str) -> Result<XmlFacets, RamlError> {
+    let mut xml = XmlFacets::default();
+    cursor.expect(TokenTypeDef::Value)?;
+    let style = get_mapping_start(cursor)?;
+    while let Some(key) = get_mapping_key(cursor, &style)? {
+        match key.value.as_str() {
+            "attribute" => xml.attribute = Some(get_boolean_value(cursor)?),
+            "wrapped" => xml.wrapped = Some(get_boolean_value(cursor)?),
+            "name" => xml.name = Some(get_single_value(cursor)?),
+            "namespace" => xml.namespace = Some(get_single_value(cursor)?),
+            "prefix" => xml.prefix = Some(get_single_value(cursor)?),
+            _ => {
+                return Err(get_error(ErrorDef::UnexpectedTypeFacet {
+                                         facet: format!("xml.{}", key.value),
+                                         type_name: type_name.to_string(),
+                                     },
+                                     Some(key.marker)))
+            }
+        }
+    }
+    Ok(xml)
+}
+
+/// Whether a type inherits from a type other than the built-in types, which may declare facets.
+fn inherits_declared_type(raml_type: &RamlType) -> bool {
+    raml_type.type_.iter().any(|parent| match *parent {
+        TypeExpression::Named(ref name) => !is_built_in_type(name),
+        _ => false,
+    })
+}
+
+/// Checks the annotations applied to the declared types and their properties.
+pub fn check_type_annotations(types: Option<&Types>,
+                              annotation_types: Option<&Types>,
+                              libraries: Option<&Libraries>)
+                              -> Result<(), RamlError> {
+    for raml_type in types.into_iter().flat_map(|types| types.values()) {
+        check_declaration_annotations(raml_type, annotation_types, libraries)?;
+    }
+    Ok(())
+}
+
+fn check_declaration_annotations(raml_type: &RamlType,
+                                 annotation_types: Option<&Types>,
+                                 libraries: Option<&Libraries>)
+                                 -> Result<(), RamlError> {
+    check_annotations(raml_type.annotations.as_ref(),
+                      annotation_types,
+                      libraries,
+                      "TypeDeclaration")?;
+    let properties = raml_type.properties.iter().chain(raml_type.pattern_properties.iter());
+    for property in properties.flat_map(|properties| properties.values()) {
+        check_declaration_annotations(&property.property_type, annotation_types, libraries)?;
+    }
+    if let Some(ref items) = raml_type.items {
+        check_declaration_annotations(items, annotation_types, libraries)?;
+    }
+    Ok(())
+}
+
+/// The declared types, and their properties and items, to which `find` gives an annotation, by
+/// name.
+pub fn get_annotated_types<'a, F>(types: Option<&'a Types>,
+                                  find: &F,
+                                  nodes: &mut Vec<AnnotatedNode<'a>>)
+    where F: Fn(Option<&'a Annotations>) -> Option<&'a AnnotationValue>
+{
+    let mut names: Vec<&String> = types.into_iter().flat_map(|types| types.keys()).collect();
+    names.sort();
+    for name in names {
+        add_annotated_declaration(&types.unwrap()[name], name.clone(), find, nodes);
+    }
+}
+
+fn add_annotated_declaration<'a, F>(raml_type: &'a RamlType,
+                                    path: String,
+                                    find: &F,
+                                    nodes: &mut Vec<AnnotatedNode<'a>>)
+    where F: Fn(Option<&'a Annotations>) -> Option<&'a AnnotationValue>
+{
+    if let Some(value) = find(raml_type.annotations.as_ref()) {
+        nodes.push(AnnotatedNode::new(path.clone(), "TypeDeclaration", value));
+    }
+    let properties = raml_type.properties.iter().chain(raml_type.pattern_properties.iter());
+    for properties in properties {
+        let mut names: Vec<&String> = properties.keys().collect();
+        names.sort();
+        for name in names {
+            add_annotated_declaration(&properties[name].property_type,
+                                      format!("{}.{}", path, name),
+                                      find,
+                                      nodes);
+        }
+    }
+    if let Some(ref items) = raml_type.items {
+        add_annotated_declaration(items, format!("{}[]", path), find, nodes);
+    }
+}
+
+/// Checks that the declared types only give values to the facets declared by the types they
+/// inherit from, and give a value to each required facet. A type that another declared type
+/// inherits from may leave the required facets to be given by the types inheriting from it.
+pub fn check_type_facets(types: Option<&Types>,
+                         libraries: Option<&Libraries>)
+                         -> Result<(), RamlError> {
+    let types = match types {
+        Some(types) => types,
+        None => return Ok(()),
+    };
+    let mut names: Vec<&String> = types.keys().collect();
+    names.sort();
+    for name in names {
+        let raml_type = &types[name];
+        let is_inherited = types.values().any(|t| {
+            t.type_.iter().any(|parent| *parent == TypeExpression::Named(name.clone()))
+        });
+        check_declaration_facets(raml_type, name, Some(types), libraries, !is_inherited)?;
+    }
+    Ok(())
+}
+
+/// Checks that the types the declared types inherit from are built in, declared, or declared in
+/// a library.
+pub fn check_parent_types(types: Option<&Types>,
+                          libraries: Option<&Libraries>)
+                          -> Result<(), RamlError> {
+    let declared = match types {
+        Some(declared) => declared,
+        None => return Ok(()),
+    };
+    let mut names: Vec<&String> = declared.keys().collect();
+    names.sort();
+    for name in names {
+        let raml_type = &declared[name];
+        for expression in &raml_type.type_ {
+            check_parent_expression(expression, name, raml_type, types, libraries)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that no declared type inherits from itself, directly or through the types it inherits
+/// from. The error gives the cycle from the first type in it by name, and points at the type
+/// facet that closes it.
+pub fn check_circular_inheritance(types: Option<&Types>) -> Result<(), RamlError> {
+    let types = match types {
+        Some(types) => types,
+        None => return Ok(()),
+    };
+    let mut names: Vec<&String> = types.keys().collect();
+    names.sort();
+    for name in names {
+        if let Some(cycle) = find_inheritance_cycle(types, name, &mut vec![]) {
+            let marker = types[&cycle[cycle.len() - 2]].type_marker;
+            return Err(get_error(ErrorDef::CircularTypeInheritance { cycle }, marker));
+        }
+    }
+    Ok(())
+}
+
+fn find_inheritance_cycle(types: &Types,
+                          name: &str,
+                          path: &mut Vec<String>)
+                          -> Option<Vec<String>> {
+    if let Some(position) = path.iter().position(|n| n == name) {
+        let mut cycle = path[position..].to_vec();
+        cycle.push(name.to_string());
+        return Some(cycle);
+    }
+    let declared = types.get(name)?;
+    path.push(name.to_string());
+    for parent in &declared.type_ {
+        if let TypeExpression::Named(ref parent_name) = *parent {
+            if let Some(cycle) = find_inheritance_cycle(types, parent_name, path) {
+                return Some(cycle);
+            }
+        }
+    }
+    path.pop();
+    None
+}
+
+fn check_parent_expression(expression: &TypeExpression,
+                           type_name: &str,
+                           raml_type: &RamlType,
+                           types: Option<&Types>,
+                           libraries: Option<&Libraries>)
+                           -> Result<(), RamlError> {
+    match *expression {
+        TypeExpression::Named(ref name) => {
+            if is_built_in_type(name) {
+                return Ok(());
+            }
+            let declaration =
+                find_declaration(name, types, libraries, Library::types, raml_type.type_marker)?;
+            match declaration {
+                Some(_) => Ok(()),
+                None => {
+                    Err(get_error(ErrorDef::UndeclaredType {
+                                      name: name.clone(),
+                                      type_name: type_name.to_string(),
+                                  },
+                                  raml_type.type_marker))
+                }
+            }
+        }
+        TypeExpression::Array(ref items) => {
+            check_parent_expression(items, type_name, raml_type, types, libraries)
+        }
+        TypeExpression::Union(ref members) => {
+            for member in members {
+                check_parent_expression(member, type_name, raml_type, types, libraries)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn check_declaration_facets(raml_type: &RamlType,
+                            type_name: &str,
+                            types: Option<&Types>,
+                            libraries: Option<&Libraries>,
+                            requires_facets: bool)
+                            -> Result<(), RamlError> {
+    let mut inherited = InheritedFacets::default();
+    get_inherited_facets(raml_type, types, libraries, &mut vec![], &mut inherited);
+    let mut values: Vec<(&String, &FacetValue)> = raml_type.facet_values.iter().flatten().collect();
+    values.sort_by_key(|&(_, value)| marker_position(value.marker).0);
+    for (facet, value) in values {
+        if !inherited.declared.contains_key(facet) {
+            return Err(get_error(ErrorDef::UnexpectedTypeFacet {
+                                     facet: facet.clone(),
+                                     type_name: type_name.to_string(),
+                                 },
+                                 Some(value.marker)));
+        }
+    }
+    if requires_facets {
+        let given = |facet: &String| {
+            inherited.given.contains(facet) ||
+            raml_type.facet_values.as_ref().is_some_and(|values| values.contains_key(facet))
+        };
+        let mut missing: Vec<&String> = inherited.declared
+            .iter()
+            .filter(|&(facet, &required)| required && !given(facet))
+            .map(|(facet, _)| *facet)
+            .collect();
+        missing.sort();
+        if let Some(facet) = missing.first() {
+            return Err(get_error(ErrorDef::MissingTypeFacet {
+                                     facet: (*facet).clone(),
+                                     type_name: type_name.to_string(),
+                                 },
+                                 raml_type.type_marker));
+        }
+    }
+    let properties = raml_type.properties.iter().chain(raml_type.pattern_properties.iter());
+    for (name, property) in properties.flatten() {
+        check_declaration_facets(&property.property_type, name, types, libraries, false)?;
+    }
+    if let Some(ref items) = raml_type.items {
+        check_declaration_facets(items, type_name, types, libraries, false)?;
+    }
+    Ok(())
+}
+
+// The facets declared by the types a type inherits from, and whether each is required, and the
+// facets those types give values to.
+#[derive(Default)]
+struct InheritedFacets<'a> {
+    declared: HashMap<&'a String, bool>,
+    given: HashSet<&'a String>,
+}
+
+fn get_inherited_facets<'a>(raml_type: &'a RamlType,
+                            types: Option<&'a Types>,
+                            libraries: Option<&'a Libraries>,
+                            path: &mut Vec<&'a str>,
+                            inherited: &mut InheritedFacets<'a>) {
+    for parent in &raml_type.type_ {
+        let name = match *parent {
+            TypeExpression::Named(ref name) if !is_built_in_type(name) => name.as_str(),
+            _ => continue,
+        };
+        // Circular inheritance is reported when the type is resolved.
+        if path.contains(&name) {
+            continue;
+        }
+        // As are parents that aren't declared.
+        let (parent_type, types, libraries) = match find_library(name, libraries, None) {
+            Ok(Some((library, name))) => {
+                (library.types().and_then(|types| types.get(name)),
+                 library.types(),
+                 library.uses())
+            }
+            Ok(None) => (types.and_then(|types| types.get(name)), types, libraries),
+            Err(_) => continue,
+        };
+        let parent_type = match parent_type {
+            Some(parent_type) => parent_type,
+            None => continue,
+        };
+        for (facet, declaration) in parent_type.facets.iter().flatten() {
+            inherited.declared.entry(facet).or_insert(declaration.required);
+        }
+        inherited.given.extend(parent_type.facet_values.iter().flatten().map(|(facet, _)| facet));
+        path.push(name);
+        get_inherited_facets(parent_type, types, libraries, path, inherited);
+        path.pop();
+    }
+}
+
+/// A declared type with the facets and properties it inherits merged into it.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ResolvedType {
+    name: String,
+    ancestors: Vec<String>,
+    raml_type: RamlType,
+}
+
+impl ResolvedType {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The declared types inherited from, nearest first.
+    pub fn ancestors(&self) -> &[String] {
+        &self.ancestors
+    }
+
+    /// The merged type, whose type is the built-in type the hierarchy is based on.
+    pub fn raml_type(&self) -> &RamlType {
+        &self.raml_type
+    }
+}
+
+/// Resolves a declared type. A name qualified with a library namespace, e.g. `myLib.Person`, is
+/// resolved among the types of that library.
+pub fn resolve_type(types: Option<&Types>,
+                    libraries: Option<&Libraries>,
+                    name: &str)
+                    -> Result<ResolvedType, RamlError> {
+    let mut ancestors = vec![];
+    let raml_type = resolve_declared_type(types, libraries, name, &mut vec![], &mut ancestors)?;
+    Ok(ResolvedType {
+        name: name.to_string(),
+        ancestors,
+        raml_type,
+    })
+}
+
+fn resolve_declared_type(types: Option<&Types>,
+                         libraries: Option<&Libraries>,
+                         name: &str,
+                         path: &mut Vec<String>,
+                         ancestors: &mut Vec<String>)
+                         -> Result<RamlType, RamlError> {
+    if let Some((library, name)) = find_library(name, libraries, None)? {
+        return resolve_declared_type(library.types(), library.uses(), name, path, ancestors);
+    }
+    if let Some(position) = path.iter().position(|n| n == name) {
+        let mut cycle = path[position..].to_vec();
+        cycle.push(name.to_string());
+        return Err(get_error(ErrorDef::CircularTypeInheritance { cycle }, None));
+    }
+    let declared = match types.and_then(|types| types.get(name)) {
+        Some(declared) => declared,
+        None => {
+            return Err(get_error(ErrorDef::UndeclaredType {
+                                     name: name.to_string(),
+                                     type_name: name.to_string(),
+                                 },
+                                 None))
+        }
+    };
+
+    path.push(name.to_string());
+    let mut resolved = RamlType::default();
+    for parent in &declared.type_ {
+        match *parent {
+            TypeExpression::Named(ref parent_name) if is_declared(types, parent_name) => {
+                if !ancestors.contains(parent_name) {
+                    ancestors.push(parent_name.clone());
+                }
+                let inherited =
+                    resolve_declared_type(types, libraries, parent_name, path, ancestors)?;
+                resolved.type_ = inherited.type_.clone();
+                merge_facets(&mut resolved, &inherited, name, false)?;
+            }
+            TypeExpression::Named(ref parent_name) if !is_built_in_type(parent_name) => {
+                return Err(get_error(ErrorDef::UndeclaredType {
+                                         name: parent_name.clone(),
+                                         type_name: name.to_string(),
+                                     },
+                                     declared.type_marker));
+            }
+            _ => resolved.type_ = vec![parent.clone()],
+        }
+    }
+    path.pop();
+
+    if resolved.type_.is_empty() {
+        let base_type = if declared.properties.is_some() { "object" } else { "string" };
+        resolved.type_ = vec![TypeExpression::Named(base_type.to_string())];
+    }
+    merge_facets(&mut resolved, declared, name, true)?;
+    // The display name and discriminator value identify the declared type, so they aren't
+    // inherited.
+    resolved.display_name = declared.display_name.clone();
+    resolved.discriminator_value = declared.discriminator_value.clone();
+    check_discriminator(&resolved, name)?;
+    Ok(resolved)
+}
+
+/// Checks that the discriminator of a resolved type names one of its required string
+/// properties.
+fn check_discriminator(resolved: &RamlType, type_name: &str) -> Result<(), RamlError> {
+    let discriminator = match resolved.discriminator {
+        Some(ref discriminator) => discriminator,
+        None => return Ok(()),
+    };
+    let property = resolved.properties.as_ref().and_then(|p| p.get(discriminator));
+    let is_string = |property: &Property| match property.property_type.type_.as_slice() {
+        [] => true,
+        [TypeExpression::Named(ref name)] => name == "string",
+        _ => false,
+    };
+    match property {
+        Some(property) if property.required && is_string(property) => Ok(()),
+        _ => {
+            Err(get_error(ErrorDef::InvalidDiscriminator {
+                              discriminator: discriminator.clone(),
+                              type_name: type_name.to_string(),
+                          },
+                          None))
+        }
+    }
+}
+
+/// Finds the declared type inheriting from `parent` that an instance whose discriminator has
+/// the given value is an instance of.
+pub fn find_subtype_by_discriminator<'a>(types: Option<&'a Types>,
+                                         libraries: Option<&Libraries>,
+                                         parent: &str,
+                                         value: &str)
+                                         -> Option<(&'a str, &'a RamlType)> {
+    let declared = types?;
+    let mut names: Vec<&String> = declared.keys().collect();
+    names.sort();
+    names.into_iter()
+        .filter(|name| {
+            resolve_type(types, libraries, name)
+                .map(|resolved| resolved.ancestors.iter().any(|a| a == parent))
+                .unwrap_or(false)
+        })
+        .map(|name| (name.as_str(), &declared[name]))
+        .find(|&(name, raml_type)| raml_type.discriminator_value().unwrap_or(name) == value)
+}
+
+/// Whether a parent type is declared, or else is qualified and looked up in a library.
+fn is_declared(types: Option<&Types>, name: &str) -> bool {
+    name.contains('.') || types.is_some_and(|types| types.contains_key(name))
+}
+
+pub fn is_built_in_type(name: &str) -> bool {
+    BuiltInType::from_name(name).is_some()
+}
+
+/// Merges the facets of `source` over those already resolved. When `restrict` is set the source
+/// is the child type, which may narrow the inherited facets but not widen them.
+fn merge_facets(resolved: &mut RamlType,
+                source: &RamlType,
+                type_name: &str,
+                restrict: bool)
+                -> Result<(), RamlError> {
+    if restrict {
+        check_not_widened("minLength", resolved.min_length, source.min_length, type_name, true)?;
+        check_not_widened("maxLength", resolved.max_length, source.max_length, type_name, false)?;
+        check_not_widened("minimum", resolved.minimum, source.minimum, type_name, true)?;
+        check_not_widened("maximum", resolved.maximum, source.maximum, type_name, false)?;
+        check_not_widened("minItems", resolved.min_items, source.min_items, type_name, true)?;
+        check_not_widened("maxItems", resolved.max_items, source.max_items, type_name, false)?;
+        check_not_widened("minProperties",
+                          resolved.min_properties,
+                          source.min_properties,
+                          type_name,
+                          true)?;
+        check_not_widened("maxProperties",
+                          resolved.max_properties,
+                          source.max_properties,
+                          type_name,
+                          false)?;
+        if let (Some(inherited), Some(values)) = (&resolved.enum_values, &source.enum_values) {
+            if values.iter().any(|v| !inherited.contains(v)) {
+                return Err(widened_facet_error("enum", type_name));
+            }
+        }
+    }
+
+    merge_facet(&mut resolved.description, &source.description);
+    merge_facet(&mut resolved.default, &source.default);
+    merge_facet(&mut resolved.pattern, &source.pattern);
+    merge_facet(&mut resolved.min_length, &source.min_length);
+    merge_facet(&mut resolved.max_length, &source.max_length);
+    merge_facet(&mut resolved.minimum, &source.minimum);
+    merge_facet(&mut resolved.maximum, &source.maximum);
+    merge_facet(&mut resolved.multiple_of, &source.multiple_of);
+    merge_facet(&mut resolved.enum_values, &source.enum_values);
+    merge_facet(&mut resolved.items, &source.items);
+    merge_facet(&mut resolved.min_items, &source.min_items);
+    merge_facet(&mut resolved.max_items, &source.max_items);
+    merge_facet(&mut resolved.unique_items, &source.unique_items);
+    merge_facet(&mut resolved.example, &source.example);
+    merge_facet(&mut resolved.examples, &source.examples);
+    merge_facet(&mut resolved.xml, &source.xml);
+    merge_facet(&mut resolved.discriminator, &source.discriminator);
+    merge_facet(&mut resolved.format, &source.format);
+    merge_facet(&mut resolved.file_types, &source.file_types);
+    merge_map(&mut resolved.properties, &source.properties);
+    merge_map(&mut resolved.pattern_properties, &source.pattern_properties);
+    merge_facet(&mut resolved.additional_properties, &source.additional_properties);
+    merge_facet(&mut resolved.min_properties, &source.min_properties);
+    merge_facet(&mut resolved.max_properties, &source.max_properties);
+    merge_map(&mut resolved.facets, &source.facets);
+    merge_map(&mut resolved.facet_values, &source.facet_values);
+    Ok(())
+}
+
+fn merge_map<T: Clone>(resolved: &mut Option<HashMap<String, T>>,
+                       source: &Option<HashMap<String, T>>) {
+    if let Some(ref source) = *source {
+        let merged = resolved.get_or_insert_with(HashMap::new);
+        for (name, value) in source {
+            merged.insert(name.clone(), value.clone());
+        }
+    }
+}
+
+fn merge_facet<T: Clone>(resolved: &mut Option<T>, source: &Option<T>) {
+    if source.is_some() {
+        resolved.clone_from(source);
+    }
+}
+
+fn check_not_widened<T: PartialOrd>(facet: &str,
+                                    inherited: Option<T>,
+                                    value: Option<T>,
+                                    type_name: &str,
+                                    is_lower_bound: bool)
+                                    -> Result<(), RamlError> {
+    let (inherited, value) = match (inherited, value) {
+        (Some(inherited), Some(value)) => (inherited, value),
+        _ => return Ok(()),
+    };
+    let widened = if is_lower_bound { value < inherited } else { value > inherited };
+    if widened {
+        return Err(widened_facet_error(facet, type_name));
+    }
+    Ok(())
+}
+
+fn widened_facet_error(facet: &str, type_name: &str) -> RamlError {
+    get_error(ErrorDef::WidenedTypeFacet {
+                  facet: facet.to_string(),
+                  type_name: type_name.to_string(),
+              },
+              None)
+}
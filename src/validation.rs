@@ -0,0 +1,451 @@
+use error_definitions::{ErrorDef, ErrorKind, RamlError, get_error, HierarchyLevel};
+use parser::{check_secured_by, get_uri_template_variables, Raml, SecuredByEntries};
+use resource::Resource;
+use method::{Method, METHOD_NAMES};
+use body::Bodies;
+use types::{is_built_in_type, RamlType};
+use type_expression::TypeExpression;
+use parameter::NamedParameters;
+use library::{find_declaration, Library};
+use location::SourceLocation;
+use media_type::MediaType;
+use options::ValidationOptions;
+
+/// A rule checked by `Raml::validate`.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum ValidationRule {
+    /// Every `securedBy` entry refers to a declared security scheme.
+    SecuredByResolves,
+    /// Every template variable of the baseUri and of the resource uris is declared as a
+    /// parameter, other than `version`. Undeclared variables are implicit string parameters, so
+    /// this rule is only checked when enabled, and gives warnings.
+    UriParametersDeclared,
+    /// Every media type of a body has the form `type/subtype`. The default media types of the
+    /// API are checked as it is parsed or built.
+    MediaTypeFormat,
+    /// A baseUri that refers to `{version}` comes with a `version`.
+    BaseUriVersion,
+    /// Every documentation entry has a title that isn't blank.
+    DocumentationTitle,
+    /// Every type a type, property or body refers to is declared or built in. This includes the
+    /// bodies of the responses that describe a security scheme. A type declared with a JSON or
+    /// XML schema is only referred to by bodies, and no type inherits from itself.
+    TypeReferencesResolve,
+}
+
+impl ValidationRule {
+    /// The identifier of the rule, e.g. `secured-by-resolves`.
+    pub fn id(&self) -> &'static str {
+        match *self {
+            ValidationRule::SecuredByResolves => "secured-by-resolves",
+            ValidationRule::UriParametersDeclared => "uri-parameters-declared",
+            ValidationRule::MediaTypeFormat => "media-type-format",
+            ValidationRule::BaseUriVersion => "base-uri-version",
+            ValidationRule::DocumentationTitle => "documentation-title",
+            ValidationRule::TypeReferencesResolve => "type-references-resolve",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match *self {
+            ValidationRule::UriParametersDeclared => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Whether the rule is checked unless `ValidationOptions::disabled_rules` disables it.
+    /// Other rules are only checked when `ValidationOptions::enabled_rules` enables them.
+    pub fn is_enabled_by_default(&self) -> bool {
+        *self != ValidationRule::UriParametersDeclared
+    }
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum Severity {
+    /// The document doesn't conform to the specification.
+    Error,
+    /// The document conforms, but probably isn't what was meant.
+    Warning,
+}
+
+/// A node of a document that breaks a validation rule.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct RamlViolation {
+    rule: ValidationRule,
+    message: String,
+    location: Option<SourceLocation>,
+}
+
+impl RamlViolation {
+    pub fn rule(&self) -> ValidationRule {
+        self.rule
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.rule.severity()
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// Where the node breaking the rule, or the closest node enclosing it, is declared, when the
+    /// document was parsed.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+}
+
+// The violations found by the rule being checked.
+struct Violations {
+    rule: ValidationRule,
+    violations: Vec<RamlViolation>,
+}
+
+impl Violations {
+    fn add(&mut self, error: RamlError, location: Option<SourceLocation>) {
+        self.violations.push(RamlViolation {
+            rule: self.rule,
+            message: error.message().to_string(),
+            location,
+        });
+    }
+}
+
+type ValidationCheck = fn(&Raml, &mut Violations);
+
+const VALIDATION_RULES: [(ValidationRule, ValidationCheck); 6] =
+    [(ValidationRule::SecuredByResolves, check_secured_by_resolves),
+     (ValidationRule::UriParametersDeclared, check_uri_parameters_declared),
+     (ValidationRule::MediaTypeFormat, check_media_type_format),
+     (ValidationRule::BaseUriVersion, check_base_uri_version),
+     (ValidationRule::DocumentationTitle, check_documentation_titles),
+     (ValidationRule::TypeReferencesResolve, check_type_references)];
+
+/// Checks a document with each rule that isn't disabled, returning the violations in the
+/// order of the rules.
+pub fn get_violations(raml: &Raml, options: &ValidationOptions) -> Vec<RamlViolation> {
+    let mut violations = vec![];
+    for &(rule, check) in VALIDATION_RULES.iter() {
+        let enabled = rule.is_enabled_by_default() || options.enabled_rules.contains(&rule);
+        if !enabled || options.disabled_rules.contains(&rule) {
+            continue;
+        }
+        let mut found = Violations {
+            rule,
+            violations: vec![],
+        };
+        check(raml, &mut found);
+        violations.extend(found.violations);
+    }
+    violations
+}
+
+// The methods of a resource in the order of METHOD_NAMES.
+fn sorted_methods(resource: &Resource) -> Vec<(&str, &Method)> {
+    METHOD_NAMES.iter()
+        .filter_map(|name| resource.methods().get(*name).map(|method| (*name, method)))
+        .collect()
+}
+
+fn check_secured_by_resolves(raml: &Raml, violations: &mut Violations) {
+    check_secured_by_entries(raml,
+                             raml.secured_by(),
+                             HierarchyLevel::DocumentRoot,
+                             None,
+                             violations);
+    check_resource_secured_by(raml, raml.resources(), violations);
+}
+
+fn check_resource_secured_by(raml: &Raml, resources: &[Resource], violations: &mut Violations) {
+    for resource in resources {
+        check_secured_by_entries(raml,
+                                 resource.secured_by(),
+                                 HierarchyLevel::Resource,
+                                 resource.location(),
+                                 violations);
+        for (_, method) in sorted_methods(resource) {
+            check_secured_by_entries(raml,
+                                     method.secured_by(),
+                                     HierarchyLevel::Method,
+                                     method.location(),
+                                     violations);
+        }
+        check_resource_secured_by(raml, resource.resources(), violations);
+    }
+}
+
+fn check_secured_by_entries(raml: &Raml,
+                            entries: Option<&SecuredByEntries>,
+                            level: HierarchyLevel,
+                            location: Option<SourceLocation>,
+                            violations: &mut Violations) {
+    for entry in entries.into_iter().flatten() {
+        let checked =
+            check_secured_by(entry, None, raml.security_schemes(), raml.uses(), level.clone());
+        if let Err(error) = checked {
+            violations.add(error, location);
+        }
+    }
+}
+
+fn check_uri_parameters_declared(raml: &Raml, violations: &mut Violations) {
+    if let Some(base_uri) = raml.base_uri() {
+        for name in get_uri_template_variables(base_uri) {
+            if name != "version" && !is_parameter_declared(raml.base_uri_parameters(), &name) {
+                violations.add(get_error(ErrorDef::UndeclaredBaseUriParameter { name }, None),
+                               None);
+            }
+        }
+    }
+    check_resource_uri_parameters(raml.resources(), &[], violations);
+}
+
+fn check_resource_uri_parameters<'a>(resources: &'a [Resource],
+                                     parent_parameters: &[&'a NamedParameters],
+                                     violations: &mut Violations) {
+    for resource in resources {
+        let mut parameters = parent_parameters.to_vec();
+        parameters.extend(resource.uri_parameters());
+        for name in get_uri_template_variables(resource.relative_uri()) {
+            // The extension of the media type is always declared, as a string.
+            let declared = name == "mediaTypeExtension" ||
+                           parameters.iter().any(|declared| declared.contains_key(&name));
+            if !declared {
+                violations.add(get_error(ErrorDef::UndeclaredUriParameter { name }, None),
+                               resource.location());
+            }
+        }
+        check_resource_uri_parameters(resource.resources(), &parameters, violations);
+    }
+}
+
+fn is_parameter_declared(parameters: Option<&NamedParameters>, name: &str) -> bool {
+    parameters.is_some_and(|parameters| parameters.contains_key(name))
+}
+
+fn check_media_type_format(raml: &Raml, violations: &mut Violations) {
+    check_resource_media_types(raml.resources(), violations);
+}
+
+fn check_resource_media_types(resources: &[Resource], violations: &mut Violations) {
+    for resource in resources {
+        for (_, method) in sorted_methods(resource) {
+            check_body_media_types(method.body(), method.location(), violations);
+            let mut responses: Vec<_> = method.responses().into_iter().flatten().collect();
+            responses.sort_by_key(|&(code, _)| *code);
+            for (_, response) in responses {
+                check_body_media_types(response.body(), method.location(), violations);
+            }
+        }
+        check_resource_media_types(resource.resources(), violations);
+    }
+}
+
+fn check_body_media_types(bodies: Option<&Bodies>,
+                          location: Option<SourceLocation>,
+                          violations: &mut Violations) {
+    for body in bodies.into_iter().flatten() {
+        // A body declared without a media type, when the API has none either.
+        if body.media_type().is_empty() {
+            continue;
+        }
+        if let Err(error) = body.media_type().parse::<MediaType>() {
+            violations.add(error, location);
+        }
+    }
+}
+
+fn check_base_uri_version(raml: &Raml, violations: &mut Violations) {
+    if let Some(base_uri) = raml.base_uri() {
+        let uses_version = get_uri_template_variables(base_uri).iter().any(|v| v == "version");
+        if uses_version && raml.version().is_none() {
+            violations.add(get_error(ErrorDef::MissingBaseUriVersion, None), None);
+        }
+    }
+}
+
+fn check_documentation_titles(raml: &Raml, violations: &mut Violations) {
+    for entry in raml.documentation().into_iter().flatten() {
+        if entry.title().trim().is_empty() {
+            let error = get_error(ErrorDef::EmptyField {
+                                      field: "title".to_string(),
+                                      level: HierarchyLevel::Documentation,
+                                  },
+                                  None);
+            violations.add(error, entry.location());
+        }
+    }
+}
+
+fn check_type_references(raml: &Raml, violations: &mut Violations) {
+    for types in raml.types().into_iter().chain(raml.annotation_types()) {
+        let mut names: Vec<&String> = types.keys().collect();
+        names.sort();
+        for name in names {
+            check_type(raml, name, &types[name], violations);
+        }
+    }
+    check_circular_types(raml, violations);
+    check_resource_types(raml, raml.resources(), violations);
+    check_security_scheme_types(raml, violations);
+}
+
+// A type that inherits from itself can't be resolved, while a type that only refers to itself
+// through its properties, e.g. a tree, can. Each type in a cycle is reported, while a type that
+// inherits from a cycle fails with the same error as the type it inherits from.
+fn check_circular_types(raml: &Raml, violations: &mut Violations) {
+    let types = match raml.types() {
+        Some(types) => types,
+        None => return,
+    };
+    let mut names: Vec<&String> = types.keys().collect();
+    names.sort();
+    for name in names {
+        match raml.resolve_type(name) {
+            Err(error) if error.kind() == ErrorKind::CircularTypeInheritance => {
+                let reported = violations.violations.iter().any(|v| v.message == error.message());
+                if !reported {
+                    violations.add(error, types[name].location());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_security_scheme_types(raml: &Raml, violations: &mut Violations) {
+    let schemes = match raml.security_schemes() {
+        Some(schemes) => schemes,
+        None => return,
+    };
+    let mut names: Vec<&String> = schemes.keys().collect();
+    names.sort();
+    for name in names {
+        let scheme = &schemes[name];
+        let responses = scheme.described_by().and_then(|d| d.responses());
+        let mut responses: Vec<_> = responses.into_iter().flatten().collect();
+        responses.sort_by_key(|&(code, _)| *code);
+        for (_, response) in responses {
+            check_body_types(raml, response.body(), scheme.location(), violations);
+        }
+    }
+}
+
+fn check_type(raml: &Raml, type_name: &str, raml_type: &RamlType, violations: &mut Violations) {
+    for expression in raml_type.parent_types() {
+        check_type_expression(raml,
+                              type_name,
+                              expression,
+                              raml_type.location(),
+                              false,
+                              violations);
+    }
+    for properties in raml_type.properties().into_iter().chain(raml_type.pattern_properties()) {
+        let mut names: Vec<&String> = properties.keys().collect();
+        names.sort();
+        for name in names {
+            check_type(raml, type_name, properties[name].property_type(), violations);
+        }
+    }
+    if let Some(items) = raml_type.items() {
+        check_type(raml, type_name, items, violations);
+    }
+}
+
+fn check_resource_types(raml: &Raml, resources: &[Resource], violations: &mut Violations) {
+    for resource in resources {
+        for (_, method) in sorted_methods(resource) {
+            if let Some(query_string) = method.query_string() {
+                check_type(raml, "queryString", query_string, violations);
+            }
+            check_body_types(raml, method.body(), method.location(), violations);
+            let mut responses: Vec<_> = method.responses().into_iter().flatten().collect();
+            responses.sort_by_key(|&(code, _)| *code);
+            for (_, response) in responses {
+                check_body_types(raml, response.body(), method.location(), violations);
+            }
+        }
+        check_resource_types(raml, resource.resources(), violations);
+    }
+}
+
+fn check_body_types(raml: &Raml,
+                    bodies: Option<&Bodies>,
+                    location: Option<SourceLocation>,
+                    violations: &mut Violations) {
+    for body in bodies.into_iter().flatten() {
+        if let Some(expression) = body.body_type() {
+            let name = expression.to_string();
+            check_type_expression(raml, &name, expression, location, true, violations);
+        }
+        if let Some(properties) = body.form_properties() {
+            let mut names: Vec<&String> = properties.keys().collect();
+            names.sort();
+            for name in names {
+                check_type(raml, body.media_type(), properties[name].property_type(), violations);
+            }
+        }
+    }
+}
+
+fn check_type_expression(raml: &Raml,
+                         type_name: &str,
+                         expression: &TypeExpression,
+                         location: Option<SourceLocation>,
+                         schema_allowed: bool,
+                         violations: &mut Violations) {
+    match *expression {
+        TypeExpression::Named(ref name) => {
+            if is_built_in_type(name) {
+                return;
+            }
+            match find_declaration(name, raml.types(), raml.uses(), Library::types, None) {
+                Ok(Some(declaration)) => {
+                    if !schema_allowed && declaration.schema().is_some() {
+                        let error = get_error(ErrorDef::SchemaTypeReference {
+                                                  name: name.clone(),
+                                                  type_name: type_name.to_string(),
+                                              },
+                                              None);
+                        violations.add(error, location);
+                    }
+                }
+                Ok(None) => {
+                    let error = get_error(ErrorDef::UndeclaredType {
+                                              name: name.clone(),
+                                              type_name: type_name.to_string(),
+                                          },
+                                          None);
+                    violations.add(error, location);
+                }
+                Err(error) => violations.add(error, location),
+            }
+        }
+        TypeExpression::Array(ref items) => {
+            check_type_expression(raml, type_name, items, location, schema_allowed, violations)
+        }
+        TypeExpression::Union(ref members) => {
+            for member in members {
+                check_type_expression(raml,
+                                      type_name,
+                                      member,
+                                      location,
+                                      schema_allowed,
+                                      violations);
+            }
+        }
+    }
+}
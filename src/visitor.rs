@@ -0,0 +1,185 @@
+use body::{Bodies, Body};
+use location::SourceLocation;
+use method::{Method, METHOD_NAMES};
+use parser::{Raml, RamlDocumentation, SecurityScheme};
+use resource::{join_uri, Resource};
+use response::Response;
+use types::RamlType;
+
+/// Where a node visited by a `RamlVisitor` is in the document.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct VisitContext {
+    path: String,
+    location: Option<SourceLocation>,
+}
+
+impl VisitContext {
+    /// The path of the node, as in `AnnotatedNode::path`: empty for the document root, the title
+    /// of a documentation item, the name of a security scheme or type, the full uri of a
+    /// resource, e.g. `/users/{id}`, and the name of a method before the uri of its resource, e.g.
+    /// `get /users/{id}`. A response adds its status code to the path of its method, and a body
+    /// adds its media type to the path of its method or response, e.g.
+    /// `get /users/{id} 200 application/json`.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Where the node is declared, when it was parsed. Responses and bodies are located at their
+    /// method, and the document root has no location.
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
+}
+
+/// Receives the nodes of a document from `Raml::accept`. Each method does nothing unless it is
+/// implemented, so a visitor only implements the methods for the nodes it is interested in.
+pub trait RamlVisitor {
+    fn visit_root(&mut self, _raml: &Raml, _context: &VisitContext) {}
+
+    fn visit_documentation(&mut self,
+                           _documentation: &RamlDocumentation,
+                           _context: &VisitContext) {
+    }
+
+    fn visit_security_scheme(&mut self,
+                             _name: &str,
+                             _security_scheme: &SecurityScheme,
+                             _context: &VisitContext) {
+    }
+
+    fn visit_type(&mut self, _name: &str, _raml_type: &RamlType, _context: &VisitContext) {}
+
+    fn visit_resource(&mut self, _resource: &Resource, _context: &VisitContext) {}
+
+    fn visit_method(&mut self, _verb: &str, _method: &Method, _context: &VisitContext) {}
+
+    fn visit_response(&mut self, _status: u16, _response: &Response, _context: &VisitContext) {}
+
+    fn visit_body(&mut self, _body: &Body, _context: &VisitContext) {}
+}
+
+/// Counts the resources, methods and responses of a document.
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Clone)]
+pub struct EndpointCounter {
+    resources: usize,
+    methods: usize,
+    responses: usize,
+}
+
+impl EndpointCounter {
+    pub fn new() -> EndpointCounter {
+        EndpointCounter::default()
+    }
+
+    pub fn resources(&self) -> usize {
+        self.resources
+    }
+
+    pub fn methods(&self) -> usize {
+        self.methods
+    }
+
+    pub fn responses(&self) -> usize {
+        self.responses
+    }
+}
+
+impl RamlVisitor for EndpointCounter {
+    fn visit_resource(&mut self, _resource: &Resource, _context: &VisitContext) {
+        self.resources += 1;
+    }
+
+    fn visit_method(&mut self, _verb: &str, _method: &Method, _context: &VisitContext) {
+        self.methods += 1;
+    }
+
+    fn visit_response(&mut self, _status: u16, _response: &Response, _context: &VisitContext) {
+        self.responses += 1;
+    }
+}
+
+/// Visits the root, then the documentation in document order, the security schemes and the
+/// declared types by name, and then the resources, depth first in document order. A resource is
+/// followed by its methods in the order of `METHOD_NAMES`, and then by its nested resources. A
+/// method is followed by the bodies of its request, and then by its responses by status code,
+/// each followed by its bodies.
+pub fn visit_document<V>(raml: &Raml, visitor: &mut V)
+    where V: RamlVisitor + ?Sized
+{
+    visitor.visit_root(raml, &context(String::new(), None));
+    for documentation in raml.documentation().into_iter().flatten() {
+        let context = context(documentation.title().to_string(), documentation.location());
+        visitor.visit_documentation(documentation, &context);
+    }
+    if let Some(security_schemes) = raml.security_schemes() {
+        let mut names: Vec<&String> = security_schemes.keys().collect();
+        names.sort();
+        for name in names {
+            let security_scheme = &security_schemes[name];
+            let context = context(name.clone(), security_scheme.location());
+            visitor.visit_security_scheme(name, security_scheme, &context);
+        }
+    }
+    if let Some(types) = raml.types() {
+        let mut names: Vec<&String> = types.keys().collect();
+        names.sort();
+        for name in names {
+            let raml_type = &types[name];
+            visitor.visit_type(name, raml_type, &context(name.clone(), raml_type.location()));
+        }
+    }
+    visit_resources(raml.resources(), "", visitor);
+}
+
+fn visit_resources<V>(resources: &[Resource], parent_path: &str, visitor: &mut V)
+    where V: RamlVisitor + ?Sized
+{
+    for resource in resources {
+        let path = join_uri(parent_path, resource.relative_uri());
+        visitor.visit_resource(resource, &context(path.clone(), resource.location()));
+        for verb in METHOD_NAMES.iter() {
+            if let Some(method) = resource.methods().get(*verb) {
+                visit_method(verb, method, format!("{} {}", verb, path), visitor);
+            }
+        }
+        visit_resources(resource.resources(), &path, visitor);
+    }
+}
+
+fn visit_method<V>(verb: &str, method: &Method, path: String, visitor: &mut V)
+    where V: RamlVisitor + ?Sized
+{
+    let location = method.location();
+    visitor.visit_method(verb, method, &context(path.clone(), location));
+    visit_bodies(method.body(), &path, location, visitor);
+    if let Some(responses) = method.responses() {
+        let mut statuses: Vec<&u16> = responses.keys().collect();
+        statuses.sort();
+        for status in statuses {
+            let response_path = format!("{} {}", path, status);
+            visitor.visit_response(*status,
+                                   &responses[status],
+                                   &context(response_path.clone(), location));
+            visit_bodies(responses[status].body(), &response_path, location, visitor);
+        }
+    }
+}
+
+fn visit_bodies<V>(bodies: Option<&Bodies>,
+                   path: &str,
+                   location: Option<SourceLocation>,
+                   visitor: &mut V)
+    where V: RamlVisitor + ?Sized
+{
+    for body in bodies.into_iter().flatten() {
+        let context = context(format!("{} {}", path, body.media_type()), location);
+        visitor.visit_body(body, &context);
+    }
+}
+
+fn context(path: String, location: Option<SourceLocation>) -> VisitContext {
+    VisitContext { path, location }
+}
@@ -0,0 +1,199 @@
+use yaml_rust::scanner::Marker;
+use std::collections::HashSet;
+use error_definitions::{marker_position, HierarchyLevel};
+use parser::{get_base_uri_protocol, Raml, SecuredBy, SecuredByEntries};
+use resource::Resource;
+use media_type::MediaType;
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum WarningKind {
+    /// A security scheme that no `securedBy` node refers to.
+    UnusedSecurityScheme,
+    /// A `baseUri` whose scheme is not one of the declared `protocols`.
+    ProtocolMismatch,
+    /// A `description` that is empty or only whitespace.
+    EmptyDescription,
+    /// A key the parser doesn't know, skipped as `ParseOptions::unknown_keys` is
+    /// `UnknownKeyBehaviour::Warn`.
+    UnknownKey,
+    /// A default media type with a `*` wildcard, such as `application/*`, which doesn't say
+    /// what bodies are sent as.
+    WildcardMediaType,
+}
+
+/// A problem with a document that is valid, but probably not what was meant.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub struct RamlWarning {
+    kind: WarningKind,
+    message: String,
+    marker: Option<Marker>,
+}
+
+impl RamlWarning {
+    fn new(kind: WarningKind, message: String) -> RamlWarning {
+        RamlWarning {
+            kind,
+            message,
+            marker: None,
+        }
+    }
+
+    pub fn kind(&self) -> WarningKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// The position the warning refers to, when it is known.
+    pub fn marker(&self) -> Option<Marker> {
+        self.marker
+    }
+
+    /// The line of the warning, starting from 1.
+    pub fn line(&self) -> Option<usize> {
+        self.marker.map(|marker| marker_position(marker).1)
+    }
+
+    /// The column of the warning, starting from 1.
+    pub fn column(&self) -> Option<usize> {
+        self.marker.map(|marker| marker_position(marker).2 + 1)
+    }
+}
+
+/// The warning for a key skipped as `ParseOptions::unknown_keys` is `UnknownKeyBehaviour::Warn`.
+pub fn get_unknown_key_warning(field: &str,
+                               level: HierarchyLevel,
+                               marker: Marker)
+                               -> RamlWarning {
+    RamlWarning {
+        kind: WarningKind::UnknownKey,
+        message: format!("Unknown field ignored at the {}: {}", level, field),
+        marker: Some(marker),
+    }
+}
+
+/// The warning for a `mediaType` value such as `*/*`.
+pub fn get_wildcard_media_type_warning(media_type: &MediaType, marker: Marker) -> RamlWarning {
+    RamlWarning {
+        kind: WarningKind::WildcardMediaType,
+        message: format!("The default media type {} is a wildcard", media_type),
+        marker: Some(marker),
+    }
+}
+
+/// A rule adding the warnings it finds in a document.
+type WarningRule = fn(&Raml, &mut Vec<RamlWarning>);
+
+const WARNING_RULES: [WarningRule; 3] = [check_unused_security_schemes,
+                                         check_base_uri_protocol,
+                                         check_empty_descriptions];
+
+/// Checks a parsed document with each warning rule, returning the warnings in the order of the
+/// rules.
+pub fn get_warnings(raml: &Raml) -> Vec<RamlWarning> {
+    let mut warnings = vec![];
+    for rule in WARNING_RULES.iter() {
+        rule(raml, &mut warnings);
+    }
+    warnings
+}
+
+fn check_unused_security_schemes(raml: &Raml, warnings: &mut Vec<RamlWarning>) {
+    let schemes = match raml.security_schemes() {
+        Some(schemes) => schemes,
+        None => return,
+    };
+    let mut used: HashSet<&str> = HashSet::new();
+    add_scheme_names(raml.secured_by(), &mut used);
+    for raml_trait in raml.traits().into_iter().flat_map(|traits| traits.values()) {
+        add_scheme_names(raml_trait.secured_by(), &mut used);
+    }
+    for resource_type in raml.resource_types().into_iter().flat_map(|types| types.values()) {
+        add_scheme_names(resource_type.secured_by(), &mut used);
+        for method in resource_type.methods().values() {
+            add_scheme_names(method.secured_by(), &mut used);
+        }
+    }
+    add_resource_scheme_names(raml.resources(), &mut used);
+    let mut unused: Vec<&String> =
+        schemes.keys().filter(|name| !used.contains(name.as_str())).collect();
+    unused.sort();
+    for name in unused {
+        warnings.push(RamlWarning::new(WarningKind::UnusedSecurityScheme,
+                                       format!("Security scheme {} is never used", name)));
+    }
+}
+
+fn add_resource_scheme_names<'a>(resources: &'a [Resource], used: &mut HashSet<&'a str>) {
+    for resource in resources {
+        add_scheme_names(resource.secured_by(), used);
+        for method in resource.methods().values() {
+            add_scheme_names(method.secured_by(), used);
+        }
+        add_resource_scheme_names(resource.resources(), used);
+    }
+}
+
+fn add_scheme_names<'a>(entries: Option<&'a SecuredByEntries>, used: &mut HashSet<&'a str>) {
+    used.extend(entries.into_iter().flatten().filter_map(SecuredBy::name));
+}
+
+fn check_base_uri_protocol(raml: &Raml, warnings: &mut Vec<RamlWarning>) {
+    let (base_uri, protocols) = match (raml.base_uri(), raml.protocols()) {
+        (Some(base_uri), Some(protocols)) => (base_uri, protocols),
+        _ => return,
+    };
+    let protocol = match get_base_uri_protocol(base_uri) {
+        Some(protocol) => protocol,
+        None => return,
+    };
+    if !protocols.contains(&protocol) {
+        let message = format!("The baseUri {} uses a protocol that is not in protocols",
+                              base_uri);
+        warnings.push(RamlWarning::new(WarningKind::ProtocolMismatch, message));
+    }
+}
+
+fn check_empty_descriptions(raml: &Raml, warnings: &mut Vec<RamlWarning>) {
+    check_empty_description(raml.description(), "the API", warnings);
+    check_resource_descriptions(raml.resources(), "", warnings);
+}
+
+fn check_resource_descriptions(resources: &[Resource],
+                               parent_uri: &str,
+                               warnings: &mut Vec<RamlWarning>) {
+    for resource in resources {
+        let uri = format!("{}{}", parent_uri, resource.relative_uri());
+        check_empty_description(resource.description(),
+                                &format!("resource {}", uri),
+                                warnings);
+        let mut methods: Vec<_> = resource.methods().iter().collect();
+        methods.sort_by_key(|&(name, _)| name);
+        for (name, method) in methods {
+            check_empty_description(method.description(),
+                                    &format!("method {} {}", name, uri),
+                                    warnings);
+        }
+        check_resource_descriptions(resource.resources(), &uri, warnings);
+    }
+}
+
+fn check_empty_description(description: Option<&str>,
+                           node: &str,
+                           warnings: &mut Vec<RamlWarning>) {
+    if let Some(description) = description {
+        if description.trim().is_empty() {
+            warnings.push(RamlWarning::new(WarningKind::EmptyDescription,
+                                           format!("The description of {} is empty", node)));
+        }
+    }
+}
@@ -1,28 +1,72 @@
-use std::collections::HashMap;
-use yaml_rust::scanner::{TokenType, Marker, Scanner, Token};
+use std::collections::{HashMap, HashSet, VecDeque};
+use yaml_rust::scanner::{TokenType, TScalarStyle, Marker, Scanner, Token};
 use error_definitions::RamlError;
-use error_definitions::{get_error, ErrorDef};
+use error_definitions::{get_error, ErrorDef, HierarchyLevel};
 use std::str::Chars;
+use std::error::Error;
 use std::fmt::Display;
 use std::fmt;
+use options::{ParseOptions, UnknownKeyBehaviour};
+use warnings::{get_unknown_key_warning, RamlWarning};
+use include::{check_limit, get_included_tokens, get_source_file, get_source_token, IncludeLimits,
+              IncludeResolver};
+use location::{get_location, SourceFile, SourceLocation};
 
-pub type BlockSequenceEntries = HashMap<String, BlockSequenceEntry>;
+pub struct FlowSequenceEntry {
+    pub value: String,
+    pub marker: Marker,
+}
 
-pub struct BlockSequenceEntry {
+pub struct ScalarValue {
     pub value: String,
     pub marker: Marker,
+    pub style: TScalarStyle,
 }
 
-pub type VectorOfBlockSequenceEntries = Vec<BlockSequenceEntries>;
+impl ScalarValue {
+    /// Whether the scalar is an explicit null: a plain `null` or `~`.
+    pub fn is_null(&self) -> bool {
+        self.style == TScalarStyle::Plain && (self.value == "null" || self.value == "~")
+    }
+}
 
-pub struct FlowSequenceEntry {
+pub struct MappingKey {
     pub value: String,
     pub marker: Marker,
+    pub style: TScalarStyle,
 }
 
-pub struct KeyValue {
-    pub key: String,
-    pub value: String,
+/// A value of any shape, read with `get_node`. Every node keeps the position it starts at.
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Clone)]
+pub enum YamlNode {
+    /// A scalar, or an empty value as an empty string.
+    Scalar(String, Marker),
+    Sequence(Vec<YamlNode>, Marker),
+    /// The entries of a mapping, in document order.
+    Mapping(Vec<(String, YamlNode)>, Marker),
+}
+
+impl YamlNode {
+    /// Where the node starts in the source. The included file it may have been read from isn't
+    /// recorded, so `file` is None.
+    pub fn location(&self) -> SourceLocation {
+        match *self {
+            YamlNode::Scalar(_, marker) |
+            YamlNode::Sequence(_, marker) |
+            YamlNode::Mapping(_, marker) => get_location(marker),
+        }
+    }
+}
+
+/// The values of keys the parser doesn't model, keyed as written, e.g. `x-monitoring` or
+/// `(monitoring)`.
+pub type Extensions = HashMap<String, YamlNode>;
+
+pub enum MappingStyle {
+    Block,
+    Flow,
 }
 
 pub type FlowSequenceEntries = Vec<FlowSequenceEntry>;
@@ -112,10 +156,21 @@ pub fn get_token_def(token_type: &TokenType) -> TokenTypeDef {
     }
 }
 
-pub fn get_scalar_value(cursor: &mut ForwardCursor) -> Result<String, RamlError> {
-    let token = cursor.next_token();
+pub fn get_scalar_value(cursor: &mut ForwardCursor) -> Result<ScalarValue, RamlError> {
+    let token = cursor.next_token()?;
     match token.1 {
-        TokenType::Scalar(_, ref v) => Ok(v.clone()),
+        TokenType::Scalar(style, value) => {
+            Ok(ScalarValue {
+                value,
+                marker: token.0,
+                style,
+            })
+        }
+        // A cursor with an include resolver has already replaced the includes of its source.
+        TokenType::Tag(ref handle, ref suffix) if handle == "!" && suffix == "include" => {
+            let path = get_scalar_value(cursor)?.value;
+            Err(get_error(ErrorDef::MissingIncludeResolver { path }, Some(token.0)))
+        }
         _ => {
             Err(get_error(ErrorDef::UnexpectedEntry {
                               expected: TokenTypeDef::Scalar,
@@ -126,10 +181,18 @@ pub fn get_scalar_value(cursor: &mut ForwardCursor) -> Result<String, RamlError>
     }
 }
 
+/// Reads a scalar if the next token is one, leaving any other token to be read again.
+pub fn get_optional_scalar(cursor: &mut ForwardCursor) -> Result<Option<ScalarValue>, RamlError> {
+    if cursor.peek_def()? != TokenTypeDef::Scalar {
+        return Ok(None);
+    }
+    get_scalar_value(cursor).map(Some)
+}
+
 pub fn get_flow_sequence(cursor: &mut ForwardCursor) -> Result<FlowSequenceEntries, RamlError> {
     let mut values = vec![];
     loop {
-        let token = cursor.next_token();
+        let token = cursor.next_token()?;
         match token.1 {
             TokenType::Scalar(_, s) => {
                 values.push(FlowSequenceEntry {
@@ -155,130 +218,892 @@ pub fn get_flow_sequence(cursor: &mut ForwardCursor) -> Result<FlowSequenceEntri
     Ok(values)
 }
 
-pub fn get_multiple_values(cursor: &mut ForwardCursor) -> Result<FlowSequenceEntries, RamlError> {
+/// Reads a sequence of scalars given in either flow (`[a, b]`) or block (`- a`) style. A key
+/// without a value has no entries.
+pub fn get_sequence_values(cursor: &mut ForwardCursor) -> Result<FlowSequenceEntries, RamlError> {
     cursor.expect(TokenTypeDef::Value)?;
-    cursor.expect(TokenTypeDef::FlowSequenceStart)?;
-    get_flow_sequence(cursor)
-}
-
-pub fn get_multiple_sets_of_values(cursor: &mut ForwardCursor)
-                                   -> Result<VectorOfBlockSequenceEntries, RamlError> {
-    cursor.expect(TokenTypeDef::Value)?;
-    cursor.expect(TokenTypeDef::BlockSequenceStart)?;
-    get_block_sequences(cursor)
+    match cursor.peek_def()? {
+        // A sequence at the same indentation as its key has no start or end token.
+        TokenTypeDef::BlockEntry => return get_block_sequence_values(cursor),
+        TokenTypeDef::Key |
+        TokenTypeDef::BlockEnd |
+        TokenTypeDef::FlowEntry |
+        TokenTypeDef::FlowMappingEnd => return Ok(vec![]),
+        _ => {}
+    }
+    get_sequence_after_value(cursor, vec![TokenTypeDef::FlowSequenceStart,
+                                          TokenTypeDef::BlockSequenceStart])
 }
 
-pub fn get_single_value(cursor: &mut ForwardCursor) -> Result<String, RamlError> {
-    cursor.expect(TokenTypeDef::Value)?;
-    get_scalar_value(cursor)
+// Reads a flow or block sequence of scalars, reporting any other node as unexpected where one
+// of `expected` was.
+fn get_sequence_after_value(cursor: &mut ForwardCursor,
+                            expected: Vec<TokenTypeDef>)
+                            -> Result<FlowSequenceEntries, RamlError> {
+    let token = cursor.next_token()?;
+    match token.1 {
+        TokenType::FlowSequenceStart => get_flow_sequence(cursor),
+        TokenType::BlockSequenceStart => {
+            let values = get_block_sequence_values(cursor)?;
+            cursor.expect(TokenTypeDef::BlockEnd)?;
+            Ok(values)
+        }
+        _ => {
+            Err(get_error(ErrorDef::UnexpectedEntryMulti {
+                              expected,
+                              found: get_token_def(&token.1),
+                          },
+                          Some(token.0)))
+        }
+    }
 }
 
-pub fn get_block_sequences(cursor: &mut ForwardCursor)
-                           -> Result<VectorOfBlockSequenceEntries, RamlError> {
-    let mut result: VectorOfBlockSequenceEntries = Vec::new();
-    loop {
-        let token = cursor.next_token();
+fn get_block_sequence_values(cursor: &mut ForwardCursor)
+                             -> Result<FlowSequenceEntries, RamlError> {
+    let mut values = vec![];
+    while cursor.next_is(TokenTypeDef::BlockEntry)? {
+        let token = cursor.next_token()?;
         match token.1 {
-            TokenType::BlockEntry => {
-                let block_sequence = get_block_sequence(cursor)?;
-                result.push(block_sequence);
-            }
-            TokenType::BlockEnd => {
-                break;
+            TokenType::Scalar(_, v) => {
+                values.push(FlowSequenceEntry {
+                    value: v,
+                    marker: token.0,
+                })
             }
             _ => {
-                return Err(get_error(ErrorDef::UnexpectedEntryMulti {
-                                         expected: vec![TokenTypeDef::BlockEntry,
-                                                        TokenTypeDef::BlockEnd],
+                return Err(get_error(ErrorDef::UnexpectedEntry {
+                                         expected: TokenTypeDef::Scalar,
                                          found: get_token_def(&token.1),
                                      },
                                      Some(token.0)))
             }
         }
     }
-    Ok(result)
+    Ok(values)
 }
 
-pub fn get_key_value(cursor: &mut ForwardCursor) -> Result<KeyValue, RamlError> {
-    let key = get_scalar_value(cursor)?;
-    cursor.expect(TokenTypeDef::Value)?;
-    let value = get_scalar_value(cursor)?;
-    Ok(KeyValue {
-        key: key,
-        value: value,
-    })
+/// Reads a sequence in flow or block style, using `get_entry` for each entry. Returns `None`
+/// without consuming anything when the next node is not a sequence.
+pub fn get_optional_sequence<T, F>(cursor: &mut ForwardCursor,
+                                   mut get_entry: F)
+                                   -> Result<Option<Vec<T>>, RamlError>
+    where F: FnMut(&mut ForwardCursor) -> Result<T, RamlError>
+{
+    let mut entries = vec![];
+    if cursor.next_is(TokenTypeDef::FlowSequenceStart)? {
+        while !cursor.next_is(TokenTypeDef::FlowSequenceEnd)? {
+            entries.push(get_entry(cursor)?);
+            cursor.next_is(TokenTypeDef::FlowEntry)?;
+        }
+    } else if cursor.next_is(TokenTypeDef::BlockSequenceStart)? {
+        while cursor.next_is(TokenTypeDef::BlockEntry)? {
+            entries.push(get_entry(cursor)?);
+        }
+        cursor.expect(TokenTypeDef::BlockEnd)?;
+    } else if cursor.next_is(TokenTypeDef::BlockEntry)? {
+        // A sequence at the same indentation as its key has no start or end token.
+        entries.push(get_entry(cursor)?);
+        while cursor.next_is(TokenTypeDef::BlockEntry)? {
+            entries.push(get_entry(cursor)?);
+        }
+    } else {
+        return Ok(None);
+    }
+    Ok(Some(entries))
 }
 
-pub fn get_block_sequence(cursor: &mut ForwardCursor) -> Result<BlockSequenceEntries, RamlError> {
-    let mut result: BlockSequenceEntries = HashMap::new();
-    cursor.expect(TokenTypeDef::BlockMappingStart)?;
+/// Reads the value of a mapping entry without interpreting it, so that it can be parsed later
+/// with `ForwardCursor::from_tokens`. The tokens start with the `Value` token.
+pub fn get_raw_value(cursor: &mut ForwardCursor) -> Result<Vec<Token>, RamlError> {
+    let token = cursor.next_token()?;
+    if get_token_def(&token.1) != TokenTypeDef::Value {
+        return Err(get_error(ErrorDef::UnexpectedEntry {
+                                 expected: TokenTypeDef::Value,
+                                 found: get_token_def(&token.1),
+                             },
+                             Some(token.0)));
+    }
+    // The tokens are marked with the files they were read from, as the cursor the tokens are
+    // read with starts in the source itself.
+    let mut tokens = vec![];
+    let mut file = None;
+    push_raw_token(&mut tokens, &mut file, cursor, token);
+    let mut depth = 0;
     loop {
-        let token = cursor.next_token();
-        match token.1 {
-            TokenType::Key => {
-                let key_value = get_key_value(cursor)?;
-                result.insert(key_value.key,
-                              BlockSequenceEntry {
-                                  value: key_value.value,
-                                  marker: token.0,
-                              });
+        let token = cursor.next_token()?;
+        let is_complete = match token.1 {
+            TokenType::BlockMappingStart |
+            TokenType::BlockSequenceStart |
+            TokenType::FlowMappingStart |
+            TokenType::FlowSequenceStart => {
+                depth += 1;
+                false
             }
-            TokenType::BlockEnd => {
-                break;
+            TokenType::BlockEnd |
+            TokenType::FlowMappingEnd |
+            TokenType::FlowSequenceEnd if depth > 0 => {
+                depth -= 1;
+                depth == 0
             }
+            TokenType::Scalar(_, _) |
+            TokenType::Alias(_) => depth == 0,
+            TokenType::Tag(_, _) |
+            TokenType::Anchor(_) => false,
+            _ if depth > 0 => false,
             _ => {
-                return Err(get_error(ErrorDef::UnexpectedEntryMulti {
-                                         expected: vec![TokenTypeDef::Key, TokenTypeDef::BlockEnd],
-                                         found: get_token_def(&token.1),
-                                     },
-                                     Some(token.0)))
+                // The value is empty, and the token belongs to the enclosing node.
+                cursor.put_back(token);
+                break;
             }
+        };
+        push_raw_token(&mut tokens, &mut file, cursor, token);
+        if is_complete {
+            break;
         }
     }
-    Ok(result)
+    Ok(tokens)
+}
+
+fn push_raw_token(tokens: &mut Vec<Token>,
+                  file: &mut Option<usize>,
+                  cursor: &ForwardCursor,
+                  token: Token) {
+    if cursor.source_file() != *file {
+        *file = cursor.source_file();
+        tokens.push(get_source_token(token.0, *file));
+    }
+    tokens.push(token);
+}
+
+const END_OF_STREAM: &str = "unexpected end of stream";
+
+/// The error for a scanner that stopped reading tokens, at the position it stopped. A scanner
+/// stops at the first error in the source, or when read past the end of the stream.
+#[allow(deprecated)]
+pub fn get_scan_error(scanner: &Scanner<Chars>) -> RamlError {
+    let info = match scanner.get_error() {
+        // The message alone, as the error adds the position.
+        Some(error) => error.description().to_string(),
+        None => END_OF_STREAM.to_string(),
+    };
+    get_error(ErrorDef::InvalidYaml { info }, Some(scanner.mark()))
+}
+
+/// Reads the value of a mapping entry, starting with its `Value` token, however deeply it is
+/// nested.
+pub fn get_node(cursor: &mut ForwardCursor) -> Result<YamlNode, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    get_entry_value(cursor)
+}
+
+// Reads the value of a mapping entry, after its `Value` token.
+fn get_entry_value(cursor: &mut ForwardCursor) -> Result<YamlNode, RamlError> {
+    if cursor.peek_def()? != TokenTypeDef::BlockEntry {
+        return get_node_value(cursor);
+    }
+    // A sequence at the same indentation as its key has no start or end token.
+    let marker = cursor.peek_marker()?;
+    let mut values = vec![];
+    while cursor.next_is(TokenTypeDef::BlockEntry)? {
+        values.push(get_node_value(cursor)?);
+    }
+    Ok(YamlNode::Sequence(values, marker))
+}
+
+fn get_node_value(cursor: &mut ForwardCursor) -> Result<YamlNode, RamlError> {
+    let marker = cursor.peek_marker()?;
+    match cursor.peek_def()? {
+        TokenTypeDef::Anchor |
+        TokenTypeDef::Tag => {
+            cursor.next_token()?;
+            get_node_value(cursor)
+        }
+        TokenTypeDef::Scalar |
+        TokenTypeDef::Alias => {
+            // Aliases are not resolved, and are read as the name of the anchor they refer to.
+            match cursor.next_token()?.1 {
+                TokenType::Scalar(_, value) |
+                TokenType::Alias(value) => Ok(YamlNode::Scalar(value, marker)),
+                _ => Ok(YamlNode::Scalar(String::new(), marker)),
+            }
+        }
+        TokenTypeDef::BlockSequenceStart => {
+            cursor.next_token()?;
+            let mut values = vec![];
+            while cursor.next_is(TokenTypeDef::BlockEntry)? {
+                values.push(get_node_value(cursor)?);
+            }
+            cursor.expect(TokenTypeDef::BlockEnd)?;
+            Ok(YamlNode::Sequence(values, marker))
+        }
+        TokenTypeDef::FlowSequenceStart => {
+            cursor.next_token()?;
+            let mut values = vec![];
+            while !cursor.next_is(TokenTypeDef::FlowSequenceEnd)? {
+                values.push(get_node_value(cursor)?);
+                cursor.next_is(TokenTypeDef::FlowEntry)?;
+            }
+            Ok(YamlNode::Sequence(values, marker))
+        }
+        TokenTypeDef::BlockMappingStart |
+        TokenTypeDef::FlowMappingStart => {
+            let style = get_mapping_start(cursor)?;
+            let marker = cursor.peek_marker()?;
+            let mut entries = vec![];
+            while let Some(key) = get_mapping_key(cursor, &style)? {
+                let value = if cursor.next_is(TokenTypeDef::Value)? {
+                    get_entry_value(cursor)?
+                } else {
+                    // A key without a value, e.g. `{ a }`.
+                    YamlNode::Scalar(String::new(), cursor.peek_marker()?)
+                };
+                entries.push((key.value, value));
+            }
+            Ok(YamlNode::Mapping(entries, marker))
+        }
+        // The value is empty, and the token belongs to the enclosing node.
+        _ => Ok(YamlNode::Scalar(String::new(), marker)),
+    }
+}
+
+/// Skips the value of a mapping entry, however deeply it is nested.
+pub fn skip_value(cursor: &mut ForwardCursor) -> Result<(), RamlError> {
+    get_node(cursor).map(|_| ())
+}
+
+/// Records a key of a mapping at `level`, failing when the mapping has already declared it.
+pub fn check_duplicate_key(keys: &mut HashSet<String>,
+                           key: &str,
+                           marker: Marker,
+                           level: HierarchyLevel)
+                           -> Result<(), RamlError> {
+    if keys.insert(key.to_string()) {
+        Ok(())
+    } else {
+        Err(get_error(ErrorDef::DuplicateKey {
+                          key: key.to_string(),
+                          level,
+                      },
+                      Some(marker)))
+    }
+}
+
+/// Handles a key that is not expected at `level` as `ParseOptions::unknown_keys` says: either
+/// fails, or skips its value, reporting a warning when asked to.
+pub fn skip_unknown_key(cursor: &mut ForwardCursor,
+                        key: MappingKey,
+                        level: HierarchyLevel)
+                        -> Result<(), RamlError> {
+    check_unknown_key(cursor, &key, level)?;
+    skip_value(cursor)
+}
+
+/// Handles a key that is not expected at `level` like `skip_unknown_key`, but keeps the value of
+/// a key that isn't rejected in `extensions`.
+pub fn keep_unknown_key(cursor: &mut ForwardCursor,
+                        key: MappingKey,
+                        level: HierarchyLevel,
+                        extensions: &mut Option<Extensions>)
+                        -> Result<(), RamlError> {
+    check_unknown_key(cursor, &key, level)?;
+    let node = get_node(cursor)?;
+    extensions.get_or_insert_with(HashMap::new).insert(key.value, node);
+    Ok(())
+}
+
+fn check_unknown_key(cursor: &mut ForwardCursor,
+                     key: &MappingKey,
+                     level: HierarchyLevel)
+                     -> Result<(), RamlError> {
+    match cursor.options().unknown_keys {
+        UnknownKeyBehaviour::Error => {
+            Err(get_error(ErrorDef::UnexpectedKeyRoot {
+                              field: key.value.clone(),
+                              level,
+                          },
+                          Some(key.marker)))
+        }
+        UnknownKeyBehaviour::Warn => {
+            cursor.warn(get_unknown_key_warning(&key.value, level, key.marker));
+            Ok(())
+        }
+        UnknownKeyBehaviour::Ignore => Ok(()),
+    }
+}
+
+pub fn get_single_value(cursor: &mut ForwardCursor) -> Result<String, RamlError> {
+    get_single_scalar(cursor).map(|scalar| scalar.value)
+}
+
+/// Reads a scalar value that may be left out: an empty value or an explicit null gives `None`.
+pub fn get_optional_value(cursor: &mut ForwardCursor) -> Result<Option<String>, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    match cursor.peek_def()? {
+        TokenTypeDef::Key |
+        TokenTypeDef::BlockEnd |
+        TokenTypeDef::FlowEntry |
+        TokenTypeDef::FlowMappingEnd => return Ok(None),
+        _ => {}
+    }
+    let scalar = get_scalar_value(cursor)?;
+    if scalar.is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(scalar.value))
+    }
+}
+
+pub fn get_single_scalar(cursor: &mut ForwardCursor) -> Result<ScalarValue, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    get_scalar_value(cursor)
+}
+
+/// Reads the value of a boolean facet in any of the forms of YAML 1.1, e.g. `true`, `True`, `yes`
+/// or `off`. Any other value is an error naming the facet.
+pub fn parse_bool_scalar(facet: &str,
+                         value: &str,
+                         marker: Option<Marker>)
+                         -> Result<bool, RamlError> {
+    match value {
+        "true" | "True" | "TRUE" | "yes" | "Yes" | "YES" | "y" | "Y" | "on" | "On" | "ON" => {
+            Ok(true)
+        }
+        "false" | "False" | "FALSE" | "no" | "No" | "NO" | "n" | "N" | "off" | "Off" | "OFF" => {
+            Ok(false)
+        }
+        _ => {
+            Err(get_error(ErrorDef::InvalidBoolean {
+                              facet: facet.to_string(),
+                              value: value.to_string(),
+                          },
+                          marker))
+        }
+    }
+}
+
+/// Reads the value of an integer facet in any of the forms of YAML 1.1: decimal, or binary,
+/// octal or hexadecimal with the prefixes `0b`, `0` and `0x`, optionally signed and with `_`
+/// between the digits. Any other value is an error naming the facet.
+pub fn parse_int_scalar(facet: &str,
+                        value: &str,
+                        marker: Option<Marker>)
+                        -> Result<i64, RamlError> {
+    let error = || {
+        get_error(ErrorDef::InvalidInteger {
+                      facet: facet.to_string(),
+                      value: value.to_string(),
+                  },
+                  marker)
+    };
+    let (negative, digits) = match value.chars().next() {
+        Some('-') => (true, &value[1..]),
+        Some('+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (radix, digits) = if let Some(digits) = digits.strip_prefix("0b") {
+        (2, digits)
+    } else if let Some(digits) = digits.strip_prefix("0x") {
+        (16, digits)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (8, &digits[1..])
+    } else {
+        (10, digits)
+    };
+    let digits: String = digits.chars().filter(|&c| c != '_').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(error());
+    }
+    let magnitude = i64::from_str_radix(&digits, radix).map_err(|_| error())?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Reads a boolean facet, named in the error for a value that isn't a boolean by the key it is
+/// the value of.
+pub fn get_boolean_value(cursor: &mut ForwardCursor) -> Result<bool, RamlError> {
+    cursor.expect(TokenTypeDef::Value)?;
+    let token = cursor.next_token()?;
+    match token.1 {
+        TokenType::Scalar(_, ref v) => {
+            let facet = cursor.key_path().pop().unwrap_or_default();
+            parse_bool_scalar(&facet, v, Some(token.0))
+        }
+        _ => {
+            Err(get_error(ErrorDef::UnexpectedEntry {
+                              expected: TokenTypeDef::Scalar,
+                              found: get_token_def(&token.1),
+                          },
+                          Some(token.0)))
+        }
+    }
+}
+
+pub fn get_number_value(cursor: &mut ForwardCursor) -> Result<f64, RamlError> {
+    let value = get_single_scalar(cursor)?;
+    value.value
+        .parse::<f64>()
+        .map_err(|_| {
+            get_error(ErrorDef::InvalidNumber { value: value.value.clone() },
+                      Some(value.marker))
+        })
+}
+
+pub fn get_unsigned_value(cursor: &mut ForwardCursor) -> Result<u64, RamlError> {
+    let value = get_single_scalar(cursor)?;
+    let facet = cursor.key_path().pop().unwrap_or_default();
+    let integer = parse_int_scalar(&facet, &value.value, Some(value.marker))?;
+    if integer < 0 {
+        return Err(get_error(ErrorDef::InvalidUnsignedInteger { value: value.value },
+                             Some(value.marker)));
+    }
+    Ok(integer as u64)
 }
 
 pub fn get_single_or_multiple_values(cursor: &mut ForwardCursor)
                                      -> Result<FlowSequenceEntries, RamlError> {
     cursor.expect(TokenTypeDef::Value)?;
+    if let Some(scalar) = get_optional_scalar(cursor)? {
+        return Ok(vec![FlowSequenceEntry {
+                           value: scalar.value,
+                           marker: scalar.marker,
+                       }]);
+    }
+    if cursor.peek_def()? == TokenTypeDef::BlockEntry {
+        return get_block_sequence_values(cursor);
+    }
+    get_sequence_after_value(cursor, vec![TokenTypeDef::Scalar,
+                                          TokenTypeDef::FlowSequenceStart,
+                                          TokenTypeDef::BlockSequenceStart])
+}
 
-    let token = cursor.next_token();
-    match token.1 {
-        TokenType::Scalar(_, v) => {
-            Ok(vec![FlowSequenceEntry {
+/// Consumes the start of a block or flow mapping if the next token is one.
+pub fn get_optional_mapping_start(cursor: &mut ForwardCursor)
+                                  -> Result<Option<MappingStyle>, RamlError> {
+    let style = if cursor.next_is(TokenTypeDef::BlockMappingStart)? {
+        MappingStyle::Block
+    } else if cursor.next_is(TokenTypeDef::FlowMappingStart)? {
+        MappingStyle::Flow
+    } else {
+        return Ok(None);
+    };
+    cursor.enter_mapping();
+    Ok(Some(style))
+}
+
+pub fn get_mapping_start(cursor: &mut ForwardCursor) -> Result<MappingStyle, RamlError> {
+    match get_optional_mapping_start(cursor)? {
+        Some(style) => Ok(style),
+        None => {
+            let token = cursor.next_token()?;
+            Err(get_error(ErrorDef::UnexpectedEntryMulti {
+                              expected: vec![TokenTypeDef::BlockMappingStart,
+                                             TokenTypeDef::FlowMappingStart],
+                              found: get_token_def(&token.1),
+                          },
+                          Some(token.0)))
+        }
+    }
+}
+
+/// Reads the next key of a mapping started with `get_mapping_start`, returning `None` once the
+/// end of the mapping has been consumed. The key is the last of the cursor's key path until the
+/// next key is read.
+pub fn get_mapping_key(cursor: &mut ForwardCursor,
+                       style: &MappingStyle)
+                       -> Result<Option<MappingKey>, RamlError> {
+    let mut token = cursor.next_token()?;
+    if let MappingStyle::Flow = *style {
+        if let TokenType::FlowEntry = token.1 {
+            token = cursor.next_token()?;
+        }
+    }
+    match (style, token.1) {
+        (_, TokenType::Key) => {
+            let token = cursor.next_token()?;
+            match token.1 {
+                TokenType::Scalar(style, v) => {
+                    cursor.enter_key(&v);
+                    Ok(Some(MappingKey {
                         value: v,
                         marker: token.0,
-                    }])
+                        style,
+                    }))
+                }
+                _ => {
+                    Err(get_error(ErrorDef::UnexpectedEntry {
+                                      expected: TokenTypeDef::Scalar,
+                                      found: get_token_def(&token.1),
+                                  },
+                                  Some(token.0)))
+                }
+            }
         }
-        TokenType::FlowSequenceStart => get_flow_sequence(cursor),
-        _ => {
+        (&MappingStyle::Block, TokenType::BlockEnd) |
+        (&MappingStyle::Flow, TokenType::FlowMappingEnd) => {
+            cursor.leave_mapping();
+            Ok(None)
+        }
+        (&MappingStyle::Block, found) => {
             Err(get_error(ErrorDef::UnexpectedEntryMulti {
-                              expected: vec![TokenTypeDef::Scalar, TokenTypeDef::FlowSequenceStart],
-                              found: get_token_def(&token.1),
+                              expected: vec![TokenTypeDef::Key, TokenTypeDef::BlockEnd],
+                              found: get_token_def(&found),
+                          },
+                          Some(token.0)))
+        }
+        (&MappingStyle::Flow, found) => {
+            Err(get_error(ErrorDef::UnexpectedEntryMulti {
+                              expected: vec![TokenTypeDef::Key, TokenTypeDef::FlowMappingEnd],
+                              found: get_token_def(&found),
                           },
                           Some(token.0)))
         }
     }
 }
 
+// Logs each token read from a source at the trace level, when the `log` feature is enabled.
+#[cfg(feature = "log")]
+fn trace_token(token: &Token) {
+    trace!("{:?} at {:?}", token.1, token.0);
+}
+
+#[cfg(not(feature = "log"))]
+fn trace_token(_token: &Token) {}
+
+/// A source without the byte order mark some editors save at its start, which the scanner would
+/// otherwise read as part of the first node.
+pub fn strip_bom(source: &str) -> &str {
+    source.strip_prefix('\u{feff}').unwrap_or(source)
+}
+
 pub struct ForwardCursor<'a> {
-    scanner: Scanner<Chars<'a>>,
+    // None when replaying tokens that were read earlier.
+    scanner: Option<Scanner<Chars<'a>>>,
+    pending: VecDeque<Token>,
+    options: ParseOptions,
+    resolver: Option<&'a dyn IncludeResolver>,
+    // The path the source was loaded from, which the paths it refers to are relative to.
+    path: Option<String>,
+    // Errors recorded with `report`, when the cursor collects errors rather than failing on the
+    // first.
+    errors: Option<Vec<RamlError>>,
+    // Warnings about the keys skipped as `ParseOptions::unknown_keys` asks.
+    warnings: Vec<RamlWarning>,
+    // The keys of the nodes the tokens were read from, when replaying part of a document.
+    base_path: Vec<String>,
+    // The key being read in each mapping entered and not yet left, innermost last.
+    mapping_keys: Vec<Option<String>>,
+    // The files included by the source, when they were read up front.
+    files: Vec<SourceFile>,
+    // The included file the last token was read from, or None for the source itself.
+    file: Option<usize>,
+    // The nesting of the nodes read from the scanner, which `ParseOptions::max_depth` limits.
+    depth: usize,
+    // The size of the source read by the scanner, which `ParseOptions::max_source_bytes` limits.
+    source_bytes: usize,
 }
 
 impl<'a> ForwardCursor<'a> {
-    pub fn new(source: &str) -> ForwardCursor {
-        ForwardCursor { scanner: Scanner::new(source.chars()) }
+    pub fn new(source: &'a str) -> ForwardCursor<'a> {
+        ForwardCursor::with_options(source, ParseOptions::default())
     }
 
-    pub fn next_token(&mut self) -> Token {
-        // todo error handling
-        self.scanner.next().unwrap()
-        // let token_def = get_token_def(&token.1);
-        // println!("Token {}", token_def);
+    pub fn with_options(source: &'a str, options: ParseOptions) -> ForwardCursor<'a> {
+        ForwardCursor {
+            scanner: Some(Scanner::new(strip_bom(source).chars())),
+            pending: VecDeque::new(),
+            options,
+            resolver: None,
+            path: None,
+            errors: None,
+            warnings: vec![],
+            base_path: vec![],
+            mapping_keys: vec![],
+            files: vec![],
+            file: None,
+            depth: 0,
+            source_bytes: source.len(),
+        }
+    }
+
+    /// A cursor that loads the documents the source refers to with `resolver`. Included
+    /// documents are read up front, so that errors loading them are reported before parsing.
+    pub fn with_resolver(source: &'a str,
+                         options: ParseOptions,
+                         resolver: &'a dyn IncludeResolver,
+                         path: Option<&str>)
+                         -> Result<ForwardCursor<'a>, RamlError> {
+        let mut chain: Vec<String> = path.into_iter().map(|p| p.to_string()).collect();
+        let mut files = vec![];
+        let mut limits = IncludeLimits::new(&options, source)?;
+        let tokens =
+            get_included_tokens(source, resolver, &mut chain, &mut files, None, &mut limits)?;
+        // The included nodes are nested in the nodes that include them.
+        let mut depth = 0;
+        for token in &tokens {
+            count_depth(&mut depth, token, options.max_depth)?;
+        }
+        Ok(ForwardCursor {
+            scanner: None,
+            pending: tokens.into_iter().collect(),
+            options,
+            resolver: Some(resolver),
+            path: path.map(|p| p.to_string()),
+            errors: None,
+            warnings: vec![],
+            base_path: vec![],
+            mapping_keys: vec![],
+            files,
+            file: None,
+            depth: 0,
+            source_bytes: 0,
+        })
+    }
+
+    /// A cursor over tokens read earlier, such as those kept by `get_raw_value`. The tokens are
+    /// followed by the end of the stream.
+    pub fn from_tokens(tokens: Vec<Token>, options: ParseOptions) -> ForwardCursor<'a> {
+        let mut pending: VecDeque<Token> = tokens.into_iter().collect();
+        if let Some(marker) = pending.back().map(|t| t.0) {
+            pending.push_back(Token(marker, TokenType::StreamEnd));
+        }
+        ForwardCursor {
+            scanner: None,
+            pending,
+            options,
+            resolver: None,
+            path: None,
+            errors: None,
+            warnings: vec![],
+            base_path: vec![],
+            mapping_keys: vec![],
+            files: vec![],
+            file: None,
+            depth: 0,
+            source_bytes: 0,
+        }
+    }
+
+    /// A cursor over tokens read earlier from the node this cursor is in, whose key path
+    /// continues from this cursor's.
+    pub fn nested(&self, tokens: Vec<Token>) -> ForwardCursor<'a> {
+        ForwardCursor {
+            base_path: self.key_path(),
+            ..ForwardCursor::from_tokens(tokens, self.options.clone())
+        }
+    }
+
+    /// A cursor over tokens read earlier from this cursor, which loads the documents they refer
+    /// to in the same way. It fails on the first error.
+    pub fn replay(&self, tokens: Vec<Token>) -> ForwardCursor<'a> {
+        ForwardCursor {
+            resolver: self.resolver,
+            path: self.path.clone(),
+            ..self.nested(tokens)
+        }
+    }
+
+    /// Makes the cursor record the errors given to `report`, rather than failing with them.
+    pub fn collect_errors(&mut self) {
+        self.errors = Some(vec![]);
+    }
+
+    pub fn collects_errors(&self) -> bool {
+        self.errors.is_some()
+    }
+
+    /// Fails with `error`, unless the cursor collects errors, in which case the error is
+    /// recorded and parsing continues.
+    pub fn report(&mut self, error: RamlError) -> Result<(), RamlError> {
+        let error = self.annotate(error);
+        match self.errors {
+            Some(ref mut errors) => {
+                errors.push(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Reports the error of `result`, if any.
+    pub fn check(&mut self, result: Result<(), RamlError>) -> Result<(), RamlError> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(error) => self.report(error),
+        }
+    }
+
+    /// Records the start of a mapping, whose keys are added to the key path as they are read.
+    pub fn enter_mapping(&mut self) {
+        self.mapping_keys.push(None);
+    }
+
+    /// Records the key of the innermost mapping whose value is being read.
+    pub fn enter_key(&mut self, key: &str) {
+        // The string of the previous key is reused, as every key of a document is recorded.
+        match self.mapping_keys.last_mut() {
+            Some(&mut Some(ref mut current)) => {
+                current.clear();
+                current.push_str(key);
+            }
+            Some(current) => *current = Some(key.to_string()),
+            None => {}
+        }
+    }
+
+    /// Records the end of the innermost mapping.
+    pub fn leave_mapping(&mut self) {
+        self.mapping_keys.pop();
+    }
+
+    /// The keys of the nodes enclosing the next token, outermost first, e.g.
+    /// `["securitySchemes", "oauth_2_0", "describedBy"]`.
+    pub fn key_path(&self) -> Vec<String> {
+        let keys = self.mapping_keys.iter().filter_map(|key| key.clone());
+        self.base_path.iter().cloned().chain(keys).collect()
+    }
+
+    /// Gives an error the key path of the node it was found in, unless it already has one. As
+    /// the cursor is left as it was when the error was found, this can be done as the error is
+    /// returned from parsing.
+    pub fn annotate(&self, error: RamlError) -> RamlError {
+        if error.key_path().is_empty() {
+            error.with_key_path(self.key_path())
+        } else {
+            error
+        }
+    }
+
+    pub fn warn(&mut self, warning: RamlWarning) {
+        self.warnings.push(warning);
+    }
+
+    /// Adds the warnings of a cursor over part of this cursor's tokens.
+    pub fn extend_warnings(&mut self, warnings: Vec<RamlWarning>) {
+        self.warnings.extend(warnings);
+    }
+
+    /// The warnings reported so far, in the order they were found.
+    pub fn take_warnings(&mut self) -> Vec<RamlWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// The errors recorded so far, in the order they were found.
+    pub fn take_errors(&mut self) -> Vec<RamlError> {
+        self.errors.take().unwrap_or_default()
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    pub fn resolver(&self) -> Option<&'a dyn IncludeResolver> {
+        self.resolver
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// The files included by the source, which `SourceLocation::file` refers to.
+    pub fn source_files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// The included file the last token was read from, or None for the source itself.
+    pub fn source_file(&self) -> Option<usize> {
+        self.file
+    }
+
+    /// The location of a node starting at `marker`, a token just read, in the file it was read
+    /// from.
+    pub fn location(&self, marker: Marker) -> SourceLocation {
+        SourceLocation { file: self.file, ..get_location(marker) }
+    }
+
+    /// Reads the next token, failing when the source is not valid YAML.
+    pub fn next_token(&mut self) -> Result<Token, RamlError> {
+        loop {
+            let token = self.read_token()?;
+            match get_source_file(&token) {
+                Some(file) => self.file = file,
+                None => return Ok(token),
+            }
+        }
+    }
+
+    fn read_token(&mut self) -> Result<Token, RamlError> {
+        if let Some(token) = self.pending.pop_front() {
+            if self.scanner.is_none() && self.pending.is_empty() {
+                // The end of replayed tokens is returned however often it is read.
+                self.pending.push_back(token.clone());
+            }
+            return Ok(token);
+        }
+        match self.scanner {
+            Some(ref mut scanner) => {
+                let token = scanner.next().ok_or_else(|| get_scan_error(scanner))?;
+                trace_token(&token);
+                if let TokenType::StreamStart(_) = token.1 {
+                    check_limit("max_source_bytes",
+                                self.source_bytes,
+                                self.options.max_source_bytes,
+                                None)?;
+                }
+                count_depth(&mut self.depth, &token, self.options.max_depth)?;
+                Ok(token)
+            }
+            None => Err(get_error(ErrorDef::InvalidYaml { info: END_OF_STREAM.to_string() }, None)),
+        }
+    }
+
+    /// The next token, which is left to be read by `next_token`.
+    pub fn peek_token(&mut self) -> Result<&Token, RamlError> {
+        while let Some(file) = self.pending.front().and_then(get_source_file) {
+            self.file = file;
+            self.pending.pop_front();
+        }
+        if self.pending.is_empty() {
+            let token = self.next_token()?;
+            self.pending.push_back(token);
+        }
+        Ok(&self.pending[0])
+    }
+
+    /// The type of the next token, which is left to be read.
+    pub fn peek_def(&mut self) -> Result<TokenTypeDef, RamlError> {
+        self.peek_token().map(|token| get_token_def(&token.1))
+    }
+
+    /// Consumes the next token only if it is of the given type.
+    pub fn next_is(&mut self, token_type: TokenTypeDef) -> Result<bool, RamlError> {
+        if self.peek_def()? == token_type {
+            self.next_token()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// The position of the next token, which is left to be read.
+    pub fn peek_marker(&mut self) -> Result<Marker, RamlError> {
+        self.peek_token().map(|token| token.0)
+    }
+
+    /// The position of the value following the next token, the `Value` token of a key just read.
+    /// Both are left to be read.
+    pub fn peek_value_marker(&mut self) -> Result<Marker, RamlError> {
+        let value = self.next_token()?;
+        let marker = self.peek_marker()?;
+        self.put_back(value);
+        Ok(marker)
+    }
+
+    /// Returns a token to the cursor so that it is read again by the next call to `next_token`.
+    pub fn put_back(&mut self, token: Token) {
+        self.pending.push_front(token);
     }
 
     pub fn expect(&mut self, expected_token_type: TokenTypeDef) -> Result<(), RamlError> {
-        let token = self.next_token();
+        let token = self.next_token()?;
         let found_token_type = get_token_def(&token.1);
         if found_token_type == expected_token_type {
             Ok(())
@@ -290,4 +1115,123 @@ impl<'a> ForwardCursor<'a> {
                           Some(token.0)))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Tracks the nesting of the nodes of a document as its tokens are read, failing once it is
+/// deeper than `max_depth`. As the parser reads nested nodes recursively, this bounds the depth
+/// of its recursion.
+fn count_depth(depth: &mut usize, token: &Token, max_depth: usize) -> Result<(), RamlError> {
+    match token.1 {
+        TokenType::BlockMappingStart |
+        TokenType::BlockSequenceStart |
+        TokenType::FlowMappingStart |
+        TokenType::FlowSequenceStart => {
+            *depth += 1;
+            check_limit("max_depth", *depth, max_depth, Some(token.0))
+        }
+        TokenType::BlockEnd |
+        TokenType::FlowMappingEnd |
+        TokenType::FlowSequenceEnd => {
+            *depth = depth.saturating_sub(1);
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads the value of the single key of a document.
+    fn read_node(source: &str) -> YamlNode {
+        let mut cursor = ForwardCursor::new(source);
+        cursor.expect(TokenTypeDef::StreamStart).unwrap();
+        let style = get_mapping_start(&mut cursor).unwrap();
+        get_mapping_key(&mut cursor, &style).unwrap().unwrap();
+        let node = get_node(&mut cursor).unwrap();
+        assert!(get_mapping_key(&mut cursor, &style).unwrap().is_none());
+        node
+    }
+
+    // The node with its markers dropped, written in a compact form.
+    fn describe(node: &YamlNode) -> String {
+        match *node {
+            YamlNode::Scalar(ref value, _) => format!("'{}'", value),
+            YamlNode::Sequence(ref values, _) => {
+                let values: Vec<String> = values.iter().map(describe).collect();
+                format!("[{}]", values.join(", "))
+            }
+            YamlNode::Mapping(ref entries, _) => {
+                let entries: Vec<String> = entries.iter()
+                    .map(|(key, value)| format!("{}: {}", key, describe(value)))
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
+        }
+    }
+
+    fn line_and_column(marker: Marker) -> (usize, usize) {
+        let position = ::error_definitions::marker_position(marker);
+        (position.1, position.2 + 1)
+    }
+
+    #[test]
+    fn reads_mixed_nesting() {
+        let node = read_node("root:
+  name: books
+  tags:
+    - a
+    - b: [c, d]
+  limits:
+    rate: 10
+");
+        assert_eq!("{name: 'books', tags: ['a', {b: ['c', 'd']}], limits: {rate: '10'}}",
+                   describe(&node));
+    }
+
+    #[test]
+    fn reads_flow_mappings() {
+        let node = read_node("root: { a: 1, b: { c: [x, y] }, d: [ { e: f } ] }");
+        assert_eq!("{a: '1', b: {c: ['x', 'y']}, d: [{e: 'f'}]}", describe(&node));
+    }
+
+    #[test]
+    fn reads_empty_values() {
+        let node = read_node("root:
+  a:
+  b: []
+  c: {}
+  d:
+    -
+    - x
+");
+        assert_eq!("{a: '', b: [], c: {}, d: ['', 'x']}", describe(&node));
+        assert_eq!("''", describe(&read_node("root:")));
+    }
+
+    #[test]
+    fn keeps_the_position_of_each_node() {
+        let node = read_node("root:
+  a: x
+  b: [y]
+");
+        let entries = match node {
+            YamlNode::Mapping(entries, marker) => {
+                assert_eq!((2, 3), line_and_column(marker));
+                entries
+            }
+            _ => panic!("Expected a mapping"),
+        };
+        match entries[1].1 {
+            YamlNode::Sequence(ref values, marker) => {
+                assert_eq!((3, 6), line_and_column(marker));
+                match values[0] {
+                    YamlNode::Scalar(_, marker) => assert_eq!((3, 7), line_and_column(marker)),
+                    _ => panic!("Expected a scalar"),
+                }
+            }
+            _ => panic!("Expected a sequence"),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+use std::collections::HashMap;
+
+#[test]
+fn loads_annotation_types() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  deprecated: nil
+  rateLimit:
+    type: integer
+    allowedTargets: [Resource, Method]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let annotation_types = raml.annotation_types().unwrap();
+    assert_eq!(Some(&named_type("nil")),
+               annotation_types.get("deprecated").unwrap().type_());
+    let rate_limit = annotation_types.get("rateLimit").unwrap();
+    assert_eq!(Some(&named_type("integer")), rate_limit.type_());
+    assert_eq!(Some(&vec!["Resource".to_string(), "Method".to_string()]),
+               rate_limit.allowed_targets());
+}
+
+#[test]
+fn loads_annotations_on_the_document_root() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  deprecated: nil
+(deprecated):";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let annotations = raml.annotations().unwrap();
+    assert_eq!(&ExampleValue::Null, annotations.get("deprecated").unwrap().value());
+}
+
+#[test]
+fn loads_annotations_on_resources_and_methods() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  rateLimit: integer
+  owner: string
+/users:
+  (rateLimit): 50
+  get:
+    (owner): users";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let resource_annotations = resources[0].annotations().unwrap();
+    assert_eq!(&ExampleValue::Number(50.0),
+               resource_annotations.get("rateLimit").unwrap().value());
+    let method_annotations = resources[0].methods().get("get").unwrap().annotations().unwrap();
+    assert_eq!(&ExampleValue::String("users".to_string()),
+               method_annotations.get("owner").unwrap().value());
+}
+
+#[test]
+fn loads_annotations_on_types() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  owner: string
+types:
+  User:
+    (owner): accounts";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let annotations = types.get("User").unwrap().annotations().unwrap();
+    assert_eq!(&ExampleValue::String("accounts".to_string()),
+               annotations.get("owner").unwrap().value());
+}
+
+#[test]
+fn error_for_undeclared_annotation_type() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    (rateLimit): 50";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing annotation. Annotation type not declared: rateLimit at \
+                         line 5 column 5");
+}
+
+#[test]
+fn error_for_annotation_applied_to_disallowed_target() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  rateLimit:
+    type: integer
+    allowedTargets: Method
+(rateLimit): 50";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing annotation rateLimit. Not allowed on target: API at line 7 \
+                         column 1");
+}
+
+#[test]
+fn error_for_annotation_from_undeclared_library() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  (common.rateLimit): 50";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing reference. Library not declared: common at line 4 column \
+                         3");
+}
+
+#[test]
+fn keeps_the_values_of_annotations_as_written() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  monitoring:
+(monitoring): { enabled: true, checks: [latency] }";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let entries = match raml.extra().unwrap()["(monitoring)"] {
+        YamlNode::Mapping(ref entries, _) => entries,
+        ref node => panic!("Expected a mapping, found {:?}", node),
+    };
+    assert_eq!("enabled", entries[0].0);
+    match entries[0].1 {
+        YamlNode::Scalar(ref value, _) => assert_eq!("true", value),
+        ref node => panic!("Expected a scalar, found {:?}", node),
+    }
+    match entries[1].1 {
+        YamlNode::Sequence(ref values, _) => assert_eq!(1, values.len()),
+        ref node => panic!("Expected a sequence, found {:?}", node),
+    }
+    assert!(raml.annotations().unwrap().contains_key("monitoring"));
+}
+
+#[test]
+fn finds_the_nodes_an_annotation_is_applied_to() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  deprecated: nil
+  beta: nil
+(deprecated):
+/users:
+  (deprecated):
+  /{userId}:
+    get:
+      (deprecated):
+    put:
+      (beta):";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let nodes: Vec<(String, &str)> = raml.find_annotations("deprecated")
+        .map(|node| (node.path().to_string(), node.kind()))
+        .collect();
+    assert_eq!(vec![("".to_string(), "API"),
+                    ("/users".to_string(), "Resource"),
+                    ("get /users/{userId}".to_string(), "Method")],
+               nodes);
+    assert_eq!(0, raml.find_annotations("missing").count());
+}
+
+#[test]
+fn finds_annotations_with_library_qualified_names() {
+    let mut resolver = HashMap::new();
+    resolver.insert("libraries/common.raml".to_string(),
+                    "#%RAML 1.0 Library
+uses:
+  shared: shared.raml"
+                        .to_string());
+    resolver.insert("libraries/shared.raml".to_string(),
+                    "#%RAML 1.0 Library
+annotationTypes:
+  deprecated: nil"
+                        .to_string());
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+types:
+  User:
+    properties:
+      name:
+        type: string
+        (common.shared.deprecated):";
+    let raml = assert_ok_and_unwrap(RamlParser::load_from_str_with_resolver(s, &resolver));
+    let nodes: Vec<AnnotatedNode> = raml.find_annotations("common.shared.deprecated").collect();
+    assert_eq!(1, nodes.len());
+    assert_eq!("User.name", nodes[0].path());
+    assert_eq!("TypeDeclaration", nodes[0].kind());
+    let name = &raml.types().unwrap()["User"].properties().unwrap()["name"];
+    assert!(raml.find_annotation(name.property_type().annotations(), "common.shared.deprecated")
+        .is_some());
+    assert!(raml.find_annotation(name.property_type().annotations(), "deprecated").is_none());
+}
@@ -0,0 +1,241 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+#[test]
+fn loads_body_with_explicit_media_type() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      application/json:
+        type: Person
+        example: '{\"name\": \"Dan\"}'";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let body = resources[0].methods().get("post").unwrap().body().unwrap();
+    assert_eq!(1, body.len());
+    assert_eq!("application/json", body[0].media_type());
+    assert_eq!(Some(&named_type("Person")), body[0].body_type());
+    let example = ExampleValue::String("{\"name\": \"Dan\"}".to_string());
+    assert_eq!(&example, body[0].example().unwrap().value());
+}
+
+#[test]
+fn loads_body_with_multiple_media_types() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      application/json: Person
+      application/xml:
+        type: PersonXml
+      text/plain:";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let body = resources[0].methods().get("post").unwrap().body().unwrap();
+    let media_types: Vec<&str> = body.iter().map(|b| b.media_type()).collect();
+    assert_eq!(vec!["application/json", "application/xml", "text/plain"], media_types);
+    assert_eq!(Some(&named_type("Person")), body[0].body_type());
+    assert_eq!(Some(&named_type("PersonXml")), body[1].body_type());
+    assert_eq!(None, body[2].body_type());
+}
+
+#[test]
+fn expands_body_using_root_media_types() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    responses:
+      200:
+        body:
+          type: Person
+mediaType: [application/json, application/xml]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let responses = resources[0].methods().get("get").unwrap().responses().unwrap();
+    let body = responses.get(&200).unwrap().body().unwrap();
+    assert_eq!(2, body.len());
+    assert_eq!("application/json", body[0].media_type());
+    assert_eq!(Some(&named_type("Person")), body[0].body_type());
+    assert_eq!("application/xml", body[1].media_type());
+    assert_eq!(Some(&named_type("Person")), body[1].body_type());
+}
+
+#[test]
+fn error_for_default_body_without_root_media_type() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      type: Person";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing body. A media type must be given when there is no \
+                         mediaType at the document root at line 5 column 5");
+}
+
+#[test]
+fn error_for_default_response_body_is_at_its_body_key() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    responses:
+      200:
+        body:
+          type: Person";
+    let error = assert_error_kind(parse(s), ErrorKind::MissingDefaultMediaType);
+    assert_eq!(Some(7), error.line());
+    assert_eq!(Some(9), error.column());
+}
+
+#[test]
+fn error_for_mixing_media_types_and_type_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+mediaType: application/json
+/users:
+  post:
+    body:
+      application/json:
+        type: Person
+      type: Person";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the body: type at line 9 column 7 (in /users > \
+                         post > body > type)");
+}
+
+#[test]
+fn loads_body_with_type_expression() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      application/json: Person[]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let body = resources[0].methods().get("post").unwrap().body().unwrap();
+    assert_eq!("Person[]", body[0].body_type().unwrap().to_string());
+}
+
+#[test]
+fn loads_structured_body_example() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      application/json:
+        example:
+          name: Dan
+          age: 40
+          admin: false
+          manager: ~
+          roles: [reader, writer]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let body = resources[0].methods().get("post").unwrap().body().unwrap();
+    let expected = ExampleValue::Mapping(vec![
+        ("name".to_string(), ExampleValue::String("Dan".to_string())),
+        ("age".to_string(), ExampleValue::Number(40.0)),
+        ("admin".to_string(), ExampleValue::Boolean(false)),
+        ("manager".to_string(), ExampleValue::Null),
+        ("roles".to_string(),
+         ExampleValue::Sequence(vec![ExampleValue::String("reader".to_string()),
+                                     ExampleValue::String("writer".to_string())])),
+    ]);
+    assert_eq!(&expected, body[0].example().unwrap().value());
+}
+
+#[test]
+fn loads_named_body_examples() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      text/plain:
+        examples:
+          short: hello
+          long:
+            displayName: A longer example
+            strict: false
+            value: |
+              first line
+              second line";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let body = resources[0].methods().get("post").unwrap().body().unwrap();
+    let examples = body[0].examples().unwrap();
+    assert_eq!(&ExampleValue::String("hello".to_string()),
+               examples.get("short").unwrap().value());
+    let long = examples.get("long").unwrap();
+    assert_eq!(&ExampleValue::String("first line\nsecond line".to_string()),
+               long.value());
+    assert_eq!(Some("A longer example"), long.display_name());
+    assert_eq!(Some(false), long.strict());
+}
+
+#[test]
+fn loads_the_fields_of_a_form_body() {
+    let s = "#%RAML 1.0
+title: Some API
+/photos:
+  post:
+    body:
+      multipart/form-data:
+        properties:
+          file:
+            type: file
+            fileTypes: [image/png, image/jpeg]
+            maxLength: 307200
+          description?: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let body = &raml.resources()[0].methods()["post"].body().unwrap()[0];
+    assert_eq!("multipart/form-data", body.media_type());
+    assert!(body.body_type().is_none());
+    let properties = body.form_properties().unwrap();
+    let file = properties["file"].property_type();
+    assert_eq!(Some(BuiltInType::File), file.built_in_type());
+    assert_eq!(Some(&["image/png".to_string(), "image/jpeg".to_string()][..]),
+               file.file_types());
+    let description = &properties["description"];
+    assert!(!description.required());
+    assert_eq!(Some(BuiltInType::String), description.property_type().built_in_type());
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn error_for_form_fields_of_a_body_that_is_not_an_object() {
+    let s = "#%RAML 1.0
+title: Some API
+/photos:
+  post:
+    body:
+      application/x-www-form-urlencoded:
+        type: string
+        properties:
+          name: string";
+    assert_error_result(parse(s),
+                        "Error parsing body. Properties can't be declared for a body of type \
+                         string at line 8 column 9 (in /photos > post > body > \
+                         application/x-www-form-urlencoded > properties)");
+}
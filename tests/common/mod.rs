@@ -10,12 +10,23 @@ pub fn assert_ok_and_unwrap(result: RamlResult) -> Raml {
     if result.is_err() {
         println!("Unexpected error {:?}", result);
     }
-    assert_eq!(result.is_ok(), true);
+    assert!(result.is_ok());
     result.ok().unwrap()
 }
 
 pub fn assert_error_result(result: RamlResult, expected_error: &str) {
-    assert_eq!(result.is_err(), true);
+    assert!(result.is_err());
     let err = result.err().unwrap();
     assert_eq!(err.error(), expected_error);
-}
\ No newline at end of file
+}
+
+pub fn assert_error_kind(result: RamlResult, kind: ErrorKind) -> RamlError {
+    assert!(result.is_err());
+    let err = result.err().unwrap();
+    assert_eq!(kind, err.kind());
+    err
+}
+
+pub fn named_type(name: &str) -> TypeExpression {
+    TypeExpression::Named(name.to_string())
+}
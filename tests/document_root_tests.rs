@@ -7,6 +7,9 @@ use raml_parser::*;
 mod common;
 
 use common::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
 
 #[test]
 fn error_for_missing_version_comment() {
@@ -16,12 +19,131 @@ fn error_for_missing_version_comment() {
                         "Document must start with the following RAML comment line: #%RAML 1.0");
 }
 
+#[test]
+fn error_for_unsupported_version() {
+    let s = "#%RAML 0.8
+title: Some API";
+    let result = parse(s);
+    assert_error_result(result, "RAML version 0.8 is not supported; only 1.0");
+}
+
+#[test]
+fn unsupported_version_error_has_kind_and_version() {
+    let s = "#%RAML 0.8
+title: Some API";
+    let err = assert_error_kind(parse(s), ErrorKind::UnsupportedRamlVersion);
+    assert_eq!(Some("0.8"), err.version());
+}
+
+#[test]
+fn error_for_version_comment_with_unknown_fragment() {
+    let s = "#%RAML 1.0 Unknown
+title: Some API";
+    assert_error_kind(parse(s), ErrorKind::MissingRamlVersion);
+}
+
+#[test]
+fn accepts_byte_order_mark_and_crlf_line_endings() {
+    let s = "\u{feff}#%RAML 1.0 \r\ntitle: Some API\r\nversion: v1\r\n";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!("Some API", raml.title());
+    assert_eq!(Some("v1"), raml.version());
+}
+
 #[test]
 fn error_for_missing_title() {
     let s = "#%RAML 1.0
     version: v1";
     let result = parse(s);
-    assert_error_result(result, "Error parsing document root. Missing field: title");
+    assert_error_result(result,
+                        "Error parsing document root. Missing field: title at line 2 column 5");
+}
+
+#[test]
+fn missing_title_error_has_kind_and_field() {
+    let s = "#%RAML 1.0
+version: v1";
+    let err = assert_error_kind(parse(s), ErrorKind::MissingField);
+    assert_eq!(Some("title"), err.field());
+    assert_eq!(None, err.name());
+}
+
+#[test]
+fn error_for_title_without_a_value() {
+    let s = "#%RAML 1.0
+title:
+version: v1";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Missing field: title at line 2 column 1 \
+                         (in title)");
+}
+
+#[test]
+fn error_for_empty_title() {
+    let s = "#%RAML 1.0
+version: v1
+title: \"\"";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Missing field: title at line 3 column 1 \
+                         (in title)");
+}
+
+#[test]
+fn error_for_blank_title() {
+    let s = "#%RAML 1.0
+title: \"   \"";
+    assert_error_kind(parse(s), ErrorKind::MissingField);
+}
+
+#[test]
+fn validation_reports_an_empty_title_once() {
+    let s = "#%RAML 1.0
+title: \"\"
+version: v1";
+    let errors = RamlParser::validate(s);
+    assert_eq!(1, errors.len());
+    assert_eq!(ErrorKind::MissingField, errors[0].kind());
+}
+
+#[test]
+fn numeric_version_is_read_as_a_string() {
+    let s = "#%RAML 1.0
+title: Some API
+version: 2";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("2"), raml.version());
+}
+
+#[test]
+fn parses_documents_on_separate_threads() {
+    let threads: Vec<_> = vec!["First API", "Second API"]
+        .into_iter()
+        .map(|title| {
+            thread::spawn(move || {
+                let s = format!("#%RAML 1.0\ntitle: {}\n/users:\n  get:", title);
+                parse(&s)
+            })
+        })
+        .collect();
+    let titles: Vec<String> = threads.into_iter()
+        .map(|thread| assert_ok_and_unwrap(thread.join().unwrap()).title().to_string())
+        .collect();
+    assert_eq!(vec!["First API", "Second API"], titles);
+}
+
+#[test]
+fn shares_a_document_between_threads() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:";
+    let raml = Arc::new(assert_ok_and_unwrap(parse(s)));
+    let shared = Arc::clone(&raml);
+    let count = thread::spawn(move || shared.iter_operations().count()).join().unwrap();
+    assert_eq!(1, count);
+    assert_eq!("Some API", raml.title());
 }
 
 #[test]
@@ -43,6 +165,21 @@ fn loads_the_version() {
     assert_eq!("v1", raml.version().unwrap());
 }
 
+#[test]
+fn accessors_borrow_the_document() {
+    let s = "#%RAML 1.0
+    title: Some API
+    version: v1
+    description: Sample description
+    protocols: [https]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(Some("v1"), raml.version());
+    assert_eq!(Some("Sample description"), raml.description());
+    assert_eq!(Some(&[Protocol::Https][..]), raml.protocols());
+    assert_eq!("Some API", raml.title());
+}
+
 #[test]
 fn loads_the_description() {
     let s = "#%RAML 1.0
@@ -57,13 +194,152 @@ fn loads_the_description() {
 fn loads_the_base_uri() {
     let s = "#%RAML 1.0
     title: Some API
+    version: v1
     baseUri: https://some.api.com/{version}";
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
     assert_eq!("https://some.api.com/{version}", raml.base_uri().unwrap());
 }
 
-// todo baseUriParameters
+#[test]
+fn error_for_base_uri_version_without_version() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://some.api.com/{version}";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. The baseUri refers to {version}, but no \
+                         version is declared at line 3 column 10");
+}
+
+#[test]
+fn error_for_relative_base_uri() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: some.api.com/v1";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. The baseUri must be an absolute URI: \
+                         some.api.com/v1 at line 3 column 10");
+}
+
+#[test]
+fn loads_base_uri_starting_with_a_template() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: \"{host}/v1\"
+baseUriParameters:
+  host:
+    type: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("{host}/v1"), raml.base_uri());
+    assert_eq!(None, raml.protocols());
+}
+
+#[test]
+fn infers_protocols_from_base_uri() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: HTTPS://some.api.com";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some(&[Protocol::Https][..]), raml.protocols());
+    assert!(raml.protocols_inferred());
+}
+
+#[test]
+fn declared_protocols_are_not_inferred() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://some.api.com
+protocols: [ HTTP, HTTPS ]";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some(&[Protocol::Http, Protocol::Https][..]), raml.protocols());
+    assert!(!raml.protocols_inferred());
+}
+
+#[test]
+fn loads_a_base_uri_parameter() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://{bucketName}.s3.amazonaws.com
+baseUriParameters:
+  bucketName:
+    description: The name of the bucket
+    type: string
+    required: true";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let parameters = raml.base_uri_parameters().unwrap();
+    let bucket_name = parameters.get("bucketName").unwrap();
+    assert_eq!(Some("The name of the bucket"), bucket_name.description());
+    assert_eq!(Some("string"), bucket_name.param_type());
+    assert!(bucket_name.required());
+}
+
+#[test]
+fn loads_multiple_base_uri_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://{region}.api.com/{tenant}/{version}
+version: v1
+baseUriParameters:
+  region:
+    type: string
+  tenant:
+    description: The tenant identifier
+    required: false";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let parameters = raml.base_uri_parameters().unwrap();
+    assert_eq!(2, parameters.len());
+    assert_eq!(Some("string"), parameters.get("region").unwrap().param_type());
+    assert_eq!(None, parameters.get("region").unwrap().description());
+    assert_eq!(Some("The tenant identifier"),
+               parameters.get("tenant").unwrap().description());
+    assert!(!parameters.get("tenant").unwrap().required());
+}
+
+#[test]
+fn error_for_reserved_version_base_uri_parameter() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://some.api.com/{version}
+baseUriParameters:
+  version:
+    type: string";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Reserved base uri parameter: version at \
+                         line 5 column 3");
+}
+
+#[test]
+fn error_for_base_uri_parameter_missing_from_base_uri() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://some.api.com
+baseUriParameters:
+  bucketName:
+    type: string";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Base uri parameter not found in baseUri: \
+                         bucketName at line 5 column 3");
+}
+
+#[test]
+fn error_for_non_boolean_required_base_uri_parameter() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://{bucketName}.s3.amazonaws.com
+baseUriParameters:
+  bucketName:
+    required: maybe";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing required. Expected true or false, Found maybe at line 6 \
+                         column 15 (in baseUriParameters > bucketName > required)");
+}
 
 #[test]
 fn loads_the_protocols_ignoring_casing() {
@@ -83,7 +359,8 @@ title: Some API
 protocols: []";
     let result = parse(s);
     assert_error_result(result,
-                        "Error parsing document root. Protocols must not be empty");
+                        "Error parsing document root. Protocols must not be empty at line 3 \
+                         column 1 (in protocols)");
 }
 
 #[test]
@@ -93,8 +370,55 @@ title: Some API
 protocols: http";
     let result = parse(s);
     assert_error_result(result,
-                        "Unexpected entry found. Expected Flow-Sequence-Start, Found Scalar at \
-                         line 3 column 12");
+                        "Unexpected entry found. Expected one of \
+                         Flow-Sequence-Start,Block-Sequence-Start, Found Scalar at line 3 \
+                         column 12 (in protocols)");
+}
+
+#[test]
+fn loads_protocols_in_block_style() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols:
+  - HTTP
+  - HTTPS";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some(&[Protocol::Http, Protocol::Https][..]), raml.protocols());
+}
+
+#[test]
+fn loads_protocols_in_block_style_at_the_key_indentation() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols:
+- HTTPS
+version: v1";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some(&[Protocol::Https][..]), raml.protocols());
+    assert_eq!(Some("v1"), raml.version());
+}
+
+#[test]
+fn error_for_protocols_without_entries() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols:
+version: v1";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Protocols must not be empty at line 3 \
+                         column 1 (in protocols)");
+}
+
+#[test]
+fn loads_media_types_in_block_style() {
+    let s = "#%RAML 1.0
+title: Some API
+mediaType:
+  - application/json
+  - application/xml";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(vec!["application/json", "application/xml"], media_types(&raml));
 }
 
 #[test]
@@ -104,7 +428,8 @@ title: Some API
 protocols: [Invalid]";
     let result = parse(s);
     assert_error_result(result,
-                        "Error parsing document root. Unexpected protocol at line 3 column 13");
+                        "Error parsing document root. Unexpected protocol at line 3 column 13 (in \
+                         protocols)");
 }
 
 #[test]
@@ -114,7 +439,7 @@ title: Some API
 mediaType: application/json";
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
-    assert_eq!(vec!["application/json"], raml.media_types().unwrap());
+    assert_eq!(vec!["application/json"], media_types(&raml));
 }
 
 #[test]
@@ -124,8 +449,46 @@ title: Some API
 mediaType: [application/json, application/xml]";
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
-    assert_eq!(vec!["application/json", "application/xml"],
-               raml.media_types().unwrap());
+    assert_eq!(vec!["application/json", "application/xml"], media_types(&raml));
+}
+
+#[test]
+fn error_for_invalid_media_type() {
+    let s = "#%RAML 1.0
+title: Some API
+mediaType: [application/json, applicationjson]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Invalid media type, expected type/subtype: applicationjson at line 3 \
+                         column 31 (in mediaType)");
+}
+
+#[test]
+fn error_for_media_type_that_is_not_a_string() {
+    let s = "#%RAML 1.0
+title: Some API
+mediaType:
+  - application/json
+  - yes";
+    let result = parse(s);
+    assert!(result.is_err());
+}
+
+#[test]
+fn media_type_parts() {
+    let s = "#%RAML 1.0
+title: Some API
+mediaType: application/hal+json; charset=utf-8";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let media_type = &raml.media_types().unwrap()[0];
+    assert_eq!("application", media_type.type_());
+    assert_eq!("hal+json", media_type.subtype());
+    assert_eq!(Some("json"), media_type.suffix());
+    assert_eq!("application/hal+json; charset=utf-8", media_type.to_string());
+}
+
+fn media_types(raml: &Raml) -> Vec<&str> {
+    raml.media_types().unwrap().iter().map(MediaType::as_str).collect()
 }
 
 #[test]
@@ -147,7 +510,7 @@ documentation:
 
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
-    assert_eq!(vec![RamlDocumentation::new("Doc Title".to_string(), "Doc Content".to_string())],
+    assert_eq!(&vec![RamlDocumentation::new("Doc Title".to_string(), "Doc Content".to_string())],
                raml.documentation().unwrap());
 }
 
@@ -161,13 +524,134 @@ documentation:
  - title: Doc Title2
    content: Doc Content2";
 
-    let expected = vec![RamlDocumentation::new("Doc Title".to_string(), "Doc Content".to_string()),
-                        RamlDocumentation::new("Doc Title2".to_string(),
-                                               "Doc Content2".to_string())];
+    let expected = RamlBuilder::new()
+        .title("Some API")
+        .documentation("Doc Title", "Doc Content")
+        .documentation("Doc Title2", "Doc Content2")
+        .build();
 
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
-    assert_eq!(expected, raml.documentation().unwrap());
+    assert_eq!(assert_ok_and_unwrap(expected), raml);
+}
+
+#[test]
+fn loads_documentation_in_flow_style() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation: [ { title: Doc Title, content: Doc Content }, { title: FAQ, content: None } ]";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let expected = vec![RamlDocumentation::new("Doc Title".to_string(), "Doc Content".to_string()),
+                        RamlDocumentation::new("FAQ".to_string(), "None".to_string())];
+    assert_eq!(&expected, raml.documentation().unwrap());
+}
+
+#[test]
+fn builds_the_same_document_as_parsed() {
+    let s = "#%RAML 1.0
+title: Some API
+description: All of it.
+version: v1
+baseUri: https://api.example.com/{version}
+protocols: [HTTP, HTTPS]
+mediaType: [application/json, application/xml]
+documentation:
+  - title: Home
+    content: Welcome.
+securitySchemes:
+  basic:
+    type: Basic Authentication
+    description: A user name and password.
+securedBy: [null, basic]";
+    let scheme = SecuritySchemeBuilder::new(SecuritySchemeType::BasicAuthentication)
+        .description("A user name and password.")
+        .build();
+    let built = RamlBuilder::new()
+        .title("Some API")
+        .description("All of it.")
+        .version("v1")
+        .base_uri("https://api.example.com/{version}")
+        .protocol(Protocol::Http)
+        .protocol(Protocol::Https)
+        .media_type("application/json")
+        .media_type("application/xml")
+        .documentation("Home", "Welcome.")
+        .security_scheme("basic", scheme)
+        .secured_by(SecuredBy::Null)
+        .secured_by(SecuredBy::Scheme("basic".to_string()))
+        .build();
+    assert_eq!(assert_ok_and_unwrap(parse(s)), assert_ok_and_unwrap(built));
+}
+
+#[test]
+fn error_building_a_document_without_a_title() {
+    let error = RamlBuilder::new().version("v1").build().unwrap_err();
+    assert_eq!(ErrorKind::MissingField, error.kind());
+    assert_eq!(Some("title"), error.field());
+}
+
+#[test]
+fn loads_a_root_in_flow_style() {
+    let s = "#%RAML 1.0
+{ title: Some API, version: v1 }";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!("Some API", raml.title());
+    assert_eq!(Some("v1"), raml.version());
+}
+
+fn block_scalar_description(indicator: &str) -> Option<String> {
+    let s = format!("#%RAML 1.0
+title: Some API
+description: {}
+  Line one
+  continues
+
+  Line two
+    indented
+
+version: v1",
+                    indicator);
+    let raml = assert_ok_and_unwrap(parse(&s));
+    raml.description().map(|d| d.to_string())
+}
+
+#[test]
+fn literal_description_keeps_newlines() {
+    assert_eq!(Some("Line one\ncontinues\n\nLine two\n  indented\n".to_string()),
+               block_scalar_description("|"));
+    assert_eq!(Some("Line one\ncontinues\n\nLine two\n  indented".to_string()),
+               block_scalar_description("|-"));
+    assert_eq!(Some("Line one\ncontinues\n\nLine two\n  indented\n\n".to_string()),
+               block_scalar_description("|+"));
+}
+
+#[test]
+fn folded_description_folds_lines() {
+    assert_eq!(Some("Line one continues\nLine two\n  indented\n".to_string()),
+               block_scalar_description(">"));
+    assert_eq!(Some("Line one continues\nLine two\n  indented".to_string()),
+               block_scalar_description(">-"));
+    assert_eq!(Some("Line one continues\nLine two\n  indented\n\n".to_string()),
+               block_scalar_description(">+"));
+}
+
+#[test]
+fn documentation_content_keeps_block_scalar_style() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+  - title: Literal
+    content: |
+      # Heading
+
+      Some text
+  - title: Folded
+    content: >-
+      Some text
+      over two lines";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let contents: Vec<&str> = raml.documentation().unwrap().iter().map(|d| d.content()).collect();
+    assert_eq!(vec!["# Heading\n\nSome text\n", "Some text over two lines"], contents);
 }
 
 #[test]
@@ -177,8 +661,8 @@ title: Some API
 documentation:";
     let result = parse(s);
     assert_error_result(result,
-                        "Unexpected entry found. Expected Block-Sequence-Start, Found Block-End \
-                         at line 4 column 1")
+                        "Unexpected entry found. Expected Block-Sequence-Start, Found Block-End at \
+                         line 4 column 1 (in documentation)")
 }
 
 #[test]
@@ -192,8 +676,8 @@ documentation:
 
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
-    assert_eq!(vec![RamlDocumentation::new("Doc Title".to_string(),
-                                           "Here is some content over multiple lines"
+    assert_eq!(&vec![RamlDocumentation::new("Doc Title".to_string(),
+                                            "Here is some content over multiple lines"
                                                .to_string())],
                raml.documentation().unwrap());
 }
@@ -207,7 +691,93 @@ documentation:
    content: Doc Content";
     let result = parse(s);
     assert_error_result(result,
-                        "Unexpected field found at the documentation: title1 at line 4 column 4")
+                        "Unexpected field found at the documentation: title1 at line 4 column 4 \
+                         (in documentation > title1)")
+}
+
+#[test]
+fn error_for_duplicate_root_key() {
+    let s = "#%RAML 1.0
+title: Some API
+version: v1
+description: Some description
+version: v2";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Duplicate key: version at line 5 column 1 \
+                         (in version)");
+}
+
+#[test]
+fn validate_reports_duplicate_root_key() {
+    let s = "#%RAML 1.0
+title: Some API
+title: Another API";
+    let errors = RamlParser::validate(s);
+    assert_eq!(1, errors.len());
+    assert_eq!(ErrorKind::DuplicateKey, errors[0].kind());
+    assert_eq!(Some("title"), errors[0].field());
+    assert_eq!(Some(3), errors[0].line());
+}
+
+#[test]
+fn error_for_duplicate_documentation_key() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+ - title: Doc title
+   content: Doc Content
+   title: Another title";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing documentation. Duplicate key: title at line 6 column 4 (in \
+                         documentation > title)")
+}
+
+#[test]
+fn loads_documentation_with_content_first_and_padded_keys() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+ - content: Doc Content
+   title: Doc Title
+ - \" title \": Doc Title2
+   \"content \": Doc Content2";
+
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(&vec![RamlDocumentation::new("Doc Title".to_string(), "Doc Content".to_string()),
+                     RamlDocumentation::new("Doc Title2".to_string(),
+                                            "Doc Content2".to_string())],
+               raml.documentation().unwrap());
+}
+
+#[test]
+fn loads_annotations_on_documentation() {
+    let s = "#%RAML 1.0
+title: Some API
+annotationTypes:
+  since: string
+documentation:
+ - title: Doc Title
+   content: Doc Content
+   (since): v2";
+
+    let raml = assert_ok_and_unwrap(parse(s));
+    let entry = &raml.documentation().unwrap()[0];
+    assert_eq!(&ExampleValue::String("v2".to_string()),
+               entry.annotations().unwrap().get("since").unwrap().value());
+}
+
+#[test]
+fn error_for_undeclared_annotation_on_documentation() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+ - title: Doc Title
+   content: Doc Content
+   (since): v2";
+
+    assert_error_kind(parse(s), ErrorKind::UndeclaredAnnotationType);
 }
 
 #[test]
@@ -217,11 +787,113 @@ title: Some API
 documentation:
  - content: Doc Content";
     let result = parse(s);
-    assert_error_result(result, "Error parsing documentation. Missing field: title")
+    assert_error_result(result,
+                        "Error parsing documentation. Missing field: title at line 4 column 4 (in \
+                         documentation)")
+}
+
+#[test]
+fn error_missing_documentation_content() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+ - title: Doc title";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing documentation. Missing field: content at line 4 column 4 \
+                         (in documentation)")
+}
+
+#[test]
+fn error_missing_documentation_title_and_content() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+ - title: Doc title
+   content: Doc Content
+ - displayName: Other";
+    let options = ParseOptions {
+        unknown_keys: UnknownKeyBehaviour::Ignore,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_str_with_options(s, &options);
+    assert_error_result(result,
+                        "Error parsing documentation. Missing field: title at line 6 column 4 (in \
+                         documentation)")
+}
+
+#[test]
+fn error_for_empty_documentation_entry() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+ - {}";
+    let err = parse(s).err().unwrap();
+    assert_eq!(ErrorKind::MissingField, err.kind());
+    assert_eq!(Some("title"), err.field());
+    assert_eq!(Some(4), err.line());
 }
 
-// Missing title
-// Missing content
+#[test]
+fn error_has_line_and_column() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols: [ HTTP, FTP ]";
+    let err = parse(s).err().unwrap();
+    assert_eq!("Error parsing document root. Unexpected protocol", err.message());
+    assert_eq!(Some(3), err.line());
+    assert_eq!(Some(20), err.column());
+    assert_eq!(Some(46), err.index());
+}
+
+#[test]
+fn error_without_position() {
+    let s = "title: Some API";
+    let err = parse(s).err().unwrap();
+    assert_eq!(err.error(), err.message());
+    assert_eq!(None, err.line());
+    assert_eq!(None, err.column());
+}
+
+#[test]
+fn error_has_key_path() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    describedBy:
+      headers: [ Authorization ]";
+    let err = parse(s).err().unwrap();
+    assert_eq!(vec!["securitySchemes", "oauth_2_0", "describedBy", "headers"],
+               err.key_path());
+    assert_eq!("Unexpected entry found. Expected one of Block-Mapping-Start,Flow-Mapping-Start, \
+                Found Flow-Sequence-Start",
+               err.message());
+}
+
+#[test]
+fn error_about_document_root_has_no_key_path() {
+    let s = "#%RAML 1.0
+version: v1";
+    let err = parse(s).err().unwrap();
+    assert!(err.key_path().is_empty());
+    assert_eq!("Error parsing document root. Missing field: title at line 2 column 1",
+               err.error());
+}
+
+#[test]
+fn validate_gives_each_error_its_key_path() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols: [ FTP ]
+/books:
+  get:
+    responses:
+      ok:";
+    let errors = RamlParser::validate(s);
+    let paths: Vec<&[String]> = errors.iter().map(|e| e.key_path()).collect();
+    assert_eq!(vec![vec!["protocols"], vec!["/books", "get", "responses", "ok"]], paths);
+}
 
 #[test]
 fn error_for_unknown_field() {
@@ -230,5 +902,339 @@ title: Some API
 unknown: field";
     let result = parse(s);
     assert_error_result(result,
-                        "Unexpected field found at the document root: unknown at line 3 column 1");
-}
\ No newline at end of file
+                        "Unexpected field found at the document root: unknown at line 3 column 1 \
+                         (in unknown)");
+}
+
+#[test]
+fn unknown_field_error_has_kind_and_field() {
+    let s = "#%RAML 1.0
+title: Some API
+owner: Books team";
+    let err = assert_error_kind(parse(s), ErrorKind::UnexpectedKeyRoot);
+    assert_eq!(Some("owner"), err.field());
+}
+#[test]
+fn clones_a_document() {
+    let s = "#%RAML 1.0
+title: Some API
+version: v1
+description: Sample description
+baseUri: https://{environment}.some.api.com/{version}
+baseUriParameters:
+  environment:
+    type: string
+protocols: [http, https]
+mediaType: application/json
+documentation:
+  - title: Doc Title
+    content: Doc Content
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    settings:
+      accessTokenUri: https://some.api.com/token
+      authorizationGrants: [ client_credentials ]
+securedBy: [oauth_2_0]
+annotationTypes:
+  deprecated: nil
+(deprecated):
+types:
+  Person:
+    properties:
+      name: string
+traits:
+  paged:
+    queryParameters:
+      page: integer
+resourceTypes:
+  collection:
+    get:
+/people:
+  type: collection
+  is: [paged]
+  get:
+    responses:
+      200:
+        body:
+          type: Person[]
+          example: [{ name: Alice }]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let copy = raml.clone();
+    assert_eq!(raml, copy);
+    let protocols: HashSet<&Protocol> = raml.protocols().unwrap().iter().collect();
+    assert!(protocols.contains(&Protocol::Https));
+}
+
+#[test]
+fn validate_reports_every_error_in_document_order() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols: [ HTTP, FTP ]
+owner: Books team
+documentation:
+ - content: Doc Content
+/books:
+  get:
+    description: List the books";
+    let errors = RamlParser::validate(s);
+    let errors: Vec<&str> = errors.iter().map(|e| e.error()).collect();
+    assert_eq!(vec!["Error parsing document root. Unexpected protocol at line 3 column 20 (in \
+                     protocols)",
+                    "Unexpected field found at the document root: owner at line 4 column 1 (in \
+                     owner)",
+                    "Error parsing documentation. Missing field: title at line 6 column 4 (in \
+                     documentation)"],
+               errors);
+}
+
+#[test]
+fn validate_returns_no_errors_for_valid_document() {
+    let s = "#%RAML 1.0
+title: Some API
+/books:
+  get:";
+    assert!(RamlParser::validate(s).is_empty());
+}
+
+fn parse_with_unknown_keys(s: &str, unknown_keys: UnknownKeyBehaviour) -> RamlResult {
+    let options = ParseOptions {
+        unknown_keys,
+        ..ParseOptions::default()
+    };
+    RamlParser::load_from_str_with_options(s, &options)
+}
+
+#[test]
+fn error_for_unknown_key_by_default() {
+    let s = "#%RAML 1.0
+title: Some API
+/books:
+  x-owner: team";
+    let result = parse_with_unknown_keys(s, UnknownKeyBehaviour::default());
+    assert_error_result(result,
+                        "Unexpected field found at the resource: x-owner at line 4 column 3 (in \
+                         /books > x-owner)");
+}
+
+#[test]
+fn ignores_unknown_keys_with_any_value() {
+    let s = "#%RAML 1.0
+title: Some API
+x-owner: team
+x-tags: [a, { b: c }]
+/books:
+  x-limits:
+    rate:
+      perMinute: 10
+    burst: 5
+  get:
+    x-internal: true
+    description: List the books
+version: v1";
+    let raml = assert_ok_and_unwrap(parse_with_unknown_keys(s, UnknownKeyBehaviour::Ignore));
+    assert_eq!(Some("v1"), raml.version());
+    let books = &raml.resources()[0];
+    assert_eq!(Some("List the books"), books.methods()["get"].description());
+}
+
+#[test]
+fn keeps_the_values_of_unknown_keys() {
+    let s = "#%RAML 1.0
+title: Some API
+x-owner: team
+/books:
+  x-limits:
+    rate:
+      perMinute: 10
+    burst: 5
+  get:
+    x-tags: [a, b]";
+    let raml = assert_ok_and_unwrap(parse_with_unknown_keys(s, UnknownKeyBehaviour::Ignore));
+    match raml.extra().unwrap()["x-owner"] {
+        YamlNode::Scalar(ref value, _) => assert_eq!("team", value),
+        ref node => panic!("Expected a scalar, found {:?}", node),
+    }
+    let books = &raml.resources()[0];
+    let limits = &books.extra().unwrap()["x-limits"];
+    assert_eq!(6, limits.location().line);
+    let entries = match *limits {
+        YamlNode::Mapping(ref entries, _) => entries,
+        ref node => panic!("Expected a mapping, found {:?}", node),
+    };
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.0.as_str()).collect();
+    assert_eq!(vec!["rate", "burst"], keys);
+    match books.methods()["get"].extra().unwrap()["x-tags"] {
+        YamlNode::Sequence(ref values, _) => assert_eq!(2, values.len()),
+        ref node => panic!("Expected a sequence, found {:?}", node),
+    }
+}
+
+#[test]
+fn error_for_tab_indentation() {
+    let s = "#%RAML 1.0
+title: Some API
+description: |
+\tThe API";
+    let err = assert_error_kind(parse(s), ErrorKind::InvalidYaml);
+    assert_eq!("Invalid YAML: while scanning a block scalar, found a tab character where an \
+                intendation space is expected at line 4 column 1 (in description)",
+               err.error());
+}
+
+#[test]
+fn error_for_unterminated_quoted_string() {
+    let s = "#%RAML 1.0
+title: \"unterminated";
+    let err = assert_error_kind(parse(s), ErrorKind::InvalidYaml);
+    assert!(err.line().is_some());
+    assert!(err.error().contains(" at line "));
+}
+
+#[test]
+fn loads_single_quoted_description() {
+    let s = "#%RAML 1.0
+title: Some API
+description: 'It''s an API: \\n is not an escape'";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("It's an API: \\n is not an escape"), raml.description());
+}
+
+#[test]
+fn loads_double_quoted_description_with_escapes() {
+    let s = "#%RAML 1.0
+title: Some API
+description: \"Line1\\nLine2\\t\\\"quoted\\\"\"";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("Line1\nLine2\t\"quoted\""), raml.description());
+}
+
+#[test]
+fn null_description_is_none() {
+    for value in &["~", "null", ""] {
+        let s = format!("#%RAML 1.0
+title: Some API
+description: {}
+/users:
+  description: {}
+  get:
+    description: {}",
+                        value,
+                        value,
+                        value);
+        let raml = assert_ok_and_unwrap(parse(&s));
+        assert_eq!(None, raml.description());
+        assert_eq!(None, raml.resources()[0].description());
+        assert_eq!(None, raml.resources()[0].methods()["get"].description());
+    }
+}
+
+#[test]
+fn quoted_null_description_is_a_string() {
+    let s = "#%RAML 1.0
+title: Some API
+description: \"~\"";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("~"), raml.description());
+}
+
+#[test]
+fn loads_quoted_resource_key() {
+    let s = "#%RAML 1.0
+title: Some API
+\"/users/{id}\":
+  description: A user";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!("/users/{id}", raml.resources()[0].relative_uri());
+}
+
+#[test]
+fn debug_returns_the_tokens_of_a_document() {
+    let s = "#%RAML 1.0
+title: Some API";
+    let tokens = RamlParser::debug(s).unwrap();
+    let types: Vec<&TokenTypeDef> = tokens.iter().map(|(_, token_type)| token_type).collect();
+    assert_eq!(vec![&TokenTypeDef::StreamStart,
+                    &TokenTypeDef::BlockMappingStart,
+                    &TokenTypeDef::Key,
+                    &TokenTypeDef::Scalar,
+                    &TokenTypeDef::Value,
+                    &TokenTypeDef::Scalar,
+                    &TokenTypeDef::BlockEnd,
+                    &TokenTypeDef::StreamEnd],
+               types);
+    let title = tokens[3].0;
+    assert_eq!((2, 1), (title.line, title.column));
+}
+
+#[test]
+fn debug_fails_for_invalid_yaml() {
+    let s = "#%RAML 1.0
+title: \"Some API";
+    assert_eq!(ErrorKind::InvalidYaml, RamlParser::debug(s).err().unwrap().kind());
+}
+
+
+#[test]
+fn error_for_deeply_nested_document() {
+    let depth = 100_000;
+    let s = format!("#%RAML 1.0
+title: Some API
+(nested): {}{}",
+                    "{a: ".repeat(depth),
+                    "}".repeat(depth));
+    let error = assert_error_kind(parse(&s), ErrorKind::LimitExceeded);
+    assert_eq!("Error parsing document. Exceeded the limit on max_depth of 200 at line 3 column \
+                807 (in (nested))",
+               error.error());
+}
+
+#[test]
+fn error_for_deeply_nested_resources() {
+    let mut s = "#%RAML 1.0
+title: Some API
+"
+        .to_string();
+    for level in 0..20 {
+        s.push_str(&format!("{}/level{}:\n", "  ".repeat(level), level));
+    }
+    s.push_str(&format!("{}get:", "  ".repeat(20)));
+    let options = ParseOptions {
+        max_depth: 10,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_str_with_options(&s, &options);
+    assert_error_kind(result, ErrorKind::LimitExceeded);
+    assert!(RamlParser::load_from_str(&s).is_ok());
+}
+
+#[test]
+fn error_for_document_over_the_size_limit() {
+    let options = ParseOptions {
+        max_source_bytes: 16,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_str_with_options("#%RAML 1.0
+title: Some API",
+                                                        &options);
+    assert_error_result(result,
+                        "Error parsing document. Exceeded the limit on max_source_bytes of 16");
+}
+
+#[test]
+fn parses_protocols_ignoring_casing() {
+    assert_eq!(Protocol::Https, "hTTpS".parse::<Protocol>().unwrap());
+    assert_eq!(Protocol::Http, "http".parse::<Protocol>().unwrap());
+    let error = "ftp".parse::<Protocol>().err().unwrap();
+    assert_eq!(ErrorKind::InvalidProtocol, error.kind());
+    assert_eq!("Invalid protocol: ftp. Expected HTTP or HTTPS", error.error());
+}
+
+#[test]
+fn displays_protocols() {
+    assert_eq!("HTTP", Protocol::Http.to_string());
+    assert_eq!("HTTPS", Protocol::Https.to_string());
+    assert_eq!(80, Protocol::Http.default_port());
+    assert_eq!(443, Protocol::Https.default_port());
+}
@@ -0,0 +1,260 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+const API: &str = "#%RAML 1.0
+title: 'Books API: the catalogue'
+description: |
+  Lists the books of the library.
+
+  Books may be borrowed # by members only.
+version: v1
+baseUri: https://{region}.example.com/{version}
+baseUriParameters:
+  region:
+    enum: [eu, us]
+protocols: [HTTP, HTTPS]
+mediaType: [application/json, application/xml]
+documentation:
+  - title: Home
+    content: |
+      Welcome to the *Books API*.
+      Read on.
+  - title: Legal
+    content: '- all rights reserved -'
+securitySchemes:
+  oauth_1_0:
+    type: OAuth 1.0
+    settings:
+      requestTokenUri: https://example.com/request
+      authorizationUri: https://example.com/authorize
+      tokenCredentialsUri: https://example.com/credentials
+      signatures: [HMAC-SHA1, PLAINTEXT]
+  oauth_2_0:
+    type: OAuth 2.0
+    displayName: OAuth
+    description: Access tokens from the authorization server.
+    describedBy:
+      headers:
+        Authorization:
+          description: The access token.
+      responses:
+        401:
+          description: The token has expired.
+    settings:
+      authorizationUri: https://example.com/authorize
+      accessTokenUri: https://example.com/token
+      authorizationGrants: [authorization_code, 'urn:ietf:params:oauth:grant-type:saml2-bearer']
+      scopes: [read, write]
+  api_key:
+    type: Pass Through
+    settings:
+      headerName: X-Api-Key
+  custom:
+    type: x-custom
+    settings:
+      realm: books
+      retries: 3
+securedBy:
+  - null
+  - oauth_2_0: { scopes: [read] }
+types:
+  Isbn:
+    type: string
+    pattern: ^[0-9-]{10,17}$
+    minLength: 10
+    maxLength: 17
+  Price:
+    type: number
+    minimum: 0
+    maximum: 999.99
+    multipleOf: 0.01
+  Genre:
+    type: string
+    enum: [fiction, 'yes', '42']
+    default: fiction
+  Book:
+    type: object
+    description: A book in the catalogue.
+    (deprecated): false
+    properties:
+      isbn: Isbn
+      title:
+        type: string
+        example: 'true'
+      authors: string[]
+      price?: Price
+      genre?: Genre | nil
+      tags:
+        type: array
+        items: string
+        minItems: 1
+        maxItems: 5
+        uniqueItems: true
+      fits?:
+        type: boolean
+        required: true
+    example:
+      displayName: Dune
+      strict: false
+      value:
+        isbn: 978-0441013593
+        title: Dune
+        authors: [Frank Herbert]
+        price: 9.99
+        tags: [sf, ~]
+  Ebook:
+    type: [Book, Download]
+    examples:
+      first:
+        value: { value: 1 }
+      second: 'null'
+  Download: object
+annotationTypes:
+  deprecated:
+    type: boolean
+    allowedTargets: [Resource, TypeDeclaration]
+  owner: string
+traits:
+  paged:
+    usage: Applied to collections.
+    queryParameters:
+      page:
+        type: integer
+        minimum: 1
+        default: 1
+      size:
+        type: integer
+        default: <<defaultSize>>
+resourceTypes:
+  collection:
+    usage: A list of <<resourcePathName>>.
+    description: All <<resourcePathName>>.
+    get:
+      description: Lists the <<resourcePathName>>.
+    post?:
+      body:
+        type: <<item>>
+(owner): Catalogue team
+/books:
+  type: { collection: { item: Book } }
+  is: [{ paged: { defaultSize: 10 } }]
+  (deprecated): true
+  get:
+    displayName: List books
+    securedBy: [api_key]
+    headers:
+      X-Request-Id:
+        type: string
+        example: '123'
+    responses:
+      200:
+        description: The books.
+        headers:
+          X-Total:
+            type: integer
+        body:
+          application/json:
+            type: Book[]
+            example:
+              - isbn: 978-0441013593
+                title: Dune
+                authors: [Frank Herbert]
+          application/xml:
+      404:
+  post:
+    body:
+      application/json: Book
+    responses:
+      201:
+  /{isbn}:
+    uriParameters:
+      isbn:
+        type: string
+        description: |-
+          The ISBN,
+          with or without dashes.
+    get:
+      queryParameters:
+        format:
+          enum: [short, long]
+          required: false
+    delete:
+      securedBy: [oauth_1_0, custom]
+      description: \"Removes the book.\\n\\n\\n\"";
+
+#[test]
+fn written_document_parses_to_the_same_api() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let written = raml.to_raml_string();
+    let reparsed = assert_ok_and_unwrap(parse(&written));
+    assert_eq!(raml, reparsed);
+    assert_eq!(written, reparsed.to_raml_string());
+}
+
+#[test]
+fn writes_the_header_and_root_nodes() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let written = raml.to_raml_string();
+    assert!(written.starts_with("#%RAML 1.0\ntitle: 'Books API: the catalogue'\n"));
+    assert!(written.contains("\nprotocols: [HTTP, HTTPS]\n"));
+    assert!(written.contains("\nmediaType: [application/json, application/xml]\n"));
+    assert!(written.contains("\ndocumentation:\n  - title: Home\n    content: |\n      Welcome"));
+}
+
+#[test]
+fn writes_multi_line_descriptions_as_literal_blocks() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let written = raml.to_raml_string();
+    assert!(written.contains("\ndescription: |\n  Lists the books of the library.\n\n  Books"));
+}
+
+#[test]
+fn quotes_strings_that_would_be_read_as_other_values() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: '#1 API'
+description: 'yes'
+annotationTypes:
+  flag: string
+(flag): 'yes'"));
+    let written = raml.to_raml_string();
+    assert!(written.contains("\ntitle: '#1 API'\n"));
+    // Only values that keep their type need quoting.
+    assert!(written.contains("\ndescription: yes\n"));
+    assert!(written.contains("\n(flag): 'yes'\n"));
+    assert_eq!(raml, assert_ok_and_unwrap(parse(&written)));
+}
+
+#[test]
+fn does_not_write_inferred_protocols() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+baseUri: https://api.example.com"));
+    assert!(raml.protocols_inferred());
+    let written = raml.to_raml_string();
+    assert!(!written.contains("protocols"));
+    assert_eq!(raml, assert_ok_and_unwrap(parse(&written)));
+}
+
+#[test]
+fn writes_back_unknown_keys_that_were_kept() {
+    let options = ParseOptions {
+        unknown_keys: UnknownKeyBehaviour::Ignore,
+        ..ParseOptions::default()
+    };
+    let s = "#%RAML 1.0
+title: Books API
+x-owner: team
+/books:
+  get:
+    x-limits: { burst: 5 }";
+    let raml = assert_ok_and_unwrap(RamlParser::load_from_str_with_options(s, &options));
+    let written = raml.to_raml_string();
+    assert!(written.contains("\nx-owner: team\n"));
+    assert!(written.contains("\n  get:\n    x-limits:\n      burst: 5\n"));
+}
@@ -0,0 +1,83 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+#[test]
+fn loads_data_type_fragments() {
+    let s = "#%RAML 1.0 DataType
+type: object
+properties:
+  name: string";
+    let fragment = RamlParser::load_fragment_from_str(s).unwrap();
+    let raml_type = match fragment {
+        RamlFragment::DataType(raml_type) => raml_type,
+        _ => panic!("Expected a DataType fragment"),
+    };
+    assert_eq!(Some(&named_type("object")), raml_type.type_());
+    assert!(raml_type.properties().unwrap().contains_key("name"));
+}
+
+#[test]
+fn loads_trait_fragments() {
+    let s = "#%RAML 1.0 Trait
+usage: Apply to paged collections
+queryParameters:
+  page: integer";
+    let fragment = RamlParser::load_fragment_from_str(s).unwrap();
+    let raml_trait = match fragment {
+        RamlFragment::Trait(raml_trait) => raml_trait,
+        _ => panic!("Expected a Trait fragment"),
+    };
+    assert_eq!(Some("Apply to paged collections"), raml_trait.usage());
+    assert!(raml_trait.query_parameters().unwrap().contains_key("page"));
+}
+
+#[test]
+fn loads_security_scheme_fragments() {
+    let s = "#%RAML 1.0 SecurityScheme
+type: Basic Authentication
+description: Username and password";
+    let fragment = RamlParser::load_fragment_from_str(s).unwrap();
+    let security_scheme = match fragment {
+        RamlFragment::SecurityScheme(security_scheme) => security_scheme,
+        _ => panic!("Expected a SecurityScheme fragment"),
+    };
+    assert_eq!(&SecuritySchemeType::BasicAuthentication,
+               security_scheme.security_type());
+    assert_eq!(Some("Username and password"), security_scheme.description());
+}
+
+#[test]
+fn loads_documentation_item_fragments() {
+    let s = "#%RAML 1.0 DocumentationItem
+title: Getting started
+content: Sign up for an API key";
+    let fragment = RamlParser::load_fragment_from_str(s).unwrap();
+    let documentation = match fragment {
+        RamlFragment::DocumentationItem(documentation) => documentation,
+        _ => panic!("Expected a DocumentationItem fragment"),
+    };
+    assert_eq!("Getting started", documentation.title());
+    assert_eq!("Sign up for an API key", documentation.content());
+}
+
+#[test]
+fn error_for_unknown_fragment() {
+    let s = "#%RAML 1.0 Widget
+title: Some API";
+    let result = RamlParser::load_fragment_from_str(s);
+    assert_eq!("Unknown fragment type: Widget", result.err().unwrap().error());
+}
+
+#[test]
+fn error_for_fragment_loaded_as_api_document() {
+    let s = "#%RAML 1.0 DataType
+type: string";
+    let result = parse(s);
+    assert_error_result(result, "Document is a DataType fragment, not an API document");
+}
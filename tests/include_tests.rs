@@ -0,0 +1,315 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+fn parse_with_files(s: &str, files: &[(&str, &str)]) -> RamlResult {
+    let resolver: HashMap<String, String> =
+        files.iter().map(|&(path, source)| (path.to_string(), source.to_string())).collect();
+    RamlParser::load_from_str_with_resolver(s, &resolver)
+}
+
+#[test]
+fn includes_text_files_as_strings() {
+    let s = "#%RAML 1.0
+title: Some API
+description: !include docs/description.md";
+    let result = parse_with_files(s, &[("docs/description.md", "# Some API\nDetails")]);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(Some("# Some API\nDetails"), raml.description());
+}
+
+#[test]
+fn includes_raml_fragments() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: !include types/person.raml";
+    let person = "#%RAML 1.0 DataType
+type: object
+properties:
+  name: string";
+    let result = parse_with_files(s, &[("types/person.raml", person)]);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let person = types.get("Person").unwrap();
+    assert_eq!(Some(&named_type("object")), person.type_());
+    assert!(person.properties().unwrap().contains_key("name"));
+}
+
+#[test]
+fn includes_files_included_by_fragments() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: !include types/person.raml";
+    let person = "#%RAML 1.0 DataType
+type: object
+description: !include ../docs/person.md";
+    let result = parse_with_files(s,
+                                  &[("types/person.raml", person),
+                                    ("docs/person.md", "A person")]);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    assert_eq!(Some("A person"), types.get("Person").unwrap().description());
+}
+
+#[test]
+fn error_for_circular_includes() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: !include a.raml";
+    let a = "#%RAML 1.0 DataType
+type: object
+properties:
+  friend: !include b.raml";
+    let b = "#%RAML 1.0 DataType
+type: object
+properties:
+  friend: !include a.raml";
+    let result = parse_with_files(s, &[("a.raml", a), ("b.raml", b)]);
+    assert_error_result(result,
+                        "Circular include: a.raml -> b.raml -> a.raml at line 4 column 11");
+}
+
+#[test]
+fn error_for_unresolved_include() {
+    let s = "#%RAML 1.0
+title: Some API
+description: !include docs/description.md";
+    let result = parse_with_files(s, &[]);
+    assert_error_result(result, "Unable to resolve include: docs/description.md");
+}
+
+#[test]
+fn unresolved_include_error_has_kind_and_path() {
+    let s = "#%RAML 1.0
+title: Some API
+description: !include docs/description.md";
+    let err = assert_error_kind(parse_with_files(s, &[]), ErrorKind::UnresolvedInclude);
+    assert_eq!(Some("docs/description.md"), err.path());
+}
+
+#[test]
+fn loads_files_including_files_relative_to_themselves() {
+    let result = RamlParser::load_from_file("tests/fixtures/api.raml");
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    assert_eq!(Some("A person\n"), types.get("Person").unwrap().description());
+}
+
+#[test]
+fn loads_files_from_absolute_paths() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/api.raml");
+    let result = RamlParser::load_from_file(path);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(Some("An API loaded from files\n"), raml.description());
+}
+
+#[test]
+fn error_for_unreadable_file() {
+    let result = RamlParser::load_from_file("tests/fixtures/missing.raml");
+    let error = result.err().unwrap();
+    assert!(error.error().starts_with("Unable to read file tests/fixtures/missing.raml:"));
+}
+
+#[test]
+fn loads_from_a_reader() {
+    let file = File::open("tests/fixtures/openapi/api.raml").unwrap();
+    let raml = assert_ok_and_unwrap(RamlParser::load_from_reader(file));
+    assert_eq!("Books API", raml.title());
+}
+
+#[test]
+fn error_for_invalid_utf8_from_a_reader() {
+    let source: &[u8] = b"#%RAML 1.0\ntitle: Books \xff API";
+    let result = RamlParser::load_from_reader(source);
+    let error = assert_error_kind(result, ErrorKind::InvalidUtf8);
+    assert_eq!("Invalid UTF-8 in source at byte offset 24", error.error());
+}
+
+#[test]
+fn stops_reading_a_reader_over_the_size_limit() {
+    let options = ParseOptions {
+        max_source_bytes: 64,
+        ..ParseOptions::default()
+    };
+    let endless = io::repeat(b'#');
+    let result = RamlParser::load_from_reader_with_options(endless, &options);
+    assert_error_result(result,
+                        "Error parsing document. Exceeded the limit on max_source_bytes of 64");
+}
+
+#[test]
+fn includes_documentation_content() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+  - title: Introduction
+    content: !include docs/intro.md";
+    let result = parse_with_files(s, &[("docs/intro.md", "# Introduction\n\nStart *here*.\n")]);
+    let raml = assert_ok_and_unwrap(result);
+    let documentation = raml.documentation().unwrap();
+    assert_eq!("# Introduction\n\nStart *here*.\n", documentation[0].content());
+}
+
+#[test]
+fn error_for_include_without_a_resolver() {
+    let s = "#%RAML 1.0
+title: Some API
+documentation:
+  - title: Introduction
+    content: !include docs/intro.md";
+    let err = assert_error_kind(parse(s), ErrorKind::MissingIncludeResolver);
+    assert_eq!("Unable to resolve include: docs/intro.md. No include resolver was given at line \
+                5 column 14 (in documentation > content)",
+               err.error());
+    assert_eq!(Some("docs/intro.md"), err.path());
+}
+
+#[test]
+fn records_the_files_a_document_was_loaded_from() {
+    let result = RamlParser::load_from_file("tests/fixtures/api.raml");
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(Some("tests/fixtures/api.raml"), raml.source_path());
+    let files: Vec<(&str, usize)> = raml.source_files()
+        .iter()
+        .map(|file| (file.path(), file.size()))
+        .collect();
+    let size = |path: &str| fs::metadata(path).unwrap().len() as usize;
+    assert_eq!(vec![("tests/fixtures/docs/api.md", size("tests/fixtures/docs/api.md")),
+                    ("tests/fixtures/types/person.raml",
+                     size("tests/fixtures/types/person.raml")),
+                    ("tests/fixtures/docs/person.md", size("tests/fixtures/docs/person.md"))],
+               files);
+}
+
+#[test]
+fn error_for_files_over_the_size_limit() {
+    let options = ParseOptions {
+        max_source_bytes: 64,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_file_with_options("tests/fixtures/api.raml", &options);
+    assert_error_result(result,
+                        "Error parsing document. Exceeded the limit on max_source_bytes of 64");
+    let options = ParseOptions {
+        max_source_bytes: 120,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_file_with_options("tests/fixtures/api.raml", &options);
+    let error = assert_error_kind(result, ErrorKind::LimitExceeded);
+    assert_eq!((Some(3), Some(14)), (error.line(), error.column()));
+}
+
+#[test]
+fn locates_nodes_in_the_files_they_were_included_from() {
+    let result = RamlParser::load_from_file("tests/fixtures/api.raml");
+    let raml = assert_ok_and_unwrap(result);
+    let person = &raml.types().unwrap()["Person"];
+    let location = person.location().unwrap();
+    assert_eq!((None, 5), (location.file, location.line));
+    let name = person.properties().unwrap()["name"].property_type().location().unwrap();
+    assert_eq!((Some(1), 5, 3), (name.file, name.line, name.column));
+    assert_eq!("tests/fixtures/types/person.raml", raml.source_files()[1].path());
+}
+
+#[test]
+fn locates_nodes_in_files_included_by_included_files() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: !include types/person.raml";
+    let person = "#%RAML 1.0 DataType
+properties:
+  address: !include address.raml
+  name: string";
+    let address = "#%RAML 1.0 DataType
+properties:
+  street: string";
+    let result = parse_with_files(s,
+                                  &[("types/person.raml", person),
+                                    ("types/address.raml", address)]);
+    let raml = assert_ok_and_unwrap(result);
+    let paths: Vec<&str> = raml.source_files().iter().map(SourceFile::path).collect();
+    assert_eq!(vec!["types/person.raml", "types/address.raml"], paths);
+    let properties = raml.types().unwrap()["Person"].properties().unwrap();
+    let address = properties["address"].property_type();
+    let street = address.properties().unwrap()["street"].property_type().location().unwrap();
+    assert_eq!((Some(1), 3), (street.file, street.line));
+    let name = properties["name"].property_type().location().unwrap();
+    assert_eq!((Some(0), 4), (name.file, name.line));
+}
+
+#[test]
+fn includes_xml_schemas_as_types() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: !include schemas/person.xsd";
+    let xsd = "<?xml version=\"1.0\"?>
+<xs:schema xmlns:xs=\"http://www.w3.org/2001/XMLSchema\">
+  <xs:element name=\"person\" type=\"xs:string\"/>
+</xs:schema>";
+    let result = parse_with_files(s, &[("schemas/person.xsd", xsd)]);
+    let raml = assert_ok_and_unwrap(result);
+    let schema = raml.types().unwrap()["Person"].schema().unwrap();
+    assert_eq!(SchemaKind::Xsd, schema.kind());
+    assert_eq!(xsd, schema.raw());
+}
+
+#[test]
+fn error_for_too_many_includes() {
+    let s = "#%RAML 1.0
+title: Some API
+description: !include docs/description.md
+documentation:
+  - title: Home
+    content: !include docs/description.md";
+    let resolver: HashMap<String, String> =
+        vec![("docs/description.md".to_string(), "Details".to_string())].into_iter().collect();
+    let options = ParseOptions {
+        max_includes: 1,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_str_with_resolver_and_options(s, &resolver, &options);
+    assert_error_result(result,
+                        "Error parsing document. Exceeded the limit on max_includes of 1 at line \
+                         6 column 14");
+}
+
+#[test]
+fn error_for_includes_nested_too_deeply() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: !include types/person.raml";
+    let person = "#%RAML 1.0 DataType
+properties:
+  address: !include address.raml";
+    let address = "#%RAML 1.0 DataType
+properties:
+  street: string";
+    let resolver: HashMap<String, String> =
+        vec![("types/person.raml".to_string(), person.to_string()),
+             ("types/address.raml".to_string(), address.to_string())]
+            .into_iter()
+            .collect();
+    let options = ParseOptions {
+        max_include_depth: 1,
+        ..ParseOptions::default()
+    };
+    let result = RamlParser::load_from_str_with_resolver_and_options(s, &resolver, &options);
+    assert_error_kind(result, ErrorKind::LimitExceeded);
+    assert!(RamlParser::load_from_str_with_resolver(s, &resolver).is_ok());
+}
@@ -0,0 +1,190 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+use std::collections::HashMap;
+
+const LIBRARY: &str = "#%RAML 1.0 Library
+usage: Shared declarations
+types:
+  Person:
+    properties:
+      name: string
+  Employee:
+    type: Person
+    properties:
+      salary: number
+traits:
+  paged:
+    queryParameters:
+      page: integer
+securitySchemes:
+  basic:
+    type: Basic Authentication";
+
+fn resolver() -> HashMap<String, String> {
+    let mut resolver = HashMap::new();
+    resolver.insert("libraries/common.raml".to_string(), LIBRARY.to_string());
+    resolver
+}
+
+fn parse_with_library(s: &str) -> RamlResult {
+    RamlParser::load_from_str_with_resolver(s, &resolver())
+}
+
+#[test]
+fn loads_libraries() {
+    let result = RamlParser::load_library_from_str(LIBRARY);
+    assert!(result.is_ok());
+    let library = result.unwrap();
+    assert_eq!(Some("Shared declarations"), library.usage());
+    assert!(library.types().unwrap().contains_key("Person"));
+    assert!(library.traits().unwrap().contains_key("paged"));
+    assert!(library.security_schemes().unwrap().contains_key("basic"));
+}
+
+#[test]
+fn error_for_title_in_library() {
+    let s = "#%RAML 1.0 Library
+title: Some API";
+    let result = RamlParser::load_library_from_str(s);
+    assert_eq!("Unexpected field found at the library: title at line 2 column 1 (in title)",
+               result.err().unwrap().error());
+}
+
+#[test]
+fn loads_used_libraries() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml";
+    let result = parse_with_library(s);
+    let raml = assert_ok_and_unwrap(result);
+    let uses = raml.uses().unwrap();
+    assert_eq!(Some("Shared declarations"), uses.get("common").unwrap().usage());
+}
+
+#[test]
+fn resolves_library_types() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+types:
+  Manager:
+    type: common.Employee";
+    let result = parse_with_library(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resolved = raml.resolve_type("Manager").unwrap();
+    let properties = resolved.raml_type().properties().unwrap();
+    assert!(properties.contains_key("name"));
+    assert!(properties.contains_key("salary"));
+}
+
+#[test]
+fn resolves_library_traits_and_security_schemes() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+securedBy: [common.basic]
+/users:
+  get:
+    is: [common.paged]";
+    let result = parse_with_library(s);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(Some(&vec![SecuredBy::Scheme("common.basic".to_string())]),
+               raml.secured_by());
+}
+
+#[test]
+fn error_for_unknown_library_namespace() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+/users:
+  get:
+    is: [shared.paged]";
+    let result = parse_with_library(s);
+    assert_error_result(result,
+                        "Error parsing reference. Library not declared: shared at line 7 column \
+                         10");
+}
+
+#[test]
+fn error_for_uses_without_resolver() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unable to resolve include: libraries/common.raml. No include resolver was \
+                         given at line 4 column 11 (in uses > common)");
+}
+
+#[test]
+fn resolves_qualified_names_in_type_expressions() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+types:
+  Staff: common.Person |common.Employee
+  Team:
+    properties:
+      members: common.Person[]  []
+/people:
+  post:
+    body:
+      application/json:
+        type: common.Employee[ ]";
+    let raml = assert_ok_and_unwrap(parse_with_library(s));
+    let types = raml.types().unwrap();
+    let expected = TypeExpression::Union(vec![named_type("common.Person"),
+                                              named_type("common.Employee")]);
+    assert_eq!(Some(&expected), types["Staff"].type_());
+    let members = types["Team"].properties().unwrap()["members"].property_type();
+    let items = members.items().unwrap().items().unwrap();
+    assert_eq!(Some(&named_type("common.Person")), items.type_());
+    let person = named_type("common.Person");
+    assert_eq!((Some("common"), Some("Person")), (person.namespace(), person.local_name()));
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn violation_for_a_type_expression_with_an_unknown_namespace() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+/people:
+  post:
+    body:
+      application/json:
+        type: common.Person | shapes.Circle";
+    let raml = assert_ok_and_unwrap(parse_with_library(s));
+    let violations = raml.validate();
+    let messages: Vec<&str> = violations.iter().map(|v| v.message()).collect();
+    assert_eq!(vec!["Error parsing reference. Library not declared: shapes"], messages);
+}
+
+#[test]
+fn error_for_a_qualified_name_without_a_type() {
+    let s = "#%RAML 1.0
+title: Some API
+uses:
+  common: libraries/common.raml
+types:
+  Staff:
+    properties:
+      lead: common.";
+    assert_error_result(parse_with_library(s),
+                        "Invalid type expression: common. at line 8 column 13 (in types > Staff \
+                         > properties > lead)");
+}
@@ -0,0 +1,89 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+const API: &str = "#%RAML 1.0
+title: Books API
+documentation:
+  - title: Home
+    content: Welcome.
+securitySchemes:
+  basic:
+    type: Basic Authentication
+  api_key:
+    type: Pass Through
+    settings:
+      headerName: X-Api-Key
+types:
+  Book:
+    properties:
+      title: string
+/books:
+  get:
+  /{isbn}:
+    delete:";
+
+#[test]
+fn locates_the_security_schemes() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let schemes = raml.security_schemes().unwrap();
+    let basic = schemes["basic"].location().unwrap();
+    assert_eq!((7, 3), (basic.line, basic.column));
+    let api_key = schemes["api_key"].location().unwrap();
+    assert_eq!((9, 3), (api_key.line, api_key.column));
+    assert_eq!(&API[api_key.index..api_key.index + 7], "api_key");
+}
+
+#[test]
+fn locates_the_title_and_documentation() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    assert_eq!(2, raml.title_location().unwrap().line);
+    let home = raml.documentation().unwrap()[0].location().unwrap();
+    assert_eq!((4, 5), (home.line, home.column));
+}
+
+#[test]
+fn locates_the_types_and_properties() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let book = &raml.types().unwrap()["Book"];
+    assert_eq!(14, book.location().unwrap().line);
+    let title = book.properties().unwrap()["title"].property_type();
+    assert_eq!(16, title.location().unwrap().line);
+}
+
+#[test]
+fn locates_the_resources_and_methods() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let books = &raml.resources()[0];
+    assert_eq!(17, books.location().unwrap().line);
+    assert_eq!(18, books.methods()["get"].location().unwrap().line);
+    let book = &books.resources()[0];
+    assert_eq!((19, 3), (book.location().unwrap().line, book.location().unwrap().column));
+    assert_eq!(20, book.methods()["delete"].location().unwrap().line);
+}
+
+#[test]
+fn locates_the_undeclared_parent_type_error() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Book: object
+  Novel:
+    properties:
+      author: string
+    type: Missing";
+    let error = assert_error_kind(parse(s), ErrorKind::UndeclaredType);
+    assert_eq!((Some(8), Some(5)), (error.line(), error.column()));
+    assert_eq!(Some("Missing"), error.name());
+}
+
+#[test]
+fn built_documents_have_no_locations() {
+    let raml = RamlBuilder::new().title("Books API").build().unwrap();
+    assert_eq!(None, raml.title_location());
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "markdown")]
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn renders_descriptions_as_html() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+description: |
+  Lists the books of a library.
+
+  - Search with `q`
+  - Page with `offset`
+securitySchemes:
+  basic:
+    type: Basic Authentication
+    description: Send the `Authorization` header
+documentation:
+  - title: Paging
+    content: Pages hold **20** books"));
+    assert_eq!(Some("<p>Lists the books of a library.</p>\n<ul>\n<li>Search with \
+                     <code>q</code></li>\n<li>Page with <code>offset</code></li>\n</ul>\n"
+                        .to_string()),
+               raml.description_html());
+    assert_eq!(Some("<p>Send the <code>Authorization</code> header</p>\n".to_string()),
+               raml.security_schemes().unwrap()["basic"].description_html());
+    assert_eq!("<p>Pages hold <strong>20</strong> books</p>\n",
+               raml.documentation().unwrap()[0].content_html());
+}
+
+#[test]
+fn no_html_without_a_description() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API"));
+    assert_eq!(None, raml.description_html());
+}
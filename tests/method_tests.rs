@@ -0,0 +1,607 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+#[test]
+fn loads_a_method() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    displayName: List users
+    description: Lists all the users";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let get = resources[0].methods().get("get").unwrap();
+    assert_eq!(Some("List users"), get.display_name());
+    assert_eq!(Some("Lists all the users"), get.description());
+}
+
+#[test]
+fn loads_all_http_methods() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+  post:
+  put:
+  delete:
+  patch:
+  head:
+  options:
+  trace:";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let methods = resources[0].methods();
+    assert_eq!(8, methods.len());
+    for verb in &["get", "post", "put", "delete", "patch", "head", "options", "trace"] {
+        assert!(methods.contains_key(*verb), "missing method {}", verb);
+    }
+}
+
+#[test]
+fn method_with_empty_body_has_no_fields() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+  post:
+    description: Creates a user
+  description: All the users";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let get = resources[0].methods().get("get").unwrap();
+    assert_eq!(None, get.display_name());
+    assert_eq!(None, get.description());
+    assert_eq!(Some("Creates a user"),
+               resources[0].methods().get("post").unwrap().description());
+    assert_eq!(Some("All the users"), resources[0].description());
+}
+
+#[test]
+fn methods_on_nested_resources() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+  /{userId}:
+    delete:
+      description: Deletes a user";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let user = &resources[0].resources()[0];
+    assert_eq!(Some("Deletes a user"),
+               user.methods().get("delete").unwrap().description());
+    assert!(user.methods().get("get").is_none());
+}
+
+#[test]
+fn error_for_unknown_method_field() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    unknown: field";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the method: unknown at line 5 column 5 (in \
+                         /users > get > unknown)");
+}
+
+#[test]
+fn error_for_unknown_verb() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  fetch:
+    description: Not a method";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the resource: fetch at line 4 column 3 (in \
+                         /users > fetch)");
+}
+
+#[test]
+fn loads_block_style_query_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      page:
+        type: integer
+        description: The page to return
+        required: false
+        default: 1
+        example: 3";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let get = resources[0].methods().get("get").unwrap();
+    let page = get.query_parameters().unwrap().get("page").unwrap();
+    assert_eq!(Some("integer"), page.param_type());
+    assert_eq!(Some("The page to return"), page.description());
+    assert!(!page.required());
+    assert_eq!(Some("1"), page.default_value());
+    assert_eq!(&ExampleValue::Number(3.0), page.example().unwrap().value());
+}
+
+#[test]
+fn loads_flow_style_query_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters: { page: { type: integer, required: false }, size: { type: integer } }";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let query_parameters = resources[0].methods().get("get").unwrap().query_parameters().unwrap();
+    assert_eq!(2, query_parameters.len());
+    assert_eq!(Some("integer"), query_parameters.get("page").unwrap().param_type());
+    assert!(!query_parameters.get("page").unwrap().required());
+    assert_eq!(Some("integer"), query_parameters.get("size").unwrap().param_type());
+}
+
+#[test]
+fn loads_shorthand_query_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      page: integer
+      sort: string";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let query_parameters = resources[0].methods().get("get").unwrap().query_parameters().unwrap();
+    assert_eq!(Some("integer"), query_parameters.get("page").unwrap().param_type());
+    assert_eq!(Some("string"), query_parameters.get("sort").unwrap().param_type());
+    assert!(query_parameters.get("sort").unwrap().required());
+}
+
+#[test]
+fn error_for_unknown_query_parameter_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      page:
+        type: integer
+        unknown: facet";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the query parameter: unknown at line 8 column 9 \
+                         (in /users > get > queryParameters > page > unknown)");
+}
+
+#[test]
+fn loads_query_parameter_enum() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      sort:
+        enum: [name, age]
+        default: name";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let get = resources[0].methods().get("get").unwrap();
+    let sort = get.query_parameters().unwrap().get("sort").unwrap();
+    assert_eq!(&vec!["name".to_string(), "age".to_string()],
+               sort.enum_values().unwrap());
+}
+
+#[test]
+fn error_for_query_parameter_example_not_in_enum() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      sort:
+        enum: [name, age]
+        example: height";
+    let result = parse(s);
+    assert_error_result(result,
+                        "The example value is not one of the enum values: height at line 8 column \
+                         9 (in /users > get > queryParameters > sort)");
+}
+
+#[test]
+fn loads_query_parameter_numeric_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      page:
+        type: integer
+        minimum: 1
+        maximum: 100
+        multipleOf: 1
+      name:
+        minLength: 2
+        maxLength: 20";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let query_parameters = resources[0].methods().get("get").unwrap().query_parameters().unwrap();
+    let page = query_parameters.get("page").unwrap();
+    assert_eq!(Some(1.0), page.minimum());
+    assert_eq!(Some(100.0), page.maximum());
+    assert_eq!(Some(1.0), page.multiple_of());
+    let name = query_parameters.get("name").unwrap();
+    assert_eq!(Some(2), name.min_length());
+    assert_eq!(Some(20), name.max_length());
+}
+
+#[test]
+fn error_for_query_parameter_min_length_greater_than_max_length() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      name:
+        minLength: 20
+        maxLength: 2";
+    let result = parse(s);
+    assert_error_result(result,
+                        "The minLength facet must not be greater than the maxLength facet at \
+                         line 8 column 20 (in /users > get > queryParameters > name)");
+}
+
+#[test]
+fn loads_headers() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    headers:
+      X-Request-Id:
+        description: Correlates the request across services
+      X-Custom-{*}:
+        type: string
+        required: true";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let headers = resources[0].methods().get("get").unwrap().headers().unwrap();
+    assert_eq!(2, headers.len());
+    let request_id = headers.get("X-Request-Id").unwrap();
+    assert_eq!(Some("Correlates the request across services"),
+               request_id.description());
+    assert_eq!(None, request_id.param_type());
+    let custom = headers.get("X-Custom-{*}").unwrap();
+    assert_eq!(Some("string"), custom.param_type());
+    assert!(custom.required());
+}
+
+#[test]
+fn loads_responses() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    responses:
+      200:
+        description: The users
+        headers:
+          X-Total-Count:
+            type: integer
+      404:
+        description: Not found";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let responses = resources[0].methods().get("get").unwrap().responses().unwrap();
+    assert_eq!(2, responses.len());
+    let ok = responses.get(&200).unwrap();
+    assert_eq!(Some("The users"), ok.description());
+    assert_eq!(Some("integer"),
+               ok.headers().unwrap().get("X-Total-Count").unwrap().param_type());
+    assert_eq!(Some("Not found"), responses.get(&404).unwrap().description());
+}
+
+#[test]
+fn response_without_body_is_present() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  delete:
+    responses:
+      204:
+      404:";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let responses = resources[0].methods().get("delete").unwrap().responses().unwrap();
+    assert_eq!(None, responses.get(&204).unwrap().description());
+    assert!(responses.contains_key(&404));
+}
+
+#[test]
+fn error_for_out_of_range_status_code() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    responses:
+      2000:
+        description: Too many digits";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing method. Invalid status code: 2000 at line 6 column 7 (in \
+                         /users > get > responses > 2000)");
+}
+
+#[test]
+fn error_for_non_numeric_status_code() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    responses:
+      ok:
+        description: Not a number";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing method. Invalid status code: ok at line 6 column 7 (in \
+                         /users > get > responses > ok)");
+}
+
+#[test]
+fn display_name_falls_back_to_the_key() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    displayName: List users
+    queryParameters:
+      page:
+        displayName: Page number
+        type: integer
+      limit: integer
+  post:";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let users = &raml.resources()[0];
+    assert_eq!("/users", users.display_name_or_key());
+    let get = users.methods().get("get").unwrap();
+    assert_eq!("List users", get.display_name_or_key("get"));
+    assert_eq!("post", users.methods().get("post").unwrap().display_name_or_key("post"));
+    let parameters = get.query_parameters().unwrap();
+    assert_eq!(Some("Page number"), parameters["page"].display_name());
+    assert_eq!("Page number", parameters["page"].display_name_or_key("page"));
+    assert_eq!("limit", parameters["limit"].display_name_or_key("limit"));
+}
+
+#[test]
+fn method_protocols_override_the_root() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: http://example.com
+/users:
+  get:
+    protocols: [ HTTPS ]
+  post:";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let users = &raml.resources()[0];
+    let get = &users.methods()["get"];
+    assert_eq!(Some(&[Protocol::Https][..]), get.protocols());
+    assert_eq!(Some(&[Protocol::Https][..]), get.effective_protocols(&raml, users));
+    let post = &users.methods()["post"];
+    assert_eq!(None, post.protocols());
+    assert_eq!(Some(&[Protocol::Http][..]), post.effective_protocols(&raml, users));
+}
+
+#[test]
+fn error_for_unexpected_method_protocol() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    protocols: [ FTP ]";
+    assert_error_result(parse(s),
+                        "Error parsing method. Unexpected protocol at line 5 column 18 (in \
+                         /users > get > protocols)");
+}
+
+#[test]
+fn error_for_empty_method_protocols() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    protocols: []";
+    assert_error_result(parse(s),
+                        "Error parsing method. Protocols must not be empty at line 5 column 5 (in \
+                         /users > get > protocols)");
+}
+
+#[test]
+fn method_description_over_multiple_lines() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    description: Lists the users
+                 of the API
+  post:
+    description: >
+      Creates a user
+      of the API";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let methods = raml.resources()[0].methods();
+    assert_eq!(Some("Lists the users of the API"), methods["get"].description());
+    assert_eq!(Some("Creates a user of the API"), methods["post"].description());
+}
+
+#[test]
+fn response_description_over_multiple_lines() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    responses:
+      200:
+        description: >-
+          The users
+          of the API";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let responses = raml.resources()[0].methods()["get"].responses().unwrap();
+    assert_eq!(Some("The users of the API"), responses[&200].description());
+}
+
+#[test]
+fn loads_a_query_string() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  PagingParams:
+    properties:
+      page: integer
+  FilterParams:
+    properties:
+      q?: string
+/books:
+  get:
+    queryString:
+      type: [PagingParams, FilterParams]";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let method = &raml.resources()[0].methods()["get"];
+    assert!(method.query_parameters().is_none());
+    let query_string = method.query_string().unwrap();
+    assert_eq!(&[named_type("PagingParams"), named_type("FilterParams")][..],
+               query_string.parent_types());
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn error_for_query_string_with_query_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+/books:
+  get:
+    queryParameters:
+      page: integer
+    queryString: PagingParams";
+    assert_error_result(parse(s),
+                        "Error parsing method. queryString and queryParameters can't both be \
+                         declared at line 7 column 5 (in /books > get > queryString)");
+}
+
+#[test]
+fn loads_response_headers() {
+    let s = "#%RAML 1.0
+title: Some API
+/books:
+  post:
+    responses:
+      201:
+        headers:
+          Location:
+            description: The URL of the new book
+            required: true
+          Cache-Control:
+            default: no-store
+      400:
+        headers:
+          Cache-Control:
+            default: no-cache
+          Retry-After: integer";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let method = &raml.resources()[0].methods()["post"];
+    let created = &method.responses().unwrap()[&201];
+    let location = &created.headers().unwrap()["Location"];
+    assert_eq!(Some("The URL of the new book"), location.description());
+    assert!(location.required());
+    let headers = method.all_response_headers();
+    let mut names: Vec<&str> = headers.keys().cloned().collect();
+    names.sort();
+    assert_eq!(vec!["Cache-Control", "Location", "Retry-After"], names);
+    assert_eq!(Some("no-store"), headers["Cache-Control"].default_value());
+}
+
+#[test]
+fn loads_yaml_boolean_forms_of_required() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      page:
+        required: True
+      size:
+        required: false
+      sort:
+        required: yes
+      filter:
+        required: Off";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let query_parameters =
+        raml.resources()[0].methods()["get"].query_parameters().unwrap();
+    assert!(query_parameters["page"].required());
+    assert!(!query_parameters["size"].required());
+    assert!(query_parameters["sort"].required());
+    assert!(!query_parameters["filter"].required());
+}
+
+#[test]
+fn error_for_required_that_is_not_a_boolean() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      page:
+        required: maybe";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing required. Expected true or false, Found maybe at line 7 \
+                         column 19 (in /users > get > queryParameters > page > required)");
+}
+
+#[test]
+fn loads_yaml_integer_forms_of_lengths() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      code:
+        minLength: 0x10
+        maxLength: 1_000";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let code = &raml.resources()[0].methods()["get"].query_parameters().unwrap()["code"];
+    assert_eq!(Some(16), code.min_length());
+    assert_eq!(Some(1000), code.max_length());
+}
+
+#[test]
+fn error_for_length_that_is_not_an_integer() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    queryParameters:
+      code:
+        minLength: ten";
+    let result = parse(s);
+    assert_error_kind(result, ErrorKind::InvalidInteger);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "openapi")]
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+extern crate serde_json;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+use std::fs;
+
+#[test]
+fn converts_the_fixture_to_the_expected_document() {
+    let raml = assert_ok_and_unwrap(RamlParser::load_from_file("tests/fixtures/openapi/api.raml"));
+    let expected = fs::read_to_string("tests/fixtures/openapi/api.json").unwrap();
+    let expected: serde_json::Value = serde_json::from_str(&expected).unwrap();
+    assert_eq!(expected, raml.to_openapi_v3());
+}
+
+#[test]
+fn reports_what_could_not_be_converted() {
+    let raml = assert_ok_and_unwrap(RamlParser::load_from_file("tests/fixtures/openapi/api.raml"));
+    let (_, warnings) = raml.to_openapi_v3_with_diagnostics();
+    let warnings: Vec<_> = warnings.iter().map(|w| (w.kind(), w.message())).collect();
+    assert_eq!(vec![(ConversionWarningKind::UnsupportedSecurityScheme,
+                     "Security scheme custom of type x-custom has no OpenAPI equivalent"),
+                    (ConversionWarningKind::UnsupportedAuthorizationGrant,
+                     "Grant urn:example:grant of security scheme oauth_2_0 has no OpenAPI flow"),
+                    (ConversionWarningKind::ParameterizedTrait,
+                     "Trait paged applied to get /books with parameters is not converted"),
+                    (ConversionWarningKind::TypeExamples,
+                     "The examples of type Ebook are left out")],
+               warnings);
+}
+
+#[test]
+fn defaults_the_missing_nodes() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+baseUri: https://api.example.com/{tenant}
+/books:
+  get:"));
+    let openapi = raml.to_openapi_v3();
+    assert_eq!("", openapi["info"]["version"]);
+    assert_eq!("", openapi["servers"][0]["variables"]["tenant"]["default"]);
+    assert_eq!(serde_json::json!({ "default": { "description": "" } }),
+               openapi["paths"]["/books"]["get"]["responses"]);
+}
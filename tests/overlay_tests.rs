@@ -0,0 +1,147 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+const API: &str = "#%RAML 1.0
+title: Books API
+description: Books for sale
+baseUri: https://api.example.com
+/books:
+  description: All books
+  get:
+    description: List the books
+    queryParameters:
+      author: string";
+
+fn load_overlay(s: &str) -> RamlOverlay {
+    let result = RamlParser::load_overlay_from_str(s);
+    if result.is_err() {
+        println!("Unexpected error {:?}", result);
+    }
+    result.ok().unwrap()
+}
+
+#[test]
+fn loads_overlay() {
+    let s = "#%RAML 1.0 Overlay
+extends: api.raml
+description: Livres en vente";
+    let overlay = load_overlay(s);
+    assert_eq!(&OverlayKind::Overlay, overlay.kind());
+    assert_eq!("api.raml", overlay.extends());
+    assert_eq!("", overlay.document().title());
+}
+
+#[test]
+fn error_for_overlay_without_extends() {
+    let s = "#%RAML 1.0 Extension
+title: Books API";
+    let result = RamlParser::load_overlay_from_str(s);
+    assert_eq!("Error parsing document root. Missing field: extends at line 2 column 1",
+               result.err().unwrap().error());
+}
+
+#[test]
+fn loads_overlay_as_fragment() {
+    let s = "#%RAML 1.0 Extension
+extends: api.raml";
+    match RamlParser::load_fragment_from_str(s) {
+        Ok(RamlFragment::Overlay(overlay)) => assert_eq!(&OverlayKind::Extension, overlay.kind()),
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn overlay_localizes_descriptions() {
+    let s = "#%RAML 1.0 Overlay
+extends: api.raml
+title: API des livres
+description: Livres en vente
+/books:
+  description: Tous les livres
+  get:
+    description: Lister les livres";
+    let raml = assert_ok_and_unwrap(parse(API));
+    let result = raml.apply_overlay(&load_overlay(s));
+    let localized = assert_ok_and_unwrap(result);
+    assert_eq!("API des livres", localized.title());
+    assert_eq!(Some("Livres en vente"), localized.description());
+    assert_eq!(Some("https://api.example.com"), localized.base_uri());
+    let resources = localized.resources();
+    assert_eq!(1, resources.len());
+    assert_eq!(Some("Tous les livres"), resources[0].description());
+    let method = &resources[0].methods()["get"];
+    assert_eq!(Some("Lister les livres"), method.description());
+    assert!(method.query_parameters().unwrap().contains_key("author"));
+}
+
+#[test]
+fn error_for_overlay_adding_resource() {
+    let s = "#%RAML 1.0 Overlay
+extends: api.raml
+/sandbox:
+  get:";
+    let raml = assert_ok_and_unwrap(parse(API));
+    let result = raml.apply_overlay(&load_overlay(s));
+    assert_error_result(result,
+                        "Error applying overlay. Only an extension may change: resource /sandbox");
+}
+
+#[test]
+fn error_for_overlay_changing_method_behavior() {
+    let s = "#%RAML 1.0 Overlay
+extends: api.raml
+/books:
+  get:
+    queryParameters:
+      title: string";
+    let raml = assert_ok_and_unwrap(parse(API));
+    let result = raml.apply_overlay(&load_overlay(s));
+    assert_error_result(result,
+                        "Error applying overlay. Only an extension may change: queryParameters \
+                         of get /books");
+}
+
+#[test]
+fn error_for_overlay_changing_base_uri() {
+    let s = "#%RAML 1.0 Overlay
+extends: api.raml
+baseUri: https://sandbox.example.com";
+    let raml = assert_ok_and_unwrap(parse(API));
+    let result = raml.apply_overlay(&load_overlay(s));
+    assert_error_result(result, "Error applying overlay. Only an extension may change: baseUri");
+}
+
+#[test]
+fn extension_adds_sandbox_resource() {
+    let s = "#%RAML 1.0 Extension
+extends: api.raml
+baseUri: https://sandbox.example.com
+/sandbox:
+  post:
+    description: Reset the sandbox
+/books:
+  get:
+    queryParameters:
+      title: string";
+    let raml = assert_ok_and_unwrap(parse(API));
+    let result = raml.apply_overlay(&load_overlay(s));
+    let sandbox = assert_ok_and_unwrap(result);
+    assert_eq!("Books API", sandbox.title());
+    assert_eq!(Some("https://sandbox.example.com"), sandbox.base_uri());
+    let resources = sandbox.resources();
+    assert_eq!(2, resources.len());
+    assert_eq!("/sandbox", resources[1].relative_uri());
+    assert_eq!(Some("Reset the sandbox"),
+               resources[1].methods()["post"].description());
+    let query_parameters = resources[0].methods()["get"].query_parameters().unwrap();
+    assert!(query_parameters.contains_key("author"));
+    assert!(query_parameters.contains_key("title"));
+    // The base API is left unchanged.
+    assert_eq!(1, raml.resources().len());
+}
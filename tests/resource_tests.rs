@@ -0,0 +1,375 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+use raml_parser::*;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn loads_a_resource() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  displayName: Users
+  description: All the users";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    assert_eq!(1, resources.len());
+    assert_eq!("/users", resources[0].relative_uri());
+    assert_eq!(Some("Users"), resources[0].display_name());
+    assert_eq!(Some("All the users"), resources[0].description());
+    assert!(resources[0].resources().is_empty());
+}
+
+#[test]
+fn sibling_resources_preserve_document_order() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  description: All the users
+/groups:
+  description: All the groups
+/accounts:
+  description: All the accounts";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let uris: Vec<String> = raml.resources()
+        .iter()
+        .map(|r| r.relative_uri().to_string())
+        .collect();
+    assert_eq!(vec!["/users", "/groups", "/accounts"], uris);
+}
+
+#[test]
+fn loads_nested_resources() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  description: All the users
+  /{userId}:
+    description: A single user
+  /me:
+    description: The current user";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let nested = resources[0].resources();
+    assert_eq!(2, nested.len());
+    assert_eq!("/{userId}", nested[0].relative_uri());
+    assert_eq!(Some("A single user"), nested[0].description());
+    assert_eq!("/me", nested[1].relative_uri());
+}
+
+#[test]
+fn loads_deeply_nested_resources() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  /{userId}:
+    /orders:
+      /{orderId}:
+        description: A single order
+/groups:
+  description: All the groups";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    assert_eq!(2, resources.len());
+    let order = &resources[0].resources()[0].resources()[0].resources()[0];
+    assert_eq!("/{orderId}", order.relative_uri());
+    assert_eq!(Some("A single order"), order.description());
+    assert_eq!("/groups", resources[1].relative_uri());
+}
+
+#[test]
+fn error_for_unknown_resource_field() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  unknown: field";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the resource: unknown at line 4 column 3 (in \
+                         /users > unknown)");
+}
+
+#[test]
+fn unknown_resource_field_error_has_kind() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  unknown: field";
+    let err = assert_error_kind(parse(s), ErrorKind::UnexpectedKeyRoot);
+    assert_eq!(Some("unknown"), err.field());
+}
+
+#[test]
+fn loads_uri_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  uriParameters:
+    userId:
+      type: integer
+      description: The user identifier";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let user_id = resources[0].uri_parameters().unwrap().get("userId").unwrap();
+    assert_eq!(Some("integer"), user_id.param_type());
+    assert_eq!(Some("The user identifier"), user_id.description());
+}
+
+#[test]
+fn undeclared_uri_parameters_are_allowed_by_default() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  description: A single user";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(None, raml.resources()[0].uri_parameters());
+}
+
+fn parse_validating_uri_parameters(s: &str) -> RamlResult {
+    let options = ParseOptions {
+        validate_uri_parameters: true,
+        ..ParseOptions::default()
+    };
+    RamlParser::load_from_str_with_options(s, &options)
+}
+
+#[test]
+fn error_for_undeclared_uri_parameter_when_validating() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  /{userId}:
+    description: A single user";
+    let result = parse_validating_uri_parameters(s);
+    assert_error_result(result,
+                        "Error parsing resource. Uri parameter not declared: userId at line 4 \
+                         column 3 (in /users > /{userId})");
+}
+
+#[test]
+fn error_for_unused_uri_parameter_when_validating() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  uriParameters:
+    userId:
+      type: integer
+    groupId:
+      type: integer";
+    let result = parse_validating_uri_parameters(s);
+    assert_error_result(result,
+                        "Error parsing resource. Uri parameter not found in the resource uri: \
+                         groupId at line 3 column 1 (in /users/{userId})");
+}
+
+#[test]
+fn nested_uri_parameters_are_matched_against_their_own_segment() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  uriParameters:
+    userId: integer
+  /orders/{orderId}:
+    uriParameters:
+      userId: integer
+      orderId: integer";
+    let result = parse_validating_uri_parameters(s);
+    assert_error_result(result,
+                        "Error parsing resource. Uri parameter not found in the resource uri: \
+                         userId at line 6 column 3 (in /users/{userId} > /orders/{orderId})");
+}
+
+#[test]
+fn validated_nested_uri_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  uriParameters:
+    userId: integer
+  /orders/{orderId}:
+    uriParameters:
+      orderId: integer";
+    let result = parse_validating_uri_parameters(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let orders = &resources[0].resources()[0];
+    assert_eq!(Some("integer"),
+               orders.uri_parameters().unwrap().get("orderId").unwrap().param_type());
+}
+
+#[test]
+fn finds_resource_by_path_preferring_literal_segments() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  /{id}:
+    description: A user
+  /me:
+    description: The current user";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let (me, values) = raml.resource_by_path("/users/me").unwrap();
+    assert_eq!("/me", me.relative_uri());
+    assert!(values.is_empty());
+    let (user, values) = raml.resource_by_path("/users/123/").unwrap();
+    assert_eq!("/{id}", user.relative_uri());
+    assert_eq!(Some(&"123".to_string()), values.get("id"));
+    let (users, _) = raml.resource_by_path("/users").unwrap();
+    assert_eq!("/users", users.relative_uri());
+}
+
+#[test]
+fn finds_resource_by_path_across_multi_segment_uris() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  /orders/{orderId}.json:
+    get:";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let (orders, values) = raml.resource_by_path("/users/7/orders/42.json").unwrap();
+    assert_eq!("/orders/{orderId}.json", orders.relative_uri());
+    assert_eq!(Some(&"7".to_string()), values.get("userId"));
+    assert_eq!(Some(&"42".to_string()), values.get("orderId"));
+    assert!(raml.resource_by_path("/users/7/orders").is_none());
+    assert!(raml.resource_by_path("/users/7/orders/42.xml").is_none());
+    assert!(raml.resource_by_path("/groups").is_none());
+}
+
+#[test]
+fn iterates_operations_depth_first_with_full_paths() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://api.example.com/
+/users:
+  post:
+  get:
+  /{userId}:
+    get:
+    /orders/:
+      get:
+  /me:
+    delete:
+/groups:
+  get:";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let operations: Vec<(String, &str)> =
+        raml.iter_operations().map(|op| (op.path().to_string(), op.verb())).collect();
+    assert_eq!(vec![("/users".to_string(), "get"),
+                    ("/users".to_string(), "post"),
+                    ("/users/{userId}".to_string(), "get"),
+                    ("/users/{userId}/orders/".to_string(), "get"),
+                    ("/users/me".to_string(), "delete"),
+                    ("/groups".to_string(), "get")],
+               operations);
+    let orders = raml.iter_operations().nth(3).unwrap();
+    assert_eq!("https://api.example.com/users/{userId}/orders/", orders.uri());
+    assert_eq!("/orders/", orders.resource().relative_uri());
+}
+
+#[test]
+fn resource_description_over_multiple_lines() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  description: All the users
+               of the API";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(Some("All the users of the API"), raml.resources()[0].description());
+}
+
+#[test]
+fn computes_the_absolute_uris_of_nested_resources() {
+    let s = "#%RAML 1.0
+title: Some API
+version: v1
+baseUri: https://api.example.com/{version}/
+/users:
+  get:
+  /{userId}:
+    /orders:
+      get:";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("https://api.example.com/v1/".to_string()),
+               raml.base_uri_expanded().unwrap());
+    let users = &raml.resources()[0];
+    let orders = &users.resources()[0].resources()[0];
+    assert_eq!("https://api.example.com/v1/users", users.absolute_uri(&raml).unwrap());
+    assert_eq!("https://api.example.com/v1/users/{userId}/orders",
+               orders.absolute_uri(&raml).unwrap());
+}
+
+#[test]
+fn computes_absolute_uris_without_a_base_uri() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  /{userId}:
+    get:";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(None, raml.base_uri_expanded().unwrap());
+    let user = &raml.resources()[0].resources()[0];
+    assert_eq!("/users/{userId}", user.absolute_uri(&raml).unwrap());
+}
+
+#[test]
+fn error_for_absolute_uri_with_an_undeclared_version() {
+    let raml = RamlBuilder::new()
+        .title("Some API")
+        .base_uri("https://api.example.com/{version}")
+        .resource(assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Some API
+/users:
+  get:"))
+            .into_resources()
+            .remove(0))
+        .build()
+        .unwrap();
+    let error = raml.resources()[0].absolute_uri(&raml).err().unwrap();
+    assert_eq!(ErrorKind::MissingBaseUriVersion, error.kind());
+}
+
+#[test]
+fn inherits_the_uri_parameters_of_parent_resources() {
+    let s = "#%RAML 1.0
+title: Some API
+/users/{userId}:
+  uriParameters:
+    userId:
+      type: integer
+  /orders/{orderId}:
+    get:
+    delete:
+      uriParameters:
+        orderId:
+          type: integer
+          description: The order to delete";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let orders = &raml.resources()[0].resources()[0];
+    let parameters = orders.inherited_uri_parameters(&raml);
+    assert_eq!(2, parameters.len());
+    assert_eq!(Some("integer"), parameters["userId"].param_type());
+    assert!(!parameters["userId"].is_implicit());
+    assert_eq!(Some("string"), parameters["orderId"].param_type());
+    assert!(parameters["orderId"].is_implicit());
+
+    let chain = orders.resource_chain(&raml);
+    assert_eq!(vec!["/users/{userId}", "/orders/{orderId}"],
+               chain.iter().map(|resource| resource.relative_uri()).collect::<Vec<_>>());
+    let get = orders.methods()["get"].effective_uri_parameters(&chain);
+    assert!(get["orderId"].is_implicit());
+    let delete = orders.methods()["delete"].effective_uri_parameters(&chain);
+    assert_eq!(Some("integer"), delete["orderId"].param_type());
+    assert_eq!(Some("The order to delete"), delete["orderId"].description());
+    assert!(!delete["orderId"].is_implicit());
+    assert_eq!(Some("integer"), delete["userId"].param_type());
+}
@@ -0,0 +1,224 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+fn parse_applying_resource_types(s: &str) -> RamlResult {
+    let options = ParseOptions {
+        apply_resource_types: true,
+        apply_traits: true,
+        ..ParseOptions::default()
+    };
+    RamlParser::load_from_str_with_options(s, &options)
+}
+
+#[test]
+fn loads_resource_types() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  collection:
+    usage: Use for collections
+    description: A collection
+    get:
+      description: List the items
+    post?:
+      description: Add an item";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resource_types = raml.resource_types().unwrap();
+    let collection = resource_types.get("collection").unwrap();
+    assert_eq!(Some("Use for collections"), collection.usage());
+    assert_eq!(Some("A collection"), collection.description());
+    assert_eq!(2, collection.methods().len());
+    assert!(!collection.is_optional_method("get"));
+    assert!(collection.is_optional_method("post"));
+}
+
+#[test]
+fn loads_resource_type_references() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  collection:
+/users:
+  type: collection
+/groups:
+  type: { collection: { itemType: Group } }";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let users_type = resources[0].resource_type().unwrap();
+    assert_eq!("collection", users_type.name());
+    assert_eq!(None, users_type.parameters());
+    let groups_type = resources[1].resource_type().unwrap();
+    assert_eq!("collection", groups_type.name());
+    assert_eq!(Some(&"Group".to_string()),
+               groups_type.parameters().unwrap().get("itemType"));
+}
+
+#[test]
+fn applies_resource_types() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+    queryParameters:
+      page: integer
+resourceTypes:
+  collection:
+    description: A collection
+    is: [paged]
+    get:
+      description: List the items
+    post?:
+      description: Add an item
+    delete?:
+      description: Remove all items
+/users:
+  type: collection
+  description: The users
+  post:";
+    let result = parse_applying_resource_types(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let users = &resources[0];
+    assert_eq!(Some("The users"), users.description());
+    assert_eq!(2, users.methods().len());
+    let get = users.methods().get("get").unwrap();
+    assert_eq!(Some("List the items"), get.description());
+    assert!(get.query_parameters().unwrap().contains_key("page"));
+    assert_eq!(Some("Add an item"),
+               users.methods().get("post").unwrap().description());
+    assert!(!users.methods().contains_key("delete"));
+}
+
+#[test]
+fn applies_resource_types_based_on_other_resource_types() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  base:
+    get:
+      description: Read the resource
+  collection:
+    type: base
+    post:
+      description: Add an item
+/users:
+  type: collection";
+    let result = parse_applying_resource_types(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    assert!(resources[0].methods().contains_key("get"));
+    assert!(resources[0].methods().contains_key("post"));
+}
+
+#[test]
+fn error_for_undeclared_resource_type() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  type: collection";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing resource. Resource type not declared: collection at line \
+                         4 column 9");
+}
+
+#[test]
+fn error_for_circular_resource_types() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  a:
+    type: b
+  b:
+    type: a
+/users:
+  type: a";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing resource. Circular resource type: a -> b -> a at line 7 \
+                         column 11");
+}
+
+#[test]
+fn substitutes_resource_type_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  collection:
+    description: A collection of <<itemType | !pluralize>> at <<resourcePath>>
+    get:
+      displayName: List <<resourcePathName | !uppercamelcase>>
+/api:
+  /users:
+    type: { collection: { itemType: user } }";
+    let result = parse_applying_resource_types(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let users = &resources[0].resources()[0];
+    assert_eq!(Some("A collection of users at /api/users"), users.description());
+    assert_eq!(Some("List Users"),
+               users.methods().get("get").unwrap().display_name());
+}
+
+#[test]
+fn pluralizes_and_singularizes_resource_path_names() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  collection:
+    description: <<resourcePathName | !pluralize>>
+    get:
+      displayName: <<resourcePathName | !singularize>>
+/users:
+  type: collection
+/user:
+  type: collection
+/categories:
+  type: collection
+/address:
+  type: collection
+/status:
+  type: collection
+/bus:
+  type: collection
+/addresses:
+  type: collection";
+    let raml = assert_ok_and_unwrap(parse_applying_resource_types(s));
+    let names: Vec<(Option<&str>, Option<&str>)> = raml.resources()
+        .iter()
+        .map(|resource| {
+            (resource.description(), resource.methods()["get"].display_name())
+        })
+        .collect();
+    assert_eq!(vec![(Some("users"), Some("user")),
+                    (Some("users"), Some("user")),
+                    (Some("categories"), Some("category")),
+                    (Some("addresses"), Some("address")),
+                    (Some("statuses"), Some("status")),
+                    (Some("buses"), Some("bus")),
+                    (Some("addresses"), Some("address"))],
+               names);
+}
+
+#[test]
+fn error_for_undeclared_resource_type_parameter() {
+    let s = "#%RAML 1.0
+title: Some API
+resourceTypes:
+  collection:
+    description: A collection of <<itemType>>
+/users:
+  type: collection";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing resource type collection. Parameter not declared: \
+                         itemType at line 5 column 18");
+}
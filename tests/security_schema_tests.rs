@@ -19,8 +19,8 @@ securitySchemes:
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
     let security_schemes = raml.security_schemes().unwrap();
-    assert_eq!(SecuritySchemeType::OAuth2,
-               security_schemes.get("oauth_2_0").unwrap().security_type);
+    assert_eq!(&SecuritySchemeType::OAuth2,
+               security_schemes.get("oauth_2_0").unwrap().security_type());
 }
 
 #[test]
@@ -34,8 +34,21 @@ securitySchemes:
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
     let security_schemes = raml.security_schemes().unwrap();
-    assert_eq!(SecuritySchemeType::XOther("x-custom".to_string()),
-               security_schemes.get("custom").unwrap().security_type);
+    assert_eq!(&SecuritySchemeType::XOther("x-custom".to_string()),
+               security_schemes.get("custom").unwrap().security_type());
+}
+
+#[test]
+fn error_for_unknown_security_type() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  custom:
+    type: Custom";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Unexpected type at line 5 column 11 (in \
+                         securitySchemes > custom > type)");
 }
 
 #[test]
@@ -52,10 +65,10 @@ securitySchemes:
     let result = parse(s);
     let raml = assert_ok_and_unwrap(result);
     let security_schemes = raml.security_schemes().unwrap();
-    assert_eq!(Some("sample display name".to_string()),
-               security_schemes.get("oauth_2_0").unwrap().display_name);
-    assert_eq!(Some("sample description".to_string()),
-               security_schemes.get("oauth_2_0").unwrap().description);
+    assert_eq!(Some("sample display name"),
+               security_schemes.get("oauth_2_0").unwrap().display_name());
+    assert_eq!(Some("sample description"),
+               security_schemes.get("oauth_2_0").unwrap().description());
 }
 
 #[test]
@@ -65,8 +78,9 @@ title: Some API
 securitySchemes:";
     let result = parse(s);
     assert_error_result(result,
-                        "Unexpected entry found. Expected Block-Mapping-Start, Found Block-End \
-                         at line 4 column 1")
+                        "Unexpected entry found. Expected one of \
+                         Block-Mapping-Start,Flow-Mapping-Start, Found Block-End at line 4 column \
+                         1 (in securitySchemes)")
 }
 
 #[test]
@@ -77,7 +91,51 @@ securitySchemes:
   oauth_2_0:
     displayName: sample display name";
     let result = parse(s);
-    assert_error_result(result, "Error parsing security scheme. Missing field: type")
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: type at line 4 column 3 (in \
+                         securitySchemes > oauth_2_0)")
+}
+
+#[test]
+fn error_if_missing_type_with_only_description() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  basic:
+    type: Basic Authentication
+  custom:
+    description: A custom scheme";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: type at line 6 column 3 (in \
+                         securitySchemes > custom)")
+}
+
+#[test]
+fn error_if_missing_type_with_empty_mapping() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  custom: {}";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: type at line 4 column 3 (in \
+                         securitySchemes > custom)")
+}
+
+#[test]
+fn error_if_missing_type_with_described_by() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  custom:
+    describedBy:
+      headers:
+        Authorization: string";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: type at line 4 column 3 (in \
+                         securitySchemes > custom)")
 }
 
 #[test]
@@ -89,9 +147,589 @@ securitySchemes:
     describedBy:";
     let result = parse(s);
     assert_error_result(result,
-                        "Unexpected entry found. Expected Block-Mapping-Start, Found Block-End \
-                         at line 6 column 1")
+                        "Unexpected entry found. Expected one of \
+                         Block-Mapping-Start,Flow-Mapping-Start, Found Block-End at line 6 column \
+                         1 (in securitySchemes > oauth_2_0 > describedBy)")
+}
+
+#[test]
+fn valid_described_by_headers() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    describedBy:
+      headers:
+        Authorization:
+          type: string
+      queryParameters:
+        access_token: string";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    let described_by = security_schemes["oauth_2_0"].described_by().unwrap();
+    assert!(described_by.headers().unwrap().contains_key("Authorization"));
+    assert!(described_by.query_parameters().unwrap().contains_key("access_token"));
+    assert_eq!(None, described_by.responses());
+}
+
+#[test]
+fn valid_described_by_responses() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    describedBy:
+      responses: { 401: { description: Unauthorized } }";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    let described_by = security_schemes["oauth_2_0"].described_by().unwrap();
+    let responses = described_by.responses().unwrap();
+    assert_eq!(Some("Unauthorized"), responses[&401].description());
+    assert_eq!(None, described_by.headers());
+}
+
+#[test]
+fn error_for_unexpected_described_by_key() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    describedBy:
+      body: {}";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the security scheme: body at line 7 column 7 \
+                         (in securitySchemes > oauth_2_0 > describedBy > body)")
+}
+
+#[test]
+fn root_secured_by_preserves_order_including_null() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+securedBy: [null, oauth_2_0]";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    assert_eq!(&vec![SecuredBy::Null, SecuredBy::Scheme("oauth_2_0".to_string())],
+               raml.secured_by().unwrap());
+}
+
+#[test]
+fn error_if_root_secured_by_references_undeclared_scheme() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+securedBy: [oauth_2_0, basic]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing document root. Security scheme not declared: basic at \
+                         line 6 column 24")
+}
+
+#[test]
+fn valid_oauth_2_0_settings() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    settings:
+      authorizationUri: https://www.example.com/oauth2/authorize
+      accessTokenUri: https://www.example.com/oauth2/token
+      authorizationGrants:
+        - authorization_code
+        - urn:ietf:params:oauth:grant-type:saml2-bearer
+      scopes:
+        - read
+        - write
+    type: OAuth 2.0";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    let settings = match security_schemes["oauth_2_0"].settings() {
+        Some(SecuritySchemeSettings::OAuth2(ref settings)) => settings,
+        ref settings => panic!("Unexpected settings {:?}", settings),
+    };
+    assert_eq!(Some("https://www.example.com/oauth2/authorize"),
+               settings.authorization_uri());
+    assert_eq!(Some("https://www.example.com/oauth2/token"),
+               settings.access_token_uri());
+    assert_eq!(&[AuthorizationGrant::AuthorizationCode,
+                 AuthorizationGrant::Extension("urn:ietf:params:oauth:grant-type:saml2-bearer"
+                     .to_string())],
+               settings.authorization_grants());
+    assert_eq!(Some(&["read".to_string(), "write".to_string()][..]), settings.scopes());
+}
+
+#[test]
+fn implicit_grant_does_not_require_access_token_uri() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    settings:
+      authorizationUri: https://www.example.com/oauth2/authorize
+      authorizationGrants: [ implicit ]";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    match security_schemes["oauth_2_0"].settings() {
+        Some(SecuritySchemeSettings::OAuth2(ref settings)) => {
+            assert_eq!(None, settings.access_token_uri())
+        }
+        ref settings => panic!("Unexpected settings {:?}", settings),
+    }
+}
+
+#[test]
+fn error_if_access_token_uri_missing() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    settings:
+      authorizationGrants: [ password ]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: accessTokenUri at line 7 \
+                         column 7 (in securitySchemes > oauth_2_0)")
+}
+
+#[test]
+fn error_for_invalid_authorization_grant() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    settings:
+      accessTokenUri: https://www.example.com/oauth2/token
+      authorizationGrants: [ password, refresh_token ]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Invalid authorization grant: refresh_token \
+                         at line 8 column 40 (in securitySchemes > oauth_2_0 > \
+                         authorizationGrants)")
+}
+
+#[test]
+fn valid_oauth_1_0_settings() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_1_0:
+    type: OAuth 1.0
+    settings:
+      requestTokenUri: https://api.example.com/1/oauth/request_token
+      authorizationUri: https://www.example.com/1/oauth/authorize
+      tokenCredentialsUri: https://api.example.com/1/oauth/access_token
+      signatures: [ HMAC-SHA1, PLAINTEXT ]";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    let settings = match security_schemes["oauth_1_0"].settings() {
+        Some(SecuritySchemeSettings::OAuth1(ref settings)) => settings,
+        ref settings => panic!("Unexpected settings {:?}", settings),
+    };
+    assert_eq!("https://api.example.com/1/oauth/request_token",
+               settings.request_token_uri());
+    assert_eq!("https://www.example.com/1/oauth/authorize",
+               settings.authorization_uri());
+    assert_eq!("https://api.example.com/1/oauth/access_token",
+               settings.token_credentials_uri());
+    assert_eq!(Some(&[OAuth1Signature::HmacSha1, OAuth1Signature::Plaintext][..]),
+               settings.signatures());
+}
+
+#[test]
+fn error_if_oauth_1_0_uri_missing() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_1_0:
+    type: OAuth 1.0
+    settings:
+      requestTokenUri: https://api.example.com/1/oauth/request_token
+      authorizationUri: https://www.example.com/1/oauth/authorize";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: tokenCredentialsUri at line \
+                         7 column 7 (in securitySchemes > oauth_1_0)")
+}
+
+#[test]
+fn error_if_oauth_1_0_settings_missing() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_1_0:
+    type: OAuth 1.0";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: settings at line 4 column 3 \
+                         (in securitySchemes > oauth_1_0)")
+}
+
+#[test]
+fn error_for_invalid_oauth_1_0_signature() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_1_0:
+    type: OAuth 1.0
+    settings:
+      requestTokenUri: https://api.example.com/1/oauth/request_token
+      authorizationUri: https://www.example.com/1/oauth/authorize
+      tokenCredentialsUri: https://api.example.com/1/oauth/access_token
+      signatures:
+        - RSA-SHA1
+        - MD5";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Invalid signature method: MD5 at line 12 \
+                         column 11 (in securitySchemes > oauth_1_0 > signatures)")
+}
+
+const SECURED_API: &str = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+  basic:
+    type: Basic Authentication
+securedBy: [basic]
+/users:
+  securedBy: [null, oauth_2_0]
+  get:
+  post:
+    securedBy: [{ oauth_2_0: { scopes: [write] } }]
+  /{id}:
+    get:";
+
+#[test]
+fn resource_and_method_secured_by() {
+    let raml = assert_ok_and_unwrap(parse(SECURED_API));
+    let resources = raml.resources();
+    let users = &resources[0];
+    assert_eq!(Some(&vec![SecuredBy::Null, SecuredBy::Scheme("oauth_2_0".to_string())]),
+               users.secured_by());
+    let post = &users.methods()["post"];
+    let secured_by = post.secured_by().unwrap();
+    assert_eq!(1, secured_by.len());
+    match secured_by[0] {
+        SecuredBy::Parameterized(ref name, ref parameters) => {
+            assert_eq!("oauth_2_0", name);
+            assert_eq!(&ExampleValue::Sequence(vec![ExampleValue::String("write".to_string())]),
+                       &parameters["scopes"]);
+        }
+        ref secured_by => panic!("Unexpected entry {:?}", secured_by),
+    }
 }
 
 #[test]
-fn valid_described_by_headers() {}
+fn effective_secured_by_falls_back_to_resource_then_root() {
+    let raml = assert_ok_and_unwrap(parse(SECURED_API));
+    let users = &raml.resources()[0];
+    let post = &users.methods()["post"];
+    assert_eq!(post.secured_by(), post.effective_secured_by(&raml));
+    let get = &users.methods()["get"];
+    assert_eq!(users.secured_by(), get.effective_secured_by(&raml));
+    let nested_get = &users.resources()[0].methods()["get"];
+    assert_eq!(Some(&vec![SecuredBy::Scheme("basic".to_string())]),
+               nested_get.effective_secured_by(&raml));
+}
+
+#[test]
+fn error_if_method_secured_by_references_undeclared_scheme() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    securedBy: [oauth_2_0]";
+    let result = parse(s);
+    assert_error_result(result, "Error parsing method. Security scheme not declared: oauth_2_0")
+}
+
+#[test]
+fn null_security_scheme_type() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  api_key:
+    type: null
+    displayName: API key
+    describedBy:
+      headers:
+        X-Api-Key: string
+  custom:
+    type: Null
+  documented:
+    type:
+    description: Documents a header";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    let api_key = &security_schemes["api_key"];
+    assert_eq!(&SecuritySchemeType::Null, api_key.security_type());
+    assert_eq!(Some("API key"), api_key.display_name());
+    let headers = api_key.described_by().unwrap().headers().unwrap();
+    assert!(headers.contains_key("X-Api-Key"));
+    assert_eq!(&SecuritySchemeType::Null, security_schemes["custom"].security_type());
+    let documented = &security_schemes["documented"];
+    assert_eq!(&SecuritySchemeType::Null, documented.security_type());
+    assert_eq!(Some("Documents a header"), documented.description());
+}
+
+#[test]
+fn error_for_duplicate_security_scheme() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+  oauth_2_0:
+    type: Basic Authentication";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Duplicate key: oauth_2_0 at line 6 column \
+                         3 (in securitySchemes > oauth_2_0)")
+}
+
+#[test]
+fn error_for_duplicate_key_in_security_scheme() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  basic:
+    type: Basic Authentication
+    description: Basic access
+    type: Digest Authentication";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Duplicate key: type at line 7 column 5 \
+                         (in securitySchemes > basic > type)")
+}
+
+#[test]
+fn x_other_security_type_preserves_case() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  jwt:
+    type: x-Custom-JWT
+  basic:
+    type: BASIC Authentication";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    assert_eq!(&SecuritySchemeType::XOther("x-Custom-JWT".to_string()),
+               security_schemes["jwt"].security_type());
+    assert_eq!(&SecuritySchemeType::BasicAuthentication,
+               security_schemes["basic"].security_type());
+}
+
+#[test]
+fn security_scheme_type_display() {
+    assert_eq!("OAuth 1.0", SecuritySchemeType::OAuth1.to_string());
+    assert_eq!("OAuth 2.0", SecuritySchemeType::OAuth2.to_string());
+    assert_eq!("Basic Authentication",
+               SecuritySchemeType::BasicAuthentication.to_string());
+    assert_eq!("Digest Authentication",
+               SecuritySchemeType::DigestAuthentication.to_string());
+    assert_eq!("Pass Through", SecuritySchemeType::PassThrough.to_string());
+    assert_eq!("x-Custom-JWT",
+               SecuritySchemeType::XOther("x-Custom-JWT".to_string()).as_str());
+}
+
+#[test]
+fn valid_pass_through_settings() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  api_key:
+    type: Pass Through
+    settings:
+      headerName: X-Api-Key";
+
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let security_schemes = raml.security_schemes().unwrap();
+    let settings = security_schemes["api_key"].pass_through_settings().unwrap();
+    assert_eq!(Some("X-Api-Key"), settings.header_name());
+    assert_eq!(None, settings.query_parameter_name());
+    assert_eq!(None, security_schemes["api_key"].oauth2_settings());
+}
+
+#[test]
+fn error_if_pass_through_settings_empty() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  api_key:
+    type: Pass Through
+    settings: {}";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme. Missing field: queryParameterName or \
+                         headerName at line 6 column 16 (in securitySchemes > api_key)")
+}
+
+#[test]
+fn error_for_unexpected_pass_through_setting() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  api_key:
+    type: Pass Through
+    settings:
+      queryParameterName: key
+      cookieName: key";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing security scheme api_key. Unexpected setting: cookieName at \
+                         line 8 column 7 (in securitySchemes > api_key > cookieName)")
+}
+
+#[test]
+fn loads_security_schemes_in_flow_style() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes: { basic: { type: Basic Authentication },
+                   digest: { type: Digest Authentication } }";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let security_schemes = raml.security_schemes().unwrap();
+    assert_eq!(&SecuritySchemeType::BasicAuthentication,
+               security_schemes["basic"].security_type());
+    assert_eq!(&SecuritySchemeType::DigestAuthentication,
+               security_schemes["digest"].security_type());
+}
+
+#[test]
+fn loads_described_by_in_flow_style_within_block_style() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    describedBy: { queryParameters: { access_token: { type: string } } }
+    settings:
+      accessTokenUri: https://some.api.com/token
+      authorizationGrants: [ client_credentials ]";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let security_schemes = raml.security_schemes().unwrap();
+    let described_by = security_schemes["oauth_2_0"].described_by().unwrap();
+    assert!(described_by.query_parameters().unwrap().contains_key("access_token"));
+}
+
+#[test]
+fn security_scheme_description_keeps_block_scalar_style() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  literal:
+    type: Basic Authentication
+    description: |+
+      Sent with
+      every request
+
+  folded:
+    type: Digest Authentication
+    description: >
+      Sent with
+      every request";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let security_schemes = raml.security_schemes().unwrap();
+    assert_eq!(Some("Sent with\nevery request\n\n"),
+               security_schemes["literal"].description());
+    // The last line of the document has no line break to keep.
+    assert_eq!(Some("Sent with every request"),
+               security_schemes["folded"].description());
+}
+
+#[test]
+fn described_by_responses_with_headers_and_body() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  ErrorBody:
+    properties:
+      message: string
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    describedBy:
+      responses:
+        401: { headers: { WWW-Authenticate: { type: string } },
+               body: { application/json: { type: ErrorBody } } }";
+
+    let raml = assert_ok_and_unwrap(parse(s));
+    let security_schemes = raml.security_schemes().unwrap();
+    let described_by = security_schemes["oauth_2_0"].described_by().unwrap();
+    let unauthorized = &described_by.responses().unwrap()[&401];
+    let header = &unauthorized.headers().unwrap()["WWW-Authenticate"];
+    assert_eq!(Some("string"), header.param_type());
+    let body = &unauthorized.body().unwrap()[0];
+    assert_eq!("application/json", body.media_type());
+    assert_eq!("ErrorBody", body.body_type().unwrap().to_string());
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn builds_security_schemes() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  basic:
+    type: Basic Authentication
+    displayName: Basic
+    description: A user name and password.
+    describedBy:
+      headers:
+        Authorization: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let parsed = raml.security_schemes().unwrap()["basic"].clone();
+    let described_by = parsed.described_by().unwrap().clone();
+    let built = SecuritySchemeBuilder::new(SecuritySchemeType::BasicAuthentication)
+        .display_name("Basic")
+        .description("A user name and password.")
+        .described_by(described_by)
+        .build();
+    assert_eq!(parsed, built);
+    assert_eq!(None, built.location());
+    assert!(parsed.location().is_some());
+}
+
+#[test]
+fn builds_described_by() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  basic:
+    type: Basic Authentication
+    describedBy:
+      headers:
+        Authorization: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let parsed = raml.security_schemes().unwrap()["basic"].described_by().unwrap();
+    let built = SecuritySchemeDescribedBy::new(parsed.headers().cloned(), None, None);
+    assert_eq!(parsed, &built);
+    assert_eq!(None, built.query_parameters());
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "serde")]
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+#[macro_use]
+extern crate serde_json;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+const API: &str = "#%RAML 1.0
+title: Books API
+version: v1
+protocols: [HTTP, HTTPS]
+mediaType: application/json
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    settings:
+      authorizationUri: https://example.com/authorize
+      accessTokenUri: https://example.com/token
+      authorizationGrants: [authorization_code]
+securedBy:
+  - null
+  - oauth_2_0: { scopes: [read] }
+types:
+  Book:
+    type: object
+    properties:
+      title: string
+      authors: string[]
+    example:
+      title: Dune
+      authors: [Frank Herbert]
+annotationTypes:
+  deprecated: boolean
+traits:
+  paged:
+    queryParameters:
+      page:
+        type: integer
+        minimum: 1
+/books:
+  (deprecated): true
+  get:
+    is: [paged]
+    responses:
+      200:
+        body:
+          type: Book[]";
+
+#[test]
+fn serializes_with_raml_node_names() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let json = serde_json::to_value(&raml).unwrap();
+    assert_eq!(json!("Books API"), json["title"]);
+    assert_eq!(json!(["http", "https"]), json["protocols"]);
+    assert_eq!(json!(["application/json"]), json["mediaType"]);
+    let scheme = &json["securitySchemes"]["oauth_2_0"];
+    assert_eq!(json!("OAuth 2.0"), scheme["type"]);
+    assert_eq!(json!(["authorization_code"]), scheme["settings"]["authorizationGrants"]);
+    assert_eq!(json!([null, { "oauth_2_0": { "scopes": ["read"] } }]), json["securedBy"]);
+    let book = &json["types"]["Book"];
+    assert_eq!(json!(["object"]), book["type"]);
+    let authors = &book["properties"]["authors"];
+    assert_eq!(json!(["array"]), authors["type"]);
+    assert_eq!(json!(["string"]), authors["items"]["type"]);
+    assert_eq!(json!({ "title": "Dune", "authors": ["Frank Herbert"] }),
+               book["example"]["value"]);
+    let resource = &json["resources"][0];
+    assert_eq!(json!("/books"), resource["relativeUri"]);
+    assert_eq!(json!({ "value": true }), resource["annotations"]["deprecated"]);
+    assert_eq!(json!([{ "name": "paged", "parameters": null }]),
+               resource["methods"]["get"]["is"]);
+    assert_eq!(json!("integer"),
+               json["traits"]["paged"]["queryParameters"]["page"]["type"]);
+}
+
+#[test]
+fn round_trips_document_through_json() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let json = serde_json::to_value(&raml).unwrap();
+    let deserialized: Raml = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!("Books API", deserialized.title());
+    assert_eq!(Some(&[Protocol::Http, Protocol::Https][..]), deserialized.protocols());
+    let scheme = &deserialized.security_schemes().unwrap()["oauth_2_0"];
+    assert_eq!(&SecuritySchemeType::OAuth2, scheme.security_type());
+    assert_eq!(raml.security_schemes(), deserialized.security_schemes());
+    assert_eq!(raml.secured_by(), deserialized.secured_by());
+    assert_eq!(json, serde_json::to_value(&deserialized).unwrap());
+}
@@ -0,0 +1,258 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+fn parse_applying_traits(s: &str) -> RamlResult {
+    let options = ParseOptions {
+        apply_traits: true,
+        ..ParseOptions::default()
+    };
+    RamlParser::load_from_str_with_options(s, &options)
+}
+
+#[test]
+fn loads_traits() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+    usage: Apply to collection resources
+    description: A paged collection
+    queryParameters:
+      page: integer
+    headers:
+      X-Page-Count: integer
+    responses:
+      200:
+        description: The requested page
+  secured:";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let traits = raml.traits().unwrap();
+    assert_eq!(2, traits.len());
+    let paged = traits.get("paged").unwrap();
+    assert_eq!(Some("Apply to collection resources"), paged.usage());
+    assert_eq!(Some("A paged collection"), paged.description());
+    assert!(paged.query_parameters().unwrap().contains_key("page"));
+    assert!(paged.headers().unwrap().contains_key("X-Page-Count"));
+    assert!(paged.responses().unwrap().contains_key(&200));
+    assert_eq!(None, traits.get("secured").unwrap().usage());
+}
+
+#[test]
+fn loads_flow_style_traits() {
+    let s = "#%RAML 1.0
+title: Some API
+traits: { paged: { queryParameters: { page: integer } } }";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let traits = raml.traits().unwrap();
+    let paged = traits.get("paged").unwrap();
+    assert!(paged.query_parameters().unwrap().contains_key("page"));
+}
+
+#[test]
+fn loads_sequence_of_traits() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  - paged:
+      queryParameters:
+        page: integer
+  - secured:
+      description: Requires a token";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let traits = raml.traits().unwrap();
+    assert_eq!(2, traits.len());
+    assert!(traits.get("paged").unwrap().query_parameters().unwrap().contains_key("page"));
+    assert_eq!(Some("Requires a token"),
+               traits.get("secured").unwrap().description());
+}
+
+#[test]
+fn error_for_unknown_trait_field() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+    unknown: value";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unexpected field found at the trait: unknown at line 5 column 5 (in \
+                         traits > paged > unknown)");
+}
+
+#[test]
+fn loads_trait_references() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+  secured:
+/users:
+  is: [secured]
+  get:
+    is: [paged, { secured: { scope: read } }]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    assert_eq!("secured", resources[0].is().unwrap()[0].name());
+    let is = resources[0].methods().get("get").unwrap().is().unwrap();
+    assert_eq!(2, is.len());
+    assert_eq!("paged", is[0].name());
+    assert_eq!(None, is[0].parameters());
+    assert_eq!("secured", is[1].name());
+    assert_eq!(Some(&"read".to_string()), is[1].parameters().unwrap().get("scope"));
+}
+
+#[test]
+fn applies_traits_to_methods() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+    description: A paged collection
+    queryParameters:
+      page: integer
+      size: integer
+  secured:
+    headers:
+      Authorization: string
+/users:
+  is: [secured]
+  get:
+    is: [paged]
+    queryParameters:
+      size: string";
+    let result = parse_applying_traits(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let get = resources[0].methods().get("get").unwrap();
+    assert_eq!(Some("A paged collection"), get.description());
+    let query_parameters = get.query_parameters().unwrap();
+    assert_eq!(Some("integer"), query_parameters.get("page").unwrap().param_type());
+    assert_eq!(Some("string"), query_parameters.get("size").unwrap().param_type());
+    assert!(get.headers().unwrap().contains_key("Authorization"));
+}
+
+#[test]
+fn traits_are_not_applied_by_default() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+    queryParameters:
+      page: integer
+/users:
+  get:
+    is: [paged]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    assert_eq!(None, resources[0].methods().get("get").unwrap().query_parameters());
+}
+
+#[test]
+fn error_for_undeclared_trait() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+/users:
+  get:
+    is: [paged, secured]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing method. Trait not declared: secured at line 7 column 17");
+}
+
+#[test]
+fn undeclared_trait_error_has_kind_and_name() {
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  get:
+    is: [secured]";
+    let err = assert_error_kind(parse(s), ErrorKind::UndeclaredTrait);
+    assert_eq!(Some("secured"), err.name());
+    assert_eq!(None, err.field());
+}
+
+#[test]
+fn substitutes_trait_parameters() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  searchable:
+    description: <<methodName | !uppercase>> <<resourcePath>> by <<field>>
+    queryParameters:
+      <<field | !singularize>>: string
+/users:
+  get:
+    is: [{ searchable: { field: names } }]";
+    let result = parse_applying_traits(s);
+    let raml = assert_ok_and_unwrap(result);
+    let resources = raml.resources();
+    let get = resources[0].methods().get("get").unwrap();
+    assert_eq!(Some("GET /users by names"), get.description());
+    assert!(get.query_parameters().unwrap().contains_key("name"));
+}
+
+#[test]
+fn error_for_unknown_template_function() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  searchable:
+    description: <<resourcePath | !reverse>>
+/users:
+  get:
+    is: [searchable]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Unknown template parameter function: !reverse at line 5 column 18");
+}
+
+#[test]
+fn error_for_duplicate_trait() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+  paged:";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing trait. Duplicate key: paged at line 5 column 3 (in traits > \
+                         paged)");
+}
+
+#[test]
+fn duplicate_trait_error_has_kind_and_field() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+  paged:";
+    let err = assert_error_kind(parse(s), ErrorKind::DuplicateKey);
+    assert_eq!(Some("paged"), err.field());
+}
+
+#[test]
+fn error_in_trait_has_key_path() {
+    let s = "#%RAML 1.0
+title: Some API
+traits:
+  paged:
+    queryParameters:
+      page:
+        required: sometimes";
+    let err = parse(s).err().unwrap();
+    assert_eq!(vec!["traits", "paged", "queryParameters", "page", "required"],
+               err.key_path());
+}
@@ -0,0 +1,1225 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+#[test]
+fn loads_a_type_with_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Email:
+    type: string
+    description: An email address
+    pattern: \".+@.+\"
+    minLength: 3
+    maxLength: 254";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let email = types.get("Email").unwrap();
+    assert_eq!(Some(&named_type("string")), email.type_());
+    assert_eq!(Some("An email address"), email.description());
+    assert_eq!(Some(".+@.+"), email.pattern());
+    assert_eq!(Some(3), email.min_length());
+    assert_eq!(Some(254), email.max_length());
+}
+
+#[test]
+fn loads_flow_style_types() {
+    let s = "#%RAML 1.0
+title: Some API
+types: { Email: { type: string, pattern: \".+@.+\" }, Age: { type: integer, minimum: 0, \
+             maximum: 150 } }";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    assert_eq!(Some(".+@.+"), types.get("Email").unwrap().pattern());
+    assert_eq!(Some(0.0), types.get("Age").unwrap().minimum());
+    assert_eq!(Some(150.0), types.get("Age").unwrap().maximum());
+}
+
+#[test]
+fn loads_shorthand_types() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Email: string
+  Size:
+    type: string
+    enum: [small, medium, large]
+    default: medium";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    assert_eq!(Some(&named_type("string")), types.get("Email").unwrap().type_());
+    assert_eq!(None, types.get("Email").unwrap().pattern());
+    let size = types.get("Size").unwrap();
+    assert_eq!(&vec!["small".to_string(), "medium".to_string(), "large".to_string()],
+               size.enum_values().unwrap());
+    assert_eq!(Some("medium"), size.default_value());
+}
+
+#[test]
+fn error_for_unknown_type_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Email:
+    type: string
+    colour: blue";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing type Email. Unexpected facet: colour at line 6 column 5 (in \
+                         types > Email > colour)");
+}
+
+#[test]
+fn loads_object_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    type: object
+    properties:
+      name: string
+      age:
+        type: integer
+        minimum: 0
+        required: false";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let properties = types.get("Person").unwrap().properties().unwrap();
+    assert_eq!(2, properties.len());
+    let name = properties.get("name").unwrap();
+    assert!(name.required());
+    assert_eq!(Some(&named_type("string")), name.property_type().type_());
+    let age = properties.get("age").unwrap();
+    assert!(!age.required());
+    assert_eq!(Some(&named_type("integer")), age.property_type().type_());
+    assert_eq!(Some(0.0), age.property_type().minimum());
+}
+
+#[test]
+fn trailing_question_mark_marks_property_optional() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    properties:
+      nickname?: string
+      middleName?:
+        type: string";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let properties = types.get("Person").unwrap().properties().unwrap();
+    assert!(!properties.get("nickname").unwrap().required());
+    assert!(!properties.get("middleName").unwrap().required());
+    assert!(properties.get("nickname?").is_none());
+}
+
+#[test]
+fn explicit_required_keeps_question_mark_in_property_name() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Query:
+    properties: { \"filter?\": { type: string, required: true } }";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let properties = types.get("Query").unwrap().properties().unwrap();
+    assert!(properties.get("filter?").unwrap().required());
+}
+
+#[test]
+fn error_for_unknown_property_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    properties:
+      name:
+        type: string
+        colour: blue";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing type name. Unexpected facet: colour at line 8 column 9 (in \
+                         types > Person > properties > name > colour)");
+}
+
+#[test]
+fn loads_array_shorthand() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  People: Person[]
+  Team:
+    properties:
+      members:
+        type: Person[]
+        minItems: 1";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let people = types.get("People").unwrap();
+    assert_eq!(Some(&named_type("array")), people.type_());
+    assert_eq!(Some(&named_type("Person")), people.items().unwrap().type_());
+    let members = types.get("Team").unwrap().properties().unwrap().get("members").unwrap();
+    assert_eq!(Some(&named_type("array")), members.property_type().type_());
+    let member = members.property_type().items().unwrap();
+    assert_eq!(Some(&named_type("Person")), member.type_());
+    assert_eq!(Some(1), members.property_type().min_items());
+}
+
+#[test]
+fn loads_array_with_items_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Emails:
+    type: array
+    items:
+      type: string
+      pattern: \".+@.+\"
+    minItems: 1
+    maxItems: 10
+    uniqueItems: true";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let emails = types.get("Emails").unwrap();
+    assert_eq!(Some(&named_type("array")), emails.type_());
+    assert_eq!(Some(&named_type("string")), emails.items().unwrap().type_());
+    assert_eq!(Some(".+@.+"), emails.items().unwrap().pattern());
+    assert_eq!(Some(1), emails.min_items());
+    assert_eq!(Some(10), emails.max_items());
+    assert_eq!(Some(true), emails.unique_items());
+}
+
+#[test]
+fn loads_nested_arrays() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Matrix: string[][]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let matrix = types.get("Matrix").unwrap();
+    assert_eq!(Some(&named_type("array")), matrix.type_());
+    let row = matrix.items().unwrap();
+    assert_eq!(Some(&named_type("array")), row.type_());
+    assert_eq!(Some(&named_type("string")), row.items().unwrap().type_());
+    assert_eq!(None, row.items().unwrap().items());
+}
+
+#[test]
+fn error_for_items_on_non_array_type() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Name:
+    type: string
+    items: string";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing type Name. The items facet is only valid for array types at \
+                         line 6 column 5 (in types > Name)");
+}
+
+#[test]
+fn loads_union_type_expressions() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  MaybeName:
+    type: string | nil
+  Pet:
+    properties:
+      kind: Cat|Dog";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let expected = TypeExpression::Union(vec![named_type("string"), named_type("nil")]);
+    assert_eq!(Some(&expected), types.get("MaybeName").unwrap().type_());
+    let kind = types.get("Pet").unwrap().properties().unwrap().get("kind").unwrap();
+    let expected = TypeExpression::Union(vec![named_type("Cat"), named_type("Dog")]);
+    assert_eq!(Some(&expected), kind.property_type().type_());
+}
+
+#[test]
+fn loads_array_of_union_type_expression() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Cat: object
+  Dog: object
+  Pets: ( Cat | Dog )[]
+  Mixed: Cat[] | Dog";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let pets = types.get("Pets").unwrap();
+    assert_eq!(Some(&named_type("array")), pets.type_());
+    let expected = TypeExpression::Union(vec![named_type("Cat"), named_type("Dog")]);
+    assert_eq!(Some(&expected), pets.items().unwrap().type_());
+    let expected =
+        TypeExpression::Union(vec![TypeExpression::Array(Box::new(named_type("Cat"))),
+                                   named_type("Dog")]);
+    assert_eq!(Some(&expected), types.get("Mixed").unwrap().type_());
+}
+
+#[test]
+fn error_for_trailing_union_operator() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Pet:
+    type: Cat |";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Invalid type expression: Cat | at line 5 column 11 (in types > Pet > \
+                         type)");
+}
+
+#[test]
+fn error_for_unbalanced_parentheses() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Pets: (Cat | Dog[]";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Invalid type expression: (Cat | Dog[] at line 4 column 9 (in types > \
+                         Pets)");
+}
+
+#[test]
+fn loads_multiple_parent_types() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: object
+  Employee: object
+  Manager:
+    type: [Person, Employee]";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let manager = types.get("Manager").unwrap();
+    assert_eq!(None, manager.type_());
+    assert_eq!(&[named_type("Person"), named_type("Employee")], manager.parent_types());
+}
+
+#[test]
+fn resolves_inherited_properties_and_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Named:
+    properties:
+      name:
+        type: string
+        maxLength: 50
+  Aged:
+    properties:
+      age: integer
+  Person:
+    type: [Named, Aged]
+    description: A person
+  Employee:
+    type: Person
+    properties:
+      employeeId: string";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let employee = raml.resolve_type("Employee").unwrap();
+    assert_eq!("Employee", employee.name());
+    assert_eq!(&["Person".to_string(), "Named".to_string(), "Aged".to_string()],
+               employee.ancestors());
+    let raml_type = employee.raml_type();
+    assert_eq!(Some(&named_type("object")), raml_type.type_());
+    assert_eq!(Some("A person"), raml_type.description());
+    let properties = raml_type.properties().unwrap();
+    assert_eq!(3, properties.len());
+    assert_eq!(Some(50), properties.get("name").unwrap().property_type().max_length());
+    assert!(properties.contains_key("age"));
+    assert!(properties.contains_key("employeeId"));
+}
+
+#[test]
+fn resolves_restricted_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Name:
+    type: string
+    minLength: 1
+    maxLength: 50
+  ShortName:
+    type: Name
+    maxLength: 10";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let short_name = raml.resolve_type("ShortName").unwrap();
+    let raml_type = short_name.raml_type();
+    assert_eq!(Some(&named_type("string")), raml_type.type_());
+    assert_eq!(Some(1), raml_type.min_length());
+    assert_eq!(Some(10), raml_type.max_length());
+}
+
+#[test]
+fn error_for_widened_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Name:
+    type: string
+    maxLength: 50
+  LongName:
+    type: Name
+    maxLength: 100";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let error = raml.resolve_type("LongName").err().unwrap();
+    assert_eq!("Error resolving type LongName. The maxLength facet widens the inherited value",
+               error.error());
+}
+
+#[test]
+fn error_for_circular_inheritance() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  A:
+    type: B
+  B:
+    type: C
+  C:
+    type: A";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error resolving type A. Circular inheritance: A -> B -> C -> A at line \
+                         9 column 5");
+}
+
+#[test]
+fn error_for_undeclared_parent_type() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Employee:
+    type: Person
+    properties:
+      employeeId: string";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error resolving type Employee. Type not declared: Person at line 5 \
+                         column 5");
+}
+
+#[test]
+fn undeclared_parent_type_error_has_kind_and_name() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Employee:
+    type: Person";
+    let error = assert_error_kind(parse(s), ErrorKind::UndeclaredType);
+    assert_eq!(Some("Person"), error.name());
+}
+
+#[test]
+fn loads_block_sequence_enum() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Size:
+    enum:
+      - small
+      - medium
+  Colour:
+    enum:
+    - red
+    - blue
+    description: A colour";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    assert_eq!(&vec!["small".to_string(), "medium".to_string()],
+               types.get("Size").unwrap().enum_values().unwrap());
+    let colour = types.get("Colour").unwrap();
+    assert_eq!(&vec!["red".to_string(), "blue".to_string()],
+               colour.enum_values().unwrap());
+    assert_eq!(Some("A colour"), colour.description());
+}
+
+#[test]
+fn error_for_default_not_in_enum() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Size:
+    enum: [small, medium, large]
+    default: huge";
+    let result = parse(s);
+    assert_error_result(result,
+                        "The default value is not one of the enum values: huge at line 6 column 5 \
+                         (in types > Size)");
+}
+
+#[test]
+fn error_for_empty_enum() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Size:
+    enum: []";
+    let result = parse(s);
+    assert_error_result(result,
+                        "The enum facet must have at least one value at line 5 column 5 (in types \
+                         > Size > enum)");
+}
+
+#[test]
+fn loads_numeric_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Price:
+    type: number
+    minimum: 0.5
+    maximum: 99.99
+    multipleOf: 0.01";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let price = types.get("Price").unwrap();
+    assert_eq!(Some(0.5), price.minimum());
+    assert_eq!(Some(99.99), price.maximum());
+    assert_eq!(Some(0.01), price.multiple_of());
+}
+
+#[test]
+fn error_for_invalid_numeric_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Name:
+    type: string
+    maxLength: ten";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing maxLength. Expected an integer, Found ten at line 6 \
+                         column 16 (in types > Name > maxLength)");
+}
+
+#[test]
+fn error_for_minimum_greater_than_maximum() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Age:
+    type: integer
+    minimum: 10
+    maximum: 5";
+    let result = parse(s);
+    assert_error_result(result,
+                        "The minimum facet must not be greater than the maximum facet at line 7 \
+                         column 14 (in types > Age)");
+}
+
+#[test]
+fn error_for_facet_range_is_at_the_facet_given_last() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Tags:
+    type: array
+    maxItems: 1
+    minItems: 3";
+    let error = assert_error_kind(parse(s), ErrorKind::InvalidFacetRange);
+    assert_eq!(Some(7), error.line());
+    assert_eq!(Some(15), error.column());
+}
+
+#[test]
+fn loads_type_and_property_examples() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    properties:
+      name:
+        type: string
+        example: Dan
+    example:
+      name: Dan";
+    let result = parse(s);
+    let raml = assert_ok_and_unwrap(result);
+    let types = raml.types().unwrap();
+    let person = types.get("Person").unwrap();
+    let expected =
+        ExampleValue::Mapping(vec![("name".to_string(), ExampleValue::String("Dan".to_string()))]);
+    assert_eq!(&expected, person.example().unwrap().value());
+    let name = person.properties().unwrap().get("name").unwrap();
+    assert_eq!(&ExampleValue::String("Dan".to_string()),
+               name.property_type().example().unwrap().value());
+}
+
+#[test]
+fn error_for_duplicate_type() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: object
+  Person: string";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing type. Duplicate key: Person at line 5 column 3 (in types > \
+                         Person)");
+}
+
+#[test]
+fn display_name_of_types_falls_back_to_the_key() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    displayName: A person
+    properties:
+      name:
+        displayName: Full name
+        type: string
+      age: integer
+  Employee:
+    type: Person";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let types = raml.types().unwrap();
+    let person = types.get("Person").unwrap();
+    assert_eq!(Some("A person"), person.display_name());
+    assert_eq!("A person", person.display_name_or_key("Person"));
+    let properties = person.properties().unwrap();
+    assert_eq!("Full name", properties["name"].property_type().display_name_or_key("name"));
+    assert_eq!("age", properties["age"].property_type().display_name_or_key("age"));
+    // The display name isn't inherited.
+    let employee = raml.resolve_type("Employee").unwrap();
+    assert_eq!(None, employee.raml_type().display_name());
+    assert_eq!("Employee", employee.raml_type().display_name_or_key(employee.name()));
+}
+
+#[test]
+fn loads_custom_facets() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  CustomDate:
+    type: date-only
+    facets:
+      noHolidays: boolean
+      region?: string
+  WorkingDay:
+    type: CustomDate
+    noHolidays: true";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let types = raml.types().unwrap();
+    let facets = types["CustomDate"].facets().unwrap();
+    assert!(facets["noHolidays"].required());
+    assert_eq!(Some(&TypeExpression::Named("boolean".to_string())),
+               facets["noHolidays"].property_type().type_());
+    assert!(!facets["region"].required());
+    let values = types["WorkingDay"].facet_values().unwrap();
+    assert_eq!(&ExampleValue::Boolean(true), values["noHolidays"].value());
+    let resolved = raml.resolve_type("WorkingDay").unwrap();
+    assert!(resolved.raml_type().facet_values().unwrap().contains_key("noHolidays"));
+}
+
+#[test]
+fn required_custom_facets_may_be_given_by_intermediate_types() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  CustomDate:
+    type: date-only
+    facets:
+      noHolidays: boolean
+  WorkingDay:
+    type: CustomDate
+  Monday:
+    type: WorkingDay
+    noHolidays: true";
+    assert_ok_and_unwrap(parse(s));
+}
+
+#[test]
+fn error_for_unset_required_custom_facet() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  CustomDate:
+    type: date-only
+    facets:
+      noHolidays: boolean
+  WorkingDay:
+    type: CustomDate";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing type WorkingDay. Missing value for the inherited facet: \
+                         noHolidays at line 9 column 5");
+}
+
+#[test]
+fn error_for_custom_facet_not_declared_by_parent() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  CustomDate:
+    type: date-only
+    facets:
+      noHolidays?: boolean
+  WorkingDay:
+    type: CustomDate
+    noWeekends: true";
+    let result = parse(s);
+    assert_error_result(result,
+                        "Error parsing type WorkingDay. Unexpected facet: noWeekends at line 10 \
+                         column 5");
+}
+
+#[test]
+fn error_for_facet_given_before_a_built_in_type() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Email:
+    colour: blue
+    type: string";
+    let err = assert_error_kind(parse(s), ErrorKind::UnexpectedTypeFacet);
+    assert_eq!(Some(5), err.line());
+}
+
+#[test]
+fn loads_a_type_declared_with_a_json_schema() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person: |
+    {
+      \"type\": \"object\",
+      \"properties\": { \"name\": { \"type\": \"string\" } }
+    }
+/people:
+  get:
+    responses:
+      200:
+        body:
+          application/json:
+            type: Person";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let person = &raml.types().unwrap()["Person"];
+    let schema = person.schema().unwrap();
+    assert_eq!(SchemaKind::Json, schema.kind());
+    assert!(schema.raw().starts_with("{\n  \"type\": \"object\""));
+    assert!(person.parent_types().is_empty());
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn loads_the_xml_facets_of_a_property() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    xml:
+      name: person
+      namespace: http://example.com/people
+      prefix: p
+    properties:
+      id:
+        type: string
+        xml: { attribute: true, name: id }
+      tags:
+        type: string[]
+        xml:
+          wrapped: false";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let person = &raml.types().unwrap()["Person"];
+    let xml = person.xml().unwrap();
+    assert_eq!((Some("person"), Some("http://example.com/people"), Some("p")),
+               (xml.name(), xml.namespace(), xml.prefix()));
+    let properties = person.properties().unwrap();
+    let id = properties["id"].xml().unwrap();
+    assert_eq!(Some(true), id.attribute());
+    assert_eq!(Some("id"), id.name());
+    assert_eq!(Some(false), properties["tags"].xml().unwrap().wrapped());
+}
+
+#[test]
+fn error_for_a_non_boolean_xml_attribute() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Person:
+    properties:
+      id:
+        xml:
+          attribute: maybe";
+    assert_error_result(parse(s),
+                        "Error parsing attribute. Expected true or false, Found maybe at line 8 \
+                         column 22 (in types > Person > properties > id > xml > attribute)");
+}
+
+#[test]
+fn finds_subtypes_by_their_discriminator_value() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Pet:
+    discriminator: kind
+    properties:
+      kind: string
+      name: string
+  Cat:
+    type: Pet
+    discriminatorValue: cat
+  Dog:
+    type: Pet
+  Puppy:
+    type: Dog
+    discriminatorValue: puppy";
+    let raml = assert_ok_and_unwrap(parse(s));
+    assert_eq!(Some("kind"), raml.types().unwrap()["Pet"].discriminator());
+    assert_eq!(Some("kind"), raml.resolve_type("Cat").unwrap().raml_type().discriminator());
+    let name = |value| raml.find_subtype_by_discriminator("Pet", value).map(|(name, _)| name);
+    assert_eq!(Some("Cat"), name("cat"));
+    // Without a discriminatorValue the name of the type is used.
+    assert_eq!(Some("Dog"), name("Dog"));
+    assert_eq!(Some("Puppy"), name("puppy"));
+    assert_eq!(None, name("Cat"));
+    assert_eq!(None, raml.find_subtype_by_discriminator("Dog", "cat"));
+}
+
+#[test]
+fn error_for_a_discriminator_that_is_not_a_required_string_property() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Pet:
+    discriminator: kind
+    properties:
+      kind?: string
+  Shape:
+    discriminator: sides
+    properties:
+      sides: integer";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let error = raml.resolve_type("Pet").unwrap_err();
+    assert_eq!(ErrorKind::InvalidDiscriminator, error.kind());
+    assert_eq!("Error resolving type Pet. The discriminator kind is not a required string \
+                property",
+               error.error());
+    let error = raml.resolve_type("Shape").unwrap_err();
+    assert_eq!(ErrorKind::InvalidDiscriminator, error.kind());
+}
+
+#[test]
+fn loads_the_date_and_file_built_in_types() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Birthday: date-only
+  Alarm: time-only
+  Meeting: datetime-only
+  Created:
+    type: datetime
+    example: 2016-02-28T16:41:41.090Z
+  Modified:
+    type: datetime
+    format: rfc2616
+    example: Sun, 28 Feb 2016 16:41:41 GMT
+  Avatar:
+    type: file
+    fileTypes: [image/png, image/jpeg]
+    minLength: 1
+    maxLength: 307200";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let types = raml.types().unwrap();
+    assert_eq!(Some(BuiltInType::DateOnly), types["Birthday"].built_in_type());
+    assert_eq!(Some(BuiltInType::TimeOnly), types["Alarm"].built_in_type());
+    assert_eq!(Some(BuiltInType::DatetimeOnly), types["Meeting"].built_in_type());
+    assert_eq!(Some(BuiltInType::Datetime), types["Created"].built_in_type());
+    assert_eq!(None, types["Created"].format());
+    assert_eq!(Some(DatetimeFormat::Rfc2616), types["Modified"].format());
+    let avatar = &types["Avatar"];
+    assert_eq!(Some(BuiltInType::File), avatar.built_in_type());
+    assert_eq!(Some(&["image/png".to_string(), "image/jpeg".to_string()][..]),
+               avatar.file_types());
+    assert_eq!((Some(1), Some(307200)), (avatar.min_length(), avatar.max_length()));
+    assert_eq!(Some("datetime-only"), BuiltInType::from_name("datetime-only").map(|t| t.name()));
+    assert_eq!(None, BuiltInType::from_name("Person"));
+}
+
+#[test]
+fn error_for_a_format_on_a_type_other_than_datetime() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Birthday:
+    format: rfc3339
+    type: date-only";
+    assert_error_result(parse(s),
+                        "Error parsing type Birthday. Unexpected facet: format at line 5 column 5 \
+                         (in types > Birthday)");
+}
+
+#[test]
+fn error_for_a_datetime_example_that_is_not_rfc3339() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Created:
+    type: datetime
+    example: 2016-02-28 16:41";
+    let error = assert_error_kind(parse(s), ErrorKind::InvalidDatetimeExample);
+    assert_eq!(Some(6), error.line());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn loads_pattern_properties_and_additional_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    additionalProperties: false
+    properties:
+      id: string
+      /^note_\\d+$/: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let order = &raml.types().unwrap()["Order"];
+    assert!(!order.additional_properties());
+    let properties: Vec<&String> = order.properties().unwrap().keys().collect();
+    assert_eq!(vec!["id"], properties);
+    let note = &order.pattern_properties().unwrap()["^note_\\d+$"];
+    assert!(!note.required());
+    assert!(order.allows_property("id"));
+    assert!(order.allows_property("note_12"));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn allows_only_properties_that_are_declared_or_match_a_pattern() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    additionalProperties: false
+    properties:
+      id: string
+      /^note_\\d+$/: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let order = &raml.types().unwrap()["Order"];
+    assert!(!order.allows_property("note_x"));
+    assert!(!order.allows_property("total"));
+}
+
+#[test]
+fn allows_additional_properties_by_default() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    properties:
+      id: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let order = &raml.types().unwrap()["Order"];
+    assert!(order.additional_properties());
+    assert!(order.allows_property("total"));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn error_for_a_pattern_property_that_is_not_a_regular_expression() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    properties:
+      /note_(\\d+/: string";
+    assert_error_result(parse(s),
+                        "Error parsing type Order. Invalid regular expression for a property: \
+                         note_(\\d+ at line 6 column 7 (in types > Order > properties > \
+                         /note_(\\d+/)");
+}
+
+#[cfg(not(feature = "regex"))]
+#[test]
+fn error_for_a_pattern_property_without_the_regex_feature() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    properties:
+      /^note_\\d+$/: string";
+    let error = assert_error_kind(parse(s), ErrorKind::UnsupportedPropertyPattern);
+    assert_eq!((Some(6), Some(7)), (error.line(), error.column()));
+}
+
+#[test]
+fn loads_min_and_max_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Labels:
+    type: object
+    minProperties: 1
+    maxProperties: 3
+    example:
+      colour: red
+      size: large";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let labels = &raml.types().unwrap()["Labels"];
+    assert_eq!((Some(1), Some(3)), (labels.min_properties(), labels.max_properties()));
+}
+
+#[test]
+fn error_for_example_with_undeclared_property_of_closed_type() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Note:
+    additionalProperties: false
+    properties:
+      a: string
+    example:
+      a: x
+      zzz: y";
+    assert_error_result(parse(s),
+                        "Error parsing type Note. An example has a property that is not \
+                         declared: zzz at line 8 column 5 (in types > Note)");
+}
+
+#[test]
+fn example_may_have_undeclared_properties_of_open_type() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Note:
+    properties:
+      a: string
+    example:
+      a: x
+      zzz: y";
+    assert_ok_and_unwrap(parse(s));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn example_of_closed_type_may_have_properties_matching_a_pattern() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    additionalProperties: false
+    properties:
+      id: string
+      /^note_\\d+$/: string
+    examples:
+      valid:
+        id: A1
+        note_1: Fragile
+      invalid:
+        id: A2
+        note_x: Fragile";
+    let error = assert_error_kind(parse(s), ErrorKind::UndeclaredExampleProperty);
+    assert_eq!(Some("note_x"), error.field());
+}
+
+#[test]
+fn error_for_a_negative_max_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Labels:
+    type: object
+    maxProperties: -1";
+    assert_error_result(parse(s),
+                        "Unexpected entry found. Expected a non-negative integer, Found -1 at \
+                         line 6 column 20 (in types > Labels > maxProperties)");
+}
+
+#[test]
+fn error_for_min_properties_greater_than_max_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Labels:
+    type: object
+    minProperties: 4
+    maxProperties: 2";
+    assert_error_result(parse(s),
+                        "The minProperties facet must not be greater than the maxProperties \
+                         facet at line 7 column 20 (in types > Labels)");
+}
+
+#[test]
+fn error_for_an_example_with_too_many_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Labels:
+    type: object
+    maxProperties: 1
+    example:
+      colour: red
+      size: large";
+    let error = assert_error_kind(parse(s), ErrorKind::ExamplePropertyCount);
+    assert_eq!(Some(7), error.line());
+}
+
+#[test]
+fn resolves_a_type_that_refers_to_itself_through_its_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  TreeNode:
+    properties:
+      value: string
+      parent?: TreeNode
+      children: TreeNode[]";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let resolved = raml.resolve_type("TreeNode").unwrap();
+    let properties = resolved.raml_type().properties().unwrap();
+    let children = properties["children"].property_type();
+    assert_eq!(Some(&named_type("TreeNode")), children.items().unwrap().type_());
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn error_for_types_that_are_aliases_of_each_other() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  A: B
+  B: A
+  C:
+    type: A";
+    let error = assert_error_kind(parse(s), ErrorKind::CircularTypeInheritance);
+    assert_eq!((Some(5), Some(6)), (error.line(), error.column()));
+    let raml = RamlBuilder::new()
+        .title("Some API")
+        .type_declaration("A", inheriting_type("B"))
+        .type_declaration("B", inheriting_type("A"))
+        .type_declaration("C", inheriting_type("A"))
+        .build()
+        .unwrap();
+    let error = raml.resolve_type("B").unwrap_err();
+    assert_eq!(ErrorKind::CircularTypeInheritance, error.kind());
+    assert_eq!("Error resolving type B. Circular inheritance: B -> A -> B", error.error());
+    let violations = raml.validate();
+    let messages: Vec<&str> = violations.iter().map(|v| v.message()).collect();
+    assert_eq!(vec!["Error resolving type A. Circular inheritance: A -> B -> A",
+                    "Error resolving type B. Circular inheritance: B -> A -> B"],
+               messages);
+}
+
+#[test]
+fn orders_types_after_the_types_they_refer_to() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Order:
+    properties:
+      customer: Customer
+      lines: OrderLine[]
+  OrderLine:
+    properties:
+      product: Product
+      quantity: integer
+  Customer:
+    type: Person
+  Person:
+    properties:
+      name: string
+  Product: string
+  TreeNode:
+    properties:
+      children: TreeNode[]
+      order?: Order";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let names: Vec<&str> =
+        raml.types_in_dependency_order().unwrap().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(vec!["Person", "Customer", "Product", "OrderLine", "Order", "TreeNode"], names);
+    let names: Vec<&str> =
+        raml.type_closure("OrderLine").unwrap().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(vec!["Product", "OrderLine"], names);
+    assert_eq!(ErrorKind::UndeclaredType, raml.type_closure("Invoice").unwrap_err().kind());
+}
+
+#[test]
+fn orders_types_that_refer_to_each_other_through_properties() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  Employee:
+    type: Person
+    properties:
+      manager: Manager
+  Manager:
+    type: Employee
+    properties:
+      reports: Employee[]
+  Person:
+    properties:
+      name: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let names: Vec<&str> =
+        raml.types_in_dependency_order().unwrap().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(vec!["Person", "Employee", "Manager"], names);
+}
+
+#[test]
+fn error_ordering_types_that_inherit_from_each_other() {
+    let s = "#%RAML 1.0
+title: Some API
+types:
+  A: object
+  D:
+    properties:
+      a: A";
+    let d = assert_ok_and_unwrap(parse(s)).types().unwrap()["D"].clone();
+    let raml = RamlBuilder::new()
+        .title("Some API")
+        .type_declaration("A", inheriting_type("B"))
+        .type_declaration("B", inheriting_type("C"))
+        .type_declaration("C", inheriting_type("A"))
+        .type_declaration("D", d)
+        .build()
+        .unwrap();
+    let error = raml.types_in_dependency_order().unwrap_err();
+    assert_eq!(ErrorKind::CircularTypeDependency, error.kind());
+    assert_eq!("Error ordering types. These types inherit from each other: A, B, C",
+               error.error());
+}
+
+// Built documents aren't checked like loaded ones, so they may declare types that inherit from
+// each other.
+fn inheriting_type(parent: &str) -> RamlType {
+    let s = format!("#%RAML 1.0
+title: Some API
+types:
+  {}: object
+  Child:
+    type: {}",
+                    parent,
+                    parent);
+    let raml = assert_ok_and_unwrap(parse(&s));
+    raml.types().unwrap()["Child"].clone()
+}
@@ -0,0 +1,234 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+mod common;
+
+use common::*;
+use raml_parser::*;
+
+fn messages(violations: &[RamlViolation]) -> Vec<(ValidationRule, &str)> {
+    violations.iter().map(|v| (v.rule(), v.message())).collect()
+}
+
+#[test]
+fn no_violations_for_a_conforming_document() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+version: v1
+baseUri: https://{region}.example.com/{version}
+baseUriParameters:
+  region:
+    enum: [eu, us]
+mediaType: application/json; charset=utf-8
+documentation:
+  - title: Home
+    content: Welcome.
+types:
+  Book:
+    properties:
+      title: string
+      related: Book[] | nil
+/books/{isbn}{mediaTypeExtension}:
+  uriParameters:
+    isbn: string
+  get:
+    responses:
+      200:
+        body:
+          type: Book"));
+    assert!(raml.validate().is_empty());
+}
+
+#[test]
+fn violation_for_an_undeclared_security_scheme() {
+    let raml = RamlBuilder::new()
+        .title("Books API")
+        .secured_by(SecuredBy::Null)
+        .secured_by(SecuredBy::Scheme("oauth_2_0".to_string()))
+        .build()
+        .unwrap();
+    let violations = raml.validate();
+    assert_eq!(vec![(ValidationRule::SecuredByResolves,
+                     "Error parsing document root. Security scheme not declared: oauth_2_0")],
+               messages(&violations));
+    assert_eq!(Severity::Error, violations[0].severity());
+}
+
+#[test]
+fn warning_for_undeclared_uri_parameters() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+baseUri: https://{region}.example.com
+/books:
+  /{isbn}:
+    /{page}:
+      uriParameters:
+        isbn: string"));
+    let mut options = ValidationOptions::default();
+    options.enabled_rules.insert(ValidationRule::UriParametersDeclared);
+    let violations = raml.validate_with_options(&options);
+    assert_eq!(vec![(ValidationRule::UriParametersDeclared,
+                     "Error parsing document root. Base uri parameter not declared: region"),
+                    (ValidationRule::UriParametersDeclared,
+                     "Error parsing resource. Uri parameter not declared: isbn"),
+                    (ValidationRule::UriParametersDeclared,
+                     "Error parsing resource. Uri parameter not declared: page")],
+               messages(&violations));
+    assert_eq!(Severity::Warning, violations[0].severity());
+    assert_eq!(None, violations[0].location());
+    assert_eq!(5, violations[1].location().unwrap().line);
+}
+
+#[test]
+fn undeclared_uri_parameters_are_valid_by_default() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Users API
+baseUri: https://{region}.example.com
+/users/{id}:
+  get:"));
+    assert!(raml.validate().is_empty());
+    assert!(!ValidationRule::UriParametersDeclared.is_enabled_by_default());
+}
+
+#[test]
+fn violation_for_an_invalid_media_type() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+/books:
+  post:
+    body:
+      application/x y:
+    responses:
+      201:
+        body:
+          text/:"));
+    let violations = raml.validate();
+    assert_eq!(vec![(ValidationRule::MediaTypeFormat,
+                     "Invalid media type, expected type/subtype: application/x y"),
+                    (ValidationRule::MediaTypeFormat,
+                     "Invalid media type, expected type/subtype: text/")],
+               messages(&violations));
+    assert_eq!(4, violations[1].location().unwrap().line);
+}
+
+#[test]
+fn violation_for_a_base_uri_version_without_a_version() {
+    let raml = RamlBuilder::new()
+        .title("Books API")
+        .base_uri("https://api.example.com/{version}")
+        .build()
+        .unwrap();
+    assert_eq!(vec![(ValidationRule::BaseUriVersion,
+                     "Error parsing document root. The baseUri refers to {version}, but no \
+                      version is declared")],
+               messages(&raml.validate()));
+}
+
+#[test]
+fn violation_for_a_blank_documentation_title() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+documentation:
+  - title: Home
+    content: Welcome.
+  - title: ' '
+    content: Nothing."));
+    let violations = raml.validate();
+    assert_eq!(vec![(ValidationRule::DocumentationTitle,
+                     "Error parsing documentation. Field must not be empty: title")],
+               messages(&violations));
+    assert_eq!(6, violations[0].location().unwrap().line);
+}
+
+#[test]
+fn violation_for_undeclared_types() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+types:
+  Book:
+    properties:
+      author: Author
+      tags: Tag[]
+/books:
+  get:
+    responses:
+      200:
+        body:
+          application/json: Book | Magazine"));
+    let violations = raml.validate();
+    assert_eq!(vec![(ValidationRule::TypeReferencesResolve,
+                     "Error resolving type Book. Type not declared: Author"),
+                    (ValidationRule::TypeReferencesResolve,
+                     "Error resolving type Book. Type not declared: Tag"),
+                    (ValidationRule::TypeReferencesResolve,
+                     "Error resolving type Book | Magazine. Type not declared: Magazine")],
+               messages(&violations));
+    assert_eq!(6, violations[0].location().unwrap().line);
+    assert_eq!(9, violations[2].location().unwrap().line);
+}
+
+#[test]
+fn violation_for_undeclared_types_in_security_scheme_responses() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+securitySchemes:
+  oauth_2_0:
+    type: OAuth 2.0
+    describedBy:
+      responses:
+        401:
+          body:
+            application/json: ErrorBody"));
+    let violations = raml.validate();
+    assert_eq!(vec![(ValidationRule::TypeReferencesResolve,
+                     "Error resolving type ErrorBody. Type not declared: ErrorBody")],
+               messages(&violations));
+    assert_eq!(4, violations[0].location().unwrap().line);
+}
+
+#[test]
+fn skips_disabled_rules() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+/books/{isbn}:
+  post:
+    body:
+      text/:"));
+    assert_eq!(1, raml.validate().len());
+    let mut options = ValidationOptions::default();
+    options.enabled_rules.insert(ValidationRule::UriParametersDeclared);
+    assert_eq!(2, raml.validate_with_options(&options).len());
+    options.disabled_rules.insert(ValidationRule::UriParametersDeclared);
+    assert_eq!(vec![(ValidationRule::MediaTypeFormat,
+                     "Invalid media type, expected type/subtype: text/")],
+               messages(&raml.validate_with_options(&options)));
+}
+
+#[test]
+fn identifies_the_rules() {
+    assert_eq!("secured-by-resolves", ValidationRule::SecuredByResolves.id());
+    assert_eq!("type-references-resolve", ValidationRule::TypeReferencesResolve.id());
+}
+
+#[test]
+fn violation_for_a_type_that_inherits_a_schema() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0
+title: Books API
+types:
+  PersonSchema: |
+    { \"type\": \"object\" }
+  Employee:
+    type: PersonSchema
+  Team:
+    properties:
+      members: PersonSchema[]"));
+    let violations = raml.validate();
+    assert_eq!(vec![(ValidationRule::TypeReferencesResolve,
+                     "Error resolving type Employee. Type PersonSchema is declared with a \
+                      schema, so it can only be the type of a body"),
+                    (ValidationRule::TypeReferencesResolve,
+                     "Error resolving type Team. Type PersonSchema is declared with a schema, \
+                      so it can only be the type of a body")],
+               messages(&violations));
+}
@@ -0,0 +1,172 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+use raml_parser::*;
+
+mod common;
+use common::*;
+
+const API: &str = "#%RAML 1.0
+title: Some API
+documentation:
+  - title: Home
+    content: Welcome
+securitySchemes:
+  oauth:
+    type: OAuth 2.0
+  basic:
+    type: Basic Authentication
+types:
+  User:
+    type: object
+  Address:
+    type: object
+/users:
+  post:
+    body:
+      application/json:
+        type: User
+  get:
+    responses:
+      404:
+        description: Not found
+      200:
+        body:
+          application/json:
+            type: User[]
+  /{id}:
+    get:
+      description: A user
+/orders:
+  delete:
+    description: Every order";
+
+// Records the path of every node it visits, with the kind of node.
+struct Recorder {
+    visited: Vec<String>,
+}
+
+impl RamlVisitor for Recorder {
+    fn visit_root(&mut self, _raml: &Raml, context: &VisitContext) {
+        self.record("root", context);
+    }
+
+    fn visit_documentation(&mut self,
+                           _documentation: &RamlDocumentation,
+                           context: &VisitContext) {
+        self.record("documentation", context);
+    }
+
+    fn visit_security_scheme(&mut self,
+                             _name: &str,
+                             _security_scheme: &SecurityScheme,
+                             context: &VisitContext) {
+        self.record("security scheme", context);
+    }
+
+    fn visit_type(&mut self, _name: &str, _raml_type: &RamlType, context: &VisitContext) {
+        self.record("type", context);
+    }
+
+    fn visit_resource(&mut self, _resource: &Resource, context: &VisitContext) {
+        self.record("resource", context);
+    }
+
+    fn visit_method(&mut self, _verb: &str, _method: &Method, context: &VisitContext) {
+        self.record("method", context);
+    }
+
+    fn visit_response(&mut self, _status: u16, _response: &Response, context: &VisitContext) {
+        self.record("response", context);
+    }
+
+    fn visit_body(&mut self, _body: &Body, context: &VisitContext) {
+        self.record("body", context);
+    }
+}
+
+impl Recorder {
+    fn record(&mut self, kind: &str, context: &VisitContext) {
+        self.visited.push(format!("{}: {}", kind, context.path()));
+    }
+}
+
+#[test]
+fn visits_the_nodes_depth_first_in_a_fixed_order() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let mut recorder = Recorder { visited: vec![] };
+    raml.accept(&mut recorder);
+    assert_eq!(vec!["root: ",
+                    "documentation: Home",
+                    "security scheme: basic",
+                    "security scheme: oauth",
+                    "type: Address",
+                    "type: User",
+                    "resource: /users",
+                    "method: get /users",
+                    "response: get /users 200",
+                    "body: get /users 200 application/json",
+                    "response: get /users 404",
+                    "method: post /users",
+                    "body: post /users application/json",
+                    "resource: /users/{id}",
+                    "method: get /users/{id}",
+                    "resource: /orders",
+                    "method: delete /orders"],
+               recorder.visited);
+}
+
+#[test]
+fn endpoint_counter_counts_resources_methods_and_responses() {
+    let raml = assert_ok_and_unwrap(parse(API));
+    let mut counter = EndpointCounter::new();
+    raml.accept(&mut counter);
+    assert_eq!(3, counter.resources());
+    assert_eq!(4, counter.methods());
+    assert_eq!(2, counter.responses());
+}
+
+#[test]
+fn visit_context_has_the_location_of_the_node() {
+    struct Locations {
+        resources: Vec<Option<SourceLocation>>,
+        bodies: Vec<Option<SourceLocation>>,
+    }
+
+    impl RamlVisitor for Locations {
+        fn visit_resource(&mut self, _resource: &Resource, context: &VisitContext) {
+            self.resources.push(context.location());
+        }
+
+        fn visit_body(&mut self, _body: &Body, context: &VisitContext) {
+            self.bodies.push(context.location());
+        }
+    }
+
+    let s = "#%RAML 1.0
+title: Some API
+/users:
+  post:
+    body:
+      application/json:
+        type: string";
+    let raml = assert_ok_and_unwrap(parse(s));
+    let mut locations = Locations {
+        resources: vec![],
+        bodies: vec![],
+    };
+    raml.accept(&mut locations);
+    let method_location = raml.resources()[0].methods()["post"].location();
+    assert_eq!(3, locations.resources[0].unwrap().line);
+    assert_eq!(vec![method_location], locations.bodies);
+}
+
+#[test]
+fn visits_nothing_but_the_root_of_an_empty_document() {
+    let raml = assert_ok_and_unwrap(parse("#%RAML 1.0\ntitle: Some API"));
+    let mut counter = EndpointCounter::new();
+    raml.accept(&mut counter);
+    assert_eq!(0, counter.resources());
+    assert_eq!(0, counter.methods());
+}
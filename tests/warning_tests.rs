@@ -0,0 +1,124 @@
+#![cfg_attr(test, allow(dead_code))]
+
+extern crate raml_parser;
+
+use raml_parser::*;
+
+fn load_warnings(s: &str) -> Vec<RamlWarning> {
+    let result = RamlParser::load_from_str_with_diagnostics(s);
+    if result.is_err() {
+        println!("Unexpected error {:?}", result);
+    }
+    result.ok().unwrap().1
+}
+
+#[test]
+fn no_warnings_for_document_without_issues() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: https://api.example.com
+protocols: [ HTTPS ]
+securitySchemes:
+  basic:
+    type: Basic Authentication
+securedBy: [ basic ]
+/users:
+  description: All users";
+    assert!(load_warnings(s).is_empty());
+}
+
+#[test]
+fn warns_about_unused_security_scheme() {
+    let s = "#%RAML 1.0
+title: Some API
+securitySchemes:
+  basic:
+    type: Basic Authentication
+  digest:
+    type: Digest Authentication
+/users:
+  get:
+    securedBy: [ basic ]";
+    let warnings = load_warnings(s);
+    assert_eq!(1, warnings.len());
+    assert_eq!(WarningKind::UnusedSecurityScheme, warnings[0].kind());
+    assert_eq!("Security scheme digest is never used", warnings[0].message());
+}
+
+#[test]
+fn warns_about_base_uri_protocol_not_in_protocols() {
+    let s = "#%RAML 1.0
+title: Some API
+baseUri: http://api.example.com
+protocols: [ HTTPS ]";
+    let warnings = load_warnings(s);
+    assert_eq!(1, warnings.len());
+    assert_eq!(WarningKind::ProtocolMismatch, warnings[0].kind());
+    assert_eq!("The baseUri http://api.example.com uses a protocol that is not in protocols",
+               warnings[0].message());
+}
+
+#[test]
+fn warns_about_empty_descriptions() {
+    let s = "#%RAML 1.0
+title: Some API
+description: \"\"
+/users:
+  /{id}:
+    get:
+      description: \" \"";
+    let warnings = load_warnings(s);
+    let messages: Vec<&str> = warnings.iter().map(|w| w.message()).collect();
+    assert_eq!(vec!["The description of the API is empty",
+                    "The description of method get /users/{id} is empty"],
+               messages);
+    assert!(warnings.iter().all(|w| w.kind() == WarningKind::EmptyDescription));
+}
+
+#[test]
+fn errors_are_not_warnings() {
+    let s = "#%RAML 1.0
+title: Some API
+protocols: [ FTP ]";
+    let result = RamlParser::load_from_str_with_diagnostics(s);
+    assert_eq!(ErrorKind::UnexpectedProtocol, result.err().unwrap().kind());
+}
+
+#[test]
+fn warns_about_skipped_unknown_keys_with_their_positions() {
+    let s = "#%RAML 1.0
+title: Some API
+x-owner: team
+traits:
+  paged:
+    x-page-size: 10
+/books:
+  get:
+    x-internal:
+      reason: unreleased
+    is: [paged]";
+    let options = ParseOptions {
+        unknown_keys: UnknownKeyBehaviour::Warn,
+        ..ParseOptions::default()
+    };
+    let (_, warnings) = RamlParser::load_from_str_with_options_and_diagnostics(s, &options)
+        .unwrap();
+    assert!(warnings.iter().all(|w| w.kind() == WarningKind::UnknownKey));
+    let found: Vec<(&str, Option<usize>, Option<usize>)> =
+        warnings.iter().map(|w| (w.message(), w.line(), w.column())).collect();
+    assert_eq!(vec![("Unknown field ignored at the document root: x-owner", Some(3), Some(1)),
+                    ("Unknown field ignored at the trait: x-page-size", Some(6), Some(5)),
+                    ("Unknown field ignored at the method: x-internal", Some(9), Some(5))],
+               found);
+}
+
+#[test]
+fn warning_for_wildcard_media_type() {
+    let s = "#%RAML 1.0
+title: Some API
+mediaType: [application/json, '*/*']";
+    let warnings = load_warnings(s);
+    assert_eq!(1, warnings.len());
+    assert_eq!(WarningKind::WildcardMediaType, warnings[0].kind());
+    assert_eq!("The default media type */* is a wildcard", warnings[0].message());
+}